@@ -1,4 +1,3 @@
-use web_sys::console;
 use yew::{Component, ComponentLink, Html, ShouldRender};
 
 const HTML: &str = include_str!("document.html");
@@ -24,12 +23,7 @@ impl Component for Model {
     }
 
     fn view(&self) -> Html {
-        let div = yew::utils::document().create_element("div").unwrap();
-        div.set_inner_html(HTML);
-        // See <https://github.com/yewstack/yew/issues/1546>
-        console::log_1(&div);
-
-        Html::VRef(div.into())
+        Html::from_html_unchecked(HTML)
     }
 }
 
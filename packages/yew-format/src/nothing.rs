@@ -7,8 +7,8 @@ use anyhow::bail;
 #[derive(Debug)]
 pub struct Nothing;
 
-impl Into<Text> for Nothing {
-    fn into(self) -> Text {
+impl From<Nothing> for Text {
+    fn from(_: Nothing) -> Text {
         bail!("nothing")
     }
 }
@@ -19,8 +19,8 @@ impl From<Text> for Nothing {
     }
 }
 
-impl Into<Binary> for Nothing {
-    fn into(self) -> Binary {
+impl From<Nothing> for Binary {
+    fn from(_: Nothing) -> Binary {
         bail!("nothing")
     }
 }
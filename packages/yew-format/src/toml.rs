@@ -5,14 +5,14 @@
 ///
 /// ```
 /// // Converts (lazy) data to a Toml
-///# use yew::format::Toml;
+///# use yew_format::Toml;
 ///# fn dont_execute() {
 ///# let data: String = unimplemented!();
 /// let dump = Toml(&data);
 ///
 /// // Converts TOML string to a data (lazy).
 /// let Toml(data) = dump;
-/// }
+///# }
 /// ```
 #[derive(Debug)]
 pub struct Toml<T>(pub T);
@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use std::ops::DerefMut;
 use std::rc::Rc;
 use yew::html::AnyScope;
+use yew::request::RequestContext;
 use yew::{Component, ComponentLink, Html, Properties};
 
 mod use_context_hook;
@@ -37,7 +38,7 @@ pub use use_context_hook::*;
 pub use yew_functional_macro::function_component;
 
 thread_local! {
-    static CURRENT_HOOK: RefCell<Option<HookState>> = RefCell::new(None);
+    static CURRENT_HOOK: RefCell<Option<HookState>> = const { RefCell::new(None) };
 }
 
 pub trait Hook {
@@ -108,7 +109,7 @@ where
         let scope = AnyScope::from(link.clone());
         let message_queue = MsgQueue::default();
         Self {
-            _never: std::marker::PhantomData::default(),
+            _never: std::marker::PhantomData,
             props,
             link: link.clone(),
             message_queue: message_queue.clone(),
@@ -213,7 +214,7 @@ where
     struct UseReducerState<State> {
         current_state: Rc<State>,
     }
-    impl<T> Hook for UseReducerState<T> {};
+    impl<T> Hook for UseReducerState<T> {}
     let init = Box::new(init);
     let reducer = Rc::new(reducer);
     use_hook(
@@ -357,7 +358,15 @@ where
     );
 }
 
-pub fn use_hook<InternalHookState, HookRunner, R, InitialStateProvider, HookUpdate: 'static>(
+/// Returns the request the component is rendered for on the server, `None` on the client.
+///
+/// The request is the one passed to `App::with_request` or `Runtime::with_request`, so headers,
+/// cookies or the preferred language don't have to be passed down through props.
+pub fn use_request_context() -> Option<Rc<RequestContext>> {
+    get_current_scope().and_then(|scope| scope.request_context())
+}
+
+pub fn use_hook<InternalHookState, HookRunner, R, InitialStateProvider, HookUpdate>(
     hook_runner: HookRunner,
     initial_state_producer: InitialStateProvider,
 ) -> R
@@ -365,13 +374,13 @@ where
     HookRunner: FnOnce(&mut InternalHookState, Box<dyn Fn(HookUpdate, bool)>) -> R,
     InternalHookState: Hook + 'static,
     InitialStateProvider: FnOnce() -> InternalHookState,
-    HookUpdate: FnOnce(&mut InternalHookState) -> bool,
+    HookUpdate: FnOnce(&mut InternalHookState) -> bool + 'static,
 {
     // Extract current hook
     let (hook, process_message) = CURRENT_HOOK.with(|hook_state_holder| {
         let hook_state_holder = hook_state_holder.try_borrow_mut();
         let mut hook_state_holder = hook_state_holder.expect("Nested hooks not supported");
-        let mut hook_state = hook_state_holder
+        let hook_state = hook_state_holder
             .as_mut()
             .expect("No current hook. Hooks can only be called inside function components");
 
@@ -412,12 +421,11 @@ where
     };
     let mut hook = hook.borrow_mut();
     let hook = hook.downcast_mut::<InternalHookState>();
-    let mut hook =
-        hook.expect("Incompatible hook type. Hooks must always be called in the same order");
+    let hook = hook.expect("Incompatible hook type. Hooks must always be called in the same order");
 
     // Execute the actual hook closure we were given. Let it mutate the hook state and let
     // it create a callback that takes the mutable hook state.
-    hook_runner(&mut hook, hook_callback)
+    hook_runner(hook, hook_callback)
 }
 
 pub(crate) fn get_current_scope() -> Option<AnyScope> {
@@ -1,3 +1,5 @@
+#![cfg(target_arch = "wasm32")]
+
 extern crate wasm_bindgen_test;
 
 use std::ops::Deref;
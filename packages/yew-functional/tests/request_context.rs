@@ -0,0 +1,39 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use yew::http::{HeaderMap, HeaderValue, Method, Uri};
+use yew::request::RequestContext;
+use yew::{html, App, Html};
+use yew_functional::{use_request_context, FunctionComponent, FunctionProvider};
+
+struct LanguageFunction;
+
+impl FunctionProvider for LanguageFunction {
+    type TProps = ();
+
+    fn run(_: &Self::TProps) -> Html {
+        let language = use_request_context()
+            .and_then(|request| request.header("accept-language").map(str::to_owned))
+            .unwrap_or_else(|| "unknown".to_owned());
+        html! { <p>{ language }</p> }
+    }
+}
+
+type Language = FunctionComponent<LanguageFunction>;
+
+#[test]
+fn use_request_context_returns_the_request() {
+    let mut headers = HeaderMap::new();
+    headers.insert("accept-language", HeaderValue::from_static("de"));
+    let request = RequestContext::new(Method::GET, Uri::from_static("/"), headers);
+    let html = App::<Language>::new()
+        .with_request(request)
+        .render_to_string()
+        .unwrap();
+    assert_eq!(html.as_str(), "<p>de</p>");
+}
+
+#[test]
+fn use_request_context_is_none_without_a_request() {
+    let html = App::<Language>::new().render_to_string().unwrap();
+    assert_eq!(html.as_str(), "<p>unknown</p>");
+}
@@ -1,3 +1,5 @@
+#![cfg(target_arch = "wasm32")]
+
 extern crate wasm_bindgen_test;
 
 use std::rc::Rc;
@@ -34,10 +36,10 @@ fn use_context_scoping_works() {
 
         fn run(_props: &Self::TProps) -> Html {
             if use_context::<ExampleContext>().is_some() {
-                yew::services::ConsoleService::log(&format!(
+                console_log!(
                     "Context should be None here, but was {:?}!",
                     use_context::<ExampleContext>().unwrap()
-                ));
+                );
             };
             return html! {
                 <div></div>
@@ -57,13 +57,13 @@ impl ToTokens for DerivePropsInput {
 
         // The wrapper is a new struct which wraps required props in `Option`
         let wrapper_name = Ident::new(&format!("{}Wrapper", props_name), Span::call_site());
-        let wrapper = PropsWrapper::new(&wrapper_name, &generics, &self.prop_fields);
+        let wrapper = PropsWrapper::new(&wrapper_name, generics, &self.prop_fields);
         tokens.extend(wrapper.into_token_stream());
 
         // The builder will only build if all required props have been set
         let builder_name = Ident::new(&format!("{}Builder", props_name), Span::call_site());
         let builder_step = Ident::new(&format!("{}BuilderStep", props_name), Span::call_site());
-        let builder = PropsBuilder::new(&builder_name, &builder_step, &self, &wrapper_name);
+        let builder = PropsBuilder::new(&builder_name, &builder_step, self, &wrapper_name);
         let builder_generic_args = builder.first_step_generic_args();
         tokens.extend(builder.into_token_stream());
 
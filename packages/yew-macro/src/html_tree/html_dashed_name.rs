@@ -11,11 +11,29 @@ use syn::{spanned::Spanned, LitStr, Token};
 
 #[derive(Clone, PartialEq)]
 pub struct HtmlDashedName {
+    /// The namespace prefix of an attribute, e.g. `xlink` in `xlink:href`
+    pub prefix: Option<(Ident, Token![:])>,
     pub name: Ident,
     pub extended: Vec<(Token![-], Ident)>,
 }
 
 impl HtmlDashedName {
+    /// Parses the name which follows the namespace prefix `self`, e.g. `href` in `xlink:href`.
+    pub fn parse_prefixed(self, input: ParseStream) -> syn::Result<Self> {
+        if self.prefix.is_some() || !self.extended.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self,
+                "expected a namespace prefix like `xlink`",
+            ));
+        }
+        let colon = input.parse::<Token![:]>()?;
+        let name = input.parse::<HtmlDashedName>()?;
+        Ok(HtmlDashedName {
+            prefix: Some((self.name, colon)),
+            ..name
+        })
+    }
+
     pub fn to_ascii_lowercase_string(&self) -> String {
         let mut s = self.to_string();
         s.make_ascii_lowercase();
@@ -29,6 +47,9 @@ impl HtmlDashedName {
 
 impl fmt::Display for HtmlDashedName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((prefix, _)) = &self.prefix {
+            write!(f, "{}:", prefix)?;
+        }
         write!(f, "{}", self.name)?;
         for (_, ident) in &self.extended {
             write!(f, "-{}", ident)?;
@@ -56,7 +77,14 @@ impl Peek<'_, Self> for HtmlDashedName {
             break;
         }
 
-        Some((HtmlDashedName { name, extended }, cursor))
+        Some((
+            HtmlDashedName {
+                prefix: None,
+                name,
+                extended,
+            },
+            cursor,
+        ))
     }
 }
 
@@ -68,17 +96,28 @@ impl Parse for HtmlDashedName {
             extended.push((input.parse::<Token![-]>()?, input.parse::<Ident>()?));
         }
 
-        Ok(HtmlDashedName { name, extended })
+        Ok(HtmlDashedName {
+            prefix: None,
+            name,
+            extended,
+        })
     }
 }
 
 impl ToTokens for HtmlDashedName {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let HtmlDashedName { name, extended } = self;
+        let HtmlDashedName {
+            prefix,
+            name,
+            extended,
+        } = self;
+        let prefix = prefix
+            .as_ref()
+            .map(|(prefix, colon)| quote! { #prefix#colon });
         let dashes = extended.iter().map(|(dash, _)| quote! {#dash});
         let idents = extended.iter().map(|(_, ident)| quote! {#ident});
         let extended = quote! { #(#dashes#idents)* };
-        tokens.extend(quote! { #name#extended });
+        tokens.extend(quote! { #prefix#name#extended });
     }
 }
 impl Stringify for HtmlDashedName {
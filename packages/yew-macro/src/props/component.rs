@@ -14,7 +14,6 @@ mod kw {
 
 pub struct WithProps {
     pub special: SpecialProps,
-    pub with: kw::with,
     pub expr: Expr,
 }
 impl WithProps {
@@ -53,7 +52,7 @@ impl Parse for WithProps {
                     if slot.is_some() {
                         return Err(syn::Error::new_spanned(
                             &prop.label,
-                            &format!("`{}` can only be set once", prop.label),
+                            format!("`{}` can only be set once", prop.label),
                         ));
                     }
                     slot.replace(prop);
@@ -66,17 +65,13 @@ impl Parse for WithProps {
             }
         }
 
-        let (with, expr) =
-            with_expr.ok_or_else(|| input.error("missing `with props` expression"))?;
+        let (_, expr) = with_expr.ok_or_else(|| input.error("missing `with props` expression"))?;
 
-        Ok(Self {
-            special,
-            with,
-            expr,
-        })
+        Ok(Self { special, expr })
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum ComponentProps {
     List(Props),
     With(Box<WithProps>),
@@ -113,7 +108,7 @@ impl ComponentProps {
         };
 
         quote_spanned! {props_ty.span()=>
-            #[allow(clippy::no_effect)]
+            #[allow(clippy::no_effect, clippy::unnecessary_operation)]
             if false {
                 let _ = |__yew_props: #props_ty| {
                     #check_children
@@ -140,13 +135,11 @@ impl ComponentProps {
                         )}
                     });
 
-                let set_children = if let Some(children) = children_renderer {
-                    Some(quote_spanned! {props_ty.span()=>
+                let set_children = children_renderer.map(|children| {
+                    quote_spanned! {props_ty.span()=>
                         .children(#children)
-                    })
-                } else {
-                    None
-                };
+                    }
+                });
 
                 quote_spanned! {props_ty.span()=>
                     <#props_ty as ::yew::html::Properties>::builder()
@@ -157,13 +150,11 @@ impl ComponentProps {
             }
             Self::With(with_props) => {
                 let ident = Ident::new("__yew_props", props_ty.span());
-                let set_children = if let Some(children) = children_renderer {
-                    Some(quote_spanned! {props_ty.span()=>
+                let set_children = children_renderer.map(|children| {
+                    quote_spanned! {props_ty.span()=>
                         #ident.children = #children;
-                    })
-                } else {
-                    None
-                };
+                    }
+                });
 
                 let expr = &with_props.expr;
                 quote! {
@@ -203,7 +194,7 @@ impl TryFrom<Props> for ComponentProps {
                     &prop.label,
                     "optional attributes are only supported on elements. Components can use `Option<T>` properties to accomplish the same thing.",
                 ))
-            } else if !prop.label.extended.is_empty() {
+            } else if prop.label.prefix.is_some() || !prop.label.extended.is_empty() {
                 Err(syn::Error::new_spanned(
                     &prop.label,
                     "expected a valid Rust identifier",
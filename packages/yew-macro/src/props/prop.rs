@@ -58,7 +58,11 @@ impl Prop {
 }
 impl Parse for Prop {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let label = input.parse::<HtmlDashedName>()?;
+        let mut label = input.parse::<HtmlDashedName>()?;
+        // Attributes of SVG elements can have a namespace prefix, e.g. `xlink:href`
+        if input.peek(Token![:]) {
+            label = label.parse_prefixed(input)?;
+        }
         let question_mark = input.parse::<Token![?]>().ok();
         let equals = input.parse::<Token![=]>().map_err(|_| {
             syn::Error::new_spanned(
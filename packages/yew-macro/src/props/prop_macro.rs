@@ -64,13 +64,13 @@ impl Parse for PropValue {
     }
 }
 
-impl Into<Prop> for PropValue {
-    fn into(self) -> Prop {
-        let Self {
+impl From<PropValue> for Prop {
+    fn from(val: PropValue) -> Self {
+        let PropValue {
             label,
             colon_token,
             value,
-        } = self;
+        } = val;
         Prop {
             label,
             question_mark: None,
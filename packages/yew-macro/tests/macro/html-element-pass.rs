@@ -30,6 +30,7 @@ fn compile_pass() {
                     <circle cx="75.3326" cy="73.4918" r="52.5" stroke="black" stroke-width="5"/>
                 </g>
                 <circle cx="71" cy="99" r="5" fill="white" fill-opacity="0.75" stroke="black" stroke-width="3"/>
+                <use xlink:href="#filter0_d" />
                 <defs>
                     <filter id="filter0_d" x="16.3326" y="18.4918" width="118" height="118" filterUnits="userSpaceOnUse" color-interpolation-filters="sRGB">
                         <feGaussianBlur stdDeviation="2"/>
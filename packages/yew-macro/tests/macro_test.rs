@@ -30,7 +30,7 @@ fn tests() {
     expected = "a dynamic tag tried to create a `<br>` tag with children. `<br>` is a void element which can't have any children."
 )]
 fn dynamic_tags_catch_void_elements() {
-    html! {
+    let _ = html! {
         <@{"br"}>
             <span>{ "No children allowed" }</span>
         </@>
@@ -40,7 +40,7 @@ fn dynamic_tags_catch_void_elements() {
 #[test]
 #[should_panic(expected = "a dynamic tag returned a tag name containing non ASCII characters: `❤`")]
 fn dynamic_tags_catch_non_ascii() {
-    html! {
+    let _ = html! {
         <@{"❤"}/>
     };
 }
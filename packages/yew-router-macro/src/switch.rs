@@ -34,8 +34,7 @@ pub fn switch_impl(input: DeriveInput) -> syn::Result<TokenStream> {
             let matcher = AttrToken::convert_attributes_to_tokens(input.attrs)?
                 .into_iter()
                 .enumerate()
-                .map(|(index, at)| at.into_shadow_matcher_tokens(index, field_naming_scheme))
-                .flatten()
+                .flat_map(|(index, at)| at.into_shadow_matcher_tokens(index, field_naming_scheme))
                 .collect::<Vec<_>>();
 
             let item = SwitchItem {
@@ -70,8 +69,7 @@ pub fn switch_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                     let matcher = AttrToken::convert_attributes_to_tokens(variant.attrs)?
                         .into_iter()
                         .enumerate()
-                        .map(|(index, at)| at.into_shadow_matcher_tokens(index, field_type))
-                        .flatten()
+                        .flat_map(|(index, at)| at.into_shadow_matcher_tokens(index, field_type))
                         .collect::<Vec<_>>();
                     Ok(SwitchItem {
                         matcher,
@@ -102,22 +100,6 @@ pub fn switch_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     })
 }
 
-trait Flatten<T> {
-    /// Because flatten is a nightly feature. I'm making a new variant of the function here for
-    /// stable use. The naming is changed to avoid this getting clobbered when object_flattening
-    /// 60258 is stabilized.
-    fn flatten_stable(self) -> Option<T>;
-}
-
-impl<T> Flatten<T> for Option<Option<T>> {
-    fn flatten_stable(self) -> Option<T> {
-        match self {
-            None => None,
-            Some(v) => v,
-        }
-    }
-}
-
 fn build_matcher_from_tokens(tokens: &[ShadowMatcherToken]) -> TokenStream {
     quote! {
         let settings = ::yew_router::matcher::MatcherSettings {
@@ -150,7 +132,7 @@ fn write_for_token(token: &ShadowMatcherToken, naming_scheme: FieldType) -> Toke
                 ShadowCaptureVariant::Named(name)
                 | ShadowCaptureVariant::ManyNamed(name)
                 | ShadowCaptureVariant::NumberedNamed { name, .. } => {
-                    let name = Ident::new(&name, Span::call_site());
+                    let name = Ident::new(name, Span::call_site());
                     quote! {
                         state = state.or_else(|| #name.build_route_section(buf));
                     }
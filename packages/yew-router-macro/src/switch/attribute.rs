@@ -48,7 +48,7 @@ impl AttrToken {
                             .and_then(|ident| match ident.to_string().as_str() {
                                 id @ "to" | id @ "rest" => Some(Err(syn::Error::new(
                                     meta_span,
-                                    &format!(
+                                    format!(
                                         "This syntax is not supported, did you mean `#[{} = ...]`?",
                                         id
                                     ),
@@ -16,8 +16,8 @@ impl<'a> ToTokens for FromRoutePart<'a> {
                 ident,
                 fields,
             } = sv;
-            let build_from_captures = build_variant_from_captures(&self.enum_ident, ident, fields);
-            let matcher = super::super::build_matcher_from_tokens(&matcher);
+            let build_from_captures = build_variant_from_captures(self.enum_ident, ident, fields);
+            let matcher = super::super::build_matcher_from_tokens(matcher);
 
             quote! {
                 #matcher
@@ -14,7 +14,7 @@ impl<'a> ToTokens for FromRoutePart<'a> {
             fields,
         } = &self.0;
 
-        let matcher = super::super::build_matcher_from_tokens(&matcher);
+        let matcher = super::super::build_matcher_from_tokens(matcher);
         let build_from_captures = build_struct_from_captures(ident, fields);
 
         tokens.extend(quote! {
@@ -150,7 +150,7 @@ fn build_struct_from_captures(ident: &Ident, fields: &Fields) -> TokenStream {
             }
         }
         Fields::Unit => {
-            return quote! {
+            quote! {
                 let mut state = if let ::std::option::Option::Some(_captures) = matcher.capture_route_into_map(&route_string).ok().map(|x| x.1) {
                     return (::std::option::Option::Some(#ident), state);
                 } else {
@@ -27,31 +27,31 @@ pub enum FieldNamingScheme {
     Unit,
 }
 
-pub fn get_slash(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn get_slash(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(char('/'), |_: char| RouteParserToken::Separator)(i)
         .map_err(|_: nom::Err<()>| nom::Err::Error(ParseError::expected(ExpectedToken::Separator)))
 }
 
-pub fn get_question(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn get_question(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(char('?'), |_: char| RouteParserToken::QueryBegin)(i)
         .map_err(|_: nom::Err<()>| nom::Err::Error(ParseError::expected(ExpectedToken::QueryBegin)))
 }
 
-pub fn get_and(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn get_and(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(char('&'), |_: char| RouteParserToken::QuerySeparator)(i).map_err(|_: nom::Err<()>| {
         nom::Err::Error(ParseError::expected(ExpectedToken::QuerySeparator))
     })
 }
 
 /// Returns a FragmentBegin variant if the next character is '\#'.
-pub fn get_hash(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn get_hash(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(char('#'), |_: char| RouteParserToken::FragmentBegin)(i).map_err(|_: nom::Err<()>| {
         nom::Err::Error(ParseError::expected(ExpectedToken::FragmentBegin))
     })
 }
 
 /// Returns an End variant if the next character is a '!`.
-pub fn get_end(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn get_end(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(char('!'), |_: char| RouteParserToken::End)(i)
         .map_err(|_: nom::Err<()>| nom::Err::Error(ParseError::expected(ExpectedToken::End)))
 }
@@ -126,7 +126,7 @@ fn escaped_item_impl(i: &str) -> IResult<&str, &str> {
 }
 
 /// Matches "".
-pub fn nothing(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn nothing(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     if i.is_empty() {
         Ok((i, RouteParserToken::Nothing))
     } else {
@@ -168,12 +168,12 @@ fn exact_impl(special_chars: &'static str) -> impl Fn(&str) -> IResult<&str, &st
 const SPECIAL_CHARS: &str = r##"/?&#={}!"##;
 const FRAGMENT_SPECIAL_CHARS: &str = r##"{}!"##;
 
-pub fn exact(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn exact(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(exact_impl(SPECIAL_CHARS), RouteParserToken::Exact)(i)
 }
 
 /// More permissive exact matchers
-pub fn fragment_exact(i: &str) -> IResult<&str, RouteParserToken, ParseError> {
+pub fn fragment_exact(i: &str) -> IResult<&str, RouteParserToken<'_>, ParseError> {
     map(exact_impl(FRAGMENT_SPECIAL_CHARS), RouteParserToken::Exact)(i)
 }
 
@@ -244,18 +244,18 @@ fn capture_impl<'a>(
 
 mod named {
     use super::*;
-    pub fn single_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
-        map(rust_ident, |key| RefCaptureVariant::Named(key))(i)
+    pub fn single_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
+        map(rust_ident, RefCaptureVariant::Named)(i)
     }
 
-    pub fn many_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
+    pub fn many_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
         map(
             separated_pair(get_star, get_colon, rust_ident),
             |(_, key)| RefCaptureVariant::ManyNamed(key),
         )(i)
     }
 
-    pub fn numbered_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
+    pub fn numbered_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
         map(
             separated_pair(digit1, get_colon, rust_ident),
             |(number, key)| RefCaptureVariant::NumberedNamed {
@@ -271,15 +271,15 @@ mod unnamed {
 
     /// #Note
     /// because this always succeeds, try this last
-    pub fn single_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
+    pub fn single_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
         Ok((i, RefCaptureVariant::Unnamed))
     }
 
-    pub fn many_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
+    pub fn many_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
         map(get_star, |_| RefCaptureVariant::ManyUnnamed)(i)
     }
 
-    pub fn numbered_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant, ParseError> {
+    pub fn numbered_capture_impl(i: &str) -> IResult<&str, RefCaptureVariant<'_>, ParseError> {
         map(digit1, |number: &str| RefCaptureVariant::NumberedUnnamed {
             sections: number.parse().unwrap(),
         })(i)
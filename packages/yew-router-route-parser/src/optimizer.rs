@@ -36,7 +36,7 @@ impl<'a> RouteParserToken<'a> {
     fn as_str(&self) -> &str {
         match self {
             RouteParserToken::Separator => "/",
-            RouteParserToken::Exact(literal) => &literal,
+            RouteParserToken::Exact(literal) => literal,
             RouteParserToken::QueryBegin => "?",
             RouteParserToken::QuerySeparator => "&",
             RouteParserToken::FragmentBegin => "#",
@@ -52,7 +52,7 @@ impl<'a> RouteParserToken<'a> {
 pub fn parse_str_and_optimize_tokens(
     i: &str,
     field_naming_scheme: FieldNamingScheme,
-) -> Result<Vec<MatcherToken>, PrettyParseError> {
+) -> Result<Vec<MatcherToken>, PrettyParseError<'_>> {
     let tokens = parse(i, field_naming_scheme)?;
     Ok(convert_tokens(&tokens))
 }
@@ -114,7 +114,7 @@ pub fn convert_tokens(tokens: &[RouteParserToken]) -> Vec<MatcherToken> {
                     run.push(RouteParserToken::Exact(s));
                 }
                 CaptureOrExact::Capture(cap) => {
-                    new_tokens.push(empty_run_with_query_cap_at_end(&mut run, *ident));
+                    new_tokens.push(empty_run_with_query_cap_at_end(&mut run, ident));
                     new_tokens.push(MatcherToken::Capture(CaptureVariant::from(*cap)))
                 }
             },
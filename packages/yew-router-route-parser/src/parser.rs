@@ -216,7 +216,7 @@ impl<'a> ParserState<'a> {
 pub fn parse(
     mut i: &str,
     field_naming_scheme: FieldNamingScheme,
-) -> Result<Vec<RouteParserToken>, PrettyParseError> {
+) -> Result<Vec<RouteParserToken<'_>>, PrettyParseError<'_>> {
     let input = i;
     let mut tokens: Vec<RouteParserToken> = vec![];
     let mut state = ParserState::None;
@@ -413,7 +413,7 @@ mod test {
     use crate::{parser::RouteParserToken, FieldNamingScheme, PrettyParseError};
 
     // Call all tests to parse with the Unnamed variant
-    fn parse(i: &str) -> Result<Vec<RouteParserToken>, PrettyParseError> {
+    fn parse(i: &str) -> Result<Vec<RouteParserToken<'_>>, PrettyParseError<'_>> {
         actual_parse(i, FieldNamingScheme::Unnamed)
     }
 
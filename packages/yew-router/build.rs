@@ -0,0 +1,16 @@
+use std::env;
+
+/// Selects the history the router uses, which the crate checks with `cfg(yew_backend = "...")`.
+///
+/// The router has to use the backend yew was built with, so it takes the choice yew's build
+/// script publishes through the `links` metadata instead of looking at its own features:
+/// browsers use the History API of `web_sys`, everywhere else the in-memory history is used if
+/// yew renders to the in-memory DOM.
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(yew_backend, values(\"web_sys\", \"static_render\"))");
+    println!("cargo:rerun-if-env-changed=DEP_YEW_BACKEND");
+
+    if let Ok(backend) = env::var("DEP_YEW_BACKEND") {
+        println!("cargo:rustc-cfg=yew_backend=\"{}\"", backend);
+    }
+}
@@ -52,7 +52,7 @@ impl<SW: Switch + Clone + 'static, STATE: RouterState> Component for RouterButto
             event.prevent_default();
             Msg::Clicked
         });
-        #[cfg(feature = "web_sys")]
+        #[cfg(any(feature = "web_sys", feature = "static_render"))]
         let cb = self.link.callback(|event: MouseEvent| {
             event.prevent_default();
             Msg::Clicked
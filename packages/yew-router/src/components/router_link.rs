@@ -64,7 +64,7 @@ impl<SW: Switch + Clone + 'static, STATE: RouterState> Component for RouterAncho
             event.prevent_default();
             Msg::Clicked
         });
-        #[cfg(feature = "web_sys")]
+        #[cfg(any(feature = "web_sys", feature = "static_render"))]
         let cb = self.link.callback(|event: MouseEvent| {
             event.prevent_default();
             Msg::Clicked
@@ -10,9 +10,9 @@
 //! * RouteAgent - A singleton agent that owns a RouteService that provides an easy place for other
 //!   components and agents to hook into it.
 //! * Switch - A trait/derive macro that allows specification of how enums or structs can be constructed
-//! from Routes.
+//!   from Routes.
 //! * Router - A component connected to the RouteAgent, and is capable of resolving Routes to
-//! Switch implementors, so you can use them to render Html.
+//!   Switch implementors, so you can use them to render Html.
 //! * Route - A struct containing an the route string and state.
 //! * RouteButton & RouteLink - Wrapper components around buttons and anchor tags respectively that
 //!   allow users to change the route.
@@ -31,15 +31,18 @@
 //!
 //! ## Features
 //! This crate has some feature-flags that allow you to not include some parts in your compilation.
-//! * "default" - Everything is included by default.
+//! * "default" - Everything, backed by the History API of the browser ("web_sys").
 //! * "core" - The fully feature complete ("router", "components", "matchers"), but without
 //!   unit_alias.
 //! * "unit_alias" - If enabled, a module will be added to the route and expanded within the prelude
-//! for aliases of Router types to their `()` variants.
+//!   for aliases of Router types to their `()` variants.
 //! * "router" - If enabled, the Router component and its dependent infrastructure (including
 //!   "agent") will be included.
 //! * "agent" - If enabled, the RouteAgent and its associated types will be included.
 //! * "components" - If enabled, the accessory components will be made available.
+//! * "static_render" - If enabled, Yew renders to its in-memory DOM outside of browsers and the
+//!   RouteService keeps an in-memory history there, which is seeded with the URL of a request, so
+//!   routes can be rendered on the server or in native tests.
 
 #![deny(
     missing_docs,
@@ -108,8 +111,6 @@ pub mod prelude {
     pub use yew_router_macro::Switch;
 }
 
-pub use alias::*;
-
 pub mod matcher;
 
 pub use matcher::Captures;
@@ -17,7 +17,6 @@ use yew_router_route_parser::{CaptureVariant, MatcherToken};
 trait CaptureCollection<'a> {
     fn new2() -> Self;
     fn insert2(&mut self, key: &'a str, value: String);
-    fn extend2(&mut self, other: Self);
 }
 
 impl<'a> CaptureCollection<'a> for Captures<'a> {
@@ -28,10 +27,6 @@ impl<'a> CaptureCollection<'a> for Captures<'a> {
     fn insert2(&mut self, key: &'a str, value: String) {
         self.insert(key, value);
     }
-
-    fn extend2(&mut self, other: Self) {
-        self.extend(other)
-    }
 }
 
 impl<'a> CaptureCollection<'a> for Vec<String> {
@@ -42,10 +37,6 @@ impl<'a> CaptureCollection<'a> for Vec<String> {
     fn insert2(&mut self, _key: &'a str, value: String) {
         self.push(value)
     }
-
-    fn extend2(&mut self, other: Self) {
-        self.extend(other)
-    }
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -82,12 +73,12 @@ fn matcher_impl<'a, 'b: 'a, CAP: CaptureCollection<'b>>(
                 tag_possibly_case_sensitive(literal.as_str(), !settings.case_insensitive)(i)?.0
             }
             MatcherToken::Capture(capture) => match &capture {
-                CaptureVariant::Named(name) => capture_named(i, &mut iter, &name, &mut captures)?,
+                CaptureVariant::Named(name) => capture_named(i, &mut iter, name, &mut captures)?,
                 CaptureVariant::ManyNamed(name) => {
-                    capture_many_named(i, &mut iter, &name, &mut captures)?
+                    capture_many_named(i, &mut iter, name, &mut captures)?
                 }
                 CaptureVariant::NumberedNamed { sections, name } => {
-                    capture_numbered_named(i, &mut iter, Some((&name, &mut captures)), *sections)?
+                    capture_numbered_named(i, &mut iter, Some((name, &mut captures)), *sections)?
                 }
                 CaptureVariant::Unnamed => capture_named(i, &mut iter, "", &mut captures)?,
                 CaptureVariant::ManyUnnamed => capture_many_named(i, &mut iter, "", &mut captures)?,
@@ -139,15 +130,15 @@ fn capture_many_named<'a, 'b, CAP: CaptureCollection<'b>>(
     if let Some(_peaked_next_token) = iter.peek() {
         let delimiter = next_delimiter(iter);
         let (ii, captured) = consume_until(delimiter)(i)?;
-        matches.insert2(&capture_key, captured);
+        matches.insert2(capture_key, captured);
         Ok(ii)
     } else if i.is_empty() {
         // If the route string is empty, return an empty value.
-        matches.insert2(&capture_key, "".to_string());
+        matches.insert2(capture_key, "".to_string());
         Ok(i) // Match even if nothing is left
     } else {
         let (ii, c) = map(valid_many_capture_characters, String::from)(i)?;
-        matches.insert2(&capture_key, c);
+        matches.insert2(capture_key, c);
         Ok(ii)
     }
 }
@@ -194,7 +185,7 @@ fn capture_numbered_named<'a, 'b, CAP: CaptureCollection<'b>>(
     }
 
     if let Some((name, captures)) = name_and_captures {
-        captures.insert2(&name, captured);
+        captures.insert2(name, captured);
     }
     Ok(i)
 }
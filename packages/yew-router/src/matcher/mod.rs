@@ -20,29 +20,21 @@ pub struct RouteMatcher {
 }
 
 /// Settings used for the matcher.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub struct MatcherSettings {
     /// All literal matches do not care about case.
     pub case_insensitive: bool,
 }
 
-impl Default for MatcherSettings {
-    fn default() -> Self {
-        MatcherSettings {
-            case_insensitive: false,
-        }
-    }
-}
-
 impl RouteMatcher {
     /// Attempt to create a RouteMatcher from a "matcher string".
-    pub fn try_from(i: &str) -> Result<Self, PrettyParseError> {
+    pub fn try_from(i: &str) -> Result<Self, PrettyParseError<'_>> {
         let settings = MatcherSettings::default();
         Self::new(i, settings)
     }
 
     /// Creates a new Matcher with settings.
-    pub fn new(i: &str, settings: MatcherSettings) -> Result<Self, PrettyParseError> {
+    pub fn new(i: &str, settings: MatcherSettings) -> Result<Self, PrettyParseError<'_>> {
         Ok(RouteMatcher {
             tokens: parse_str_and_optimize_tokens(
                 i,
@@ -82,7 +74,7 @@ impl RouteMatcher {
                             CaptureVariant::ManyNamed(name)
                             | CaptureVariant::Named(name)
                             | CaptureVariant::NumberedNamed { name, .. } => {
-                                acc.insert(&name);
+                                acc.insert(name);
                             }
                             CaptureVariant::Unnamed
                             | CaptureVariant::ManyUnnamed
@@ -28,9 +28,15 @@ impl<T> RouteState for T where
 }
 
 /// Any state that can be used in the router agent must meet the criteria of this trait.
-#[cfg(all(feature = "service", feature = "web_sys"))]
+#[cfg(all(
+    feature = "service",
+    any(feature = "web_sys", feature = "static_render")
+))]
 pub trait RouteState: Serialize + DeserializeOwned + Debug + Clone + Default + 'static {}
-#[cfg(all(feature = "service", feature = "web_sys"))]
+#[cfg(all(
+    feature = "service",
+    any(feature = "web_sys", feature = "static_render")
+))]
 impl<T> RouteState for T where T: Serialize + DeserializeOwned + Debug + Clone + Default + 'static {}
 
 /// The representation of a route, segmented into different sections for easy access.
@@ -66,7 +72,7 @@ impl<STATE: Default> Route<STATE> {
 
 impl<STATE> fmt::Display for Route<STATE> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        std::fmt::Display::fmt(&self.route, f)
+        fmt::Display::fmt(&self.route, f)
     }
 }
 
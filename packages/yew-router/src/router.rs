@@ -9,7 +9,12 @@ use std::{
     fmt::{self, Debug, Error as FmtError, Formatter},
     rc::Rc,
 };
-use yew::{html, virtual_dom::VNode, Component, ComponentLink, Html, Properties, ShouldRender};
+use yew::{
+    html,
+    response::{ResponseContext, StatusCode},
+    virtual_dom::VNode,
+    Component, ComponentLink, Html, Properties, ShouldRender,
+};
 
 /// Any state that can be managed by the `Router` must meet the criteria of this trait.
 pub trait RouterState: RouteState + PartialEq {}
@@ -62,6 +67,8 @@ impl<STATE> RouterState for STATE where STATE: RouteState + PartialEq {}
 #[derive(Debug)]
 pub struct Router<SW: Switch + Clone + 'static, STATE: RouterState = ()> {
     switch: Option<SW>,
+    /// The route if it didn't match the switch
+    unmatched: Option<Route<STATE>>,
     props: Props<STATE, SW>,
     router_agent: RouteAgentBridge<STATE>,
 }
@@ -100,6 +107,14 @@ where
     pub fn redirect<F: RedirectFn<SW, STATE> + 'static>(f: F) -> Option<Redirect<SW, STATE>> {
         Some(Redirect::new(f))
     }
+
+    /// Wrap a function which renders routes that don't match so that it can be used by the
+    /// Router.
+    pub fn not_found<F: NotFoundFn<Router<SW, STATE>, STATE> + 'static>(
+        f: F,
+    ) -> Option<NotFound<SW, STATE>> {
+        Some(NotFound::new(f))
+    }
 }
 
 /// Message for Router.
@@ -149,16 +164,41 @@ impl<STATE: RouterState, SW: Switch> Debug for Redirect<SW, STATE> {
     }
 }
 
+/// Render function that takes a route that didn't match any of the Switch variants and
+/// converts it to HTML.
+pub trait NotFoundFn<CTX: Component, STATE>: Fn(Route<STATE>) -> Html {}
+impl<T, CTX: Component, STATE> NotFoundFn<CTX, STATE> for T where T: Fn(Route<STATE>) -> Html {}
+/// Clonable NotFound function
+#[derive(Clone)]
+pub struct NotFound<SW: Switch + Clone + 'static, STATE: RouterState = ()>(
+    pub(crate) Rc<dyn NotFoundFn<Router<SW, STATE>, STATE>>,
+);
+impl<STATE: RouterState, SW: Switch + Clone> NotFound<SW, STATE> {
+    fn new<F: NotFoundFn<Router<SW, STATE>, STATE> + 'static>(f: F) -> Self {
+        NotFound(Rc::new(f))
+    }
+}
+impl<STATE: RouterState, SW: Switch + Clone> Debug for NotFound<SW, STATE> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotFound").finish()
+    }
+}
+
 /// Properties for Router.
 #[derive(Properties, Clone)]
 pub struct Props<STATE: RouterState, SW: Switch + Clone + 'static> {
     /// Render function that takes a Switch and produces Html
     pub render: Render<SW, STATE>,
     /// Optional redirect function that will convert the route to a known switch variant if explicit matching fails.
-    /// This should mostly be used to handle 404s and redirection.
+    /// The route is replaced with the redirected one, so on the server the page is answered with
+    /// `302 Found` and a `Location` header. Use `not_found` to keep the route instead.
     /// It is not strictly necessary as your Switch is capable of handling unknown routes using `#[to="/{*:any}"]`.
     #[prop_or_default]
     pub redirect: Option<Redirect<SW, STATE>>,
+    /// Optional render function for routes which don't match the Switch and aren't redirected.
+    /// The route is kept, so on the server the page is answered with `404 Not Found`.
+    #[prop_or_default]
+    pub not_found: Option<NotFound<SW, STATE>>,
 }
 
 impl<STATE: RouterState, SW: Switch + Clone> Debug for Props<STATE, SW> {
@@ -183,6 +223,7 @@ where
         Router {
             switch: Default::default(), /* This must be updated by immediately requesting a route
                                          * update from the service bridge. */
+            unmatched: None,
             props,
             router_agent,
         }
@@ -195,20 +236,31 @@ where
 
                 if switch.is_none() {
                     if let Some(redirect) = &self.props.redirect {
-                        let redirected: SW = (&redirect.0)(route);
+                        let redirected: SW = (redirect.0)(route.clone());
 
                         log::trace!(
                             "Route failed to match, but redirecting route to a known switch."
                         );
+                        let redirected_route: Route<STATE> = redirected.clone().into();
+                        // On the server the redirect becomes the response
+                        if let Some(response) = ResponseContext::current() {
+                            response.redirect(&redirected_route.route);
+                        }
                         // Replace the route in the browser with the redirected.
                         self.router_agent
-                            .send(RouteRequest::ReplaceRouteNoBroadcast(
-                                redirected.clone().into(),
-                            ));
+                            .send(RouteRequest::ReplaceRouteNoBroadcast(redirected_route));
                         switch = Some(redirected)
                     }
                 }
 
+                self.unmatched = None;
+                if switch.is_none() {
+                    if let Some(response) = ResponseContext::current() {
+                        response.set_status(StatusCode::NOT_FOUND);
+                    }
+                    self.unmatched = Some(route);
+                }
+
                 self.switch = switch;
                 true
             }
@@ -221,10 +273,13 @@ where
     }
 
     fn view(&self) -> VNode {
-        match self.switch.clone() {
-            Some(switch) => (&self.props.render.0)(switch),
-            None => {
-                log::warn!("No route matched, provide a redirect prop to the router to handle cases where no route can be matched");
+        if let Some(switch) = self.switch.clone() {
+            return (self.props.render.0)(switch);
+        }
+        match (&self.props.not_found, self.unmatched.clone()) {
+            (Some(not_found), Some(route)) => (not_found.0)(route),
+            _ => {
+                log::warn!("No route matched, provide a redirect or not_found prop to the router to handle cases where no route can be matched");
                 html! {"No route matched"}
             }
         }
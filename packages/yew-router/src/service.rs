@@ -15,10 +15,15 @@ cfg_if! {
             web::{event::PopStateEvent, window, EventListenerHandle, History, IEventTarget, Location},
             Value,
         };
-    } else if #[cfg(feature = "web_sys")] {
+    } else if #[cfg(yew_backend = "web_sys")] {
         use web_sys::{History, Location, PopStateEvent};
         use gloo::events::EventListener;
         use wasm_bindgen::{JsValue as Value, JsCast};
+    } else if #[cfg(yew_backend = "static_render")] {
+        mod memory;
+
+        use yew::http::Uri;
+        use memory::{History, Location};
     }
 }
 
@@ -32,7 +37,7 @@ pub struct RouteService<STATE = ()> {
     location: Location,
     #[cfg(feature = "std_web")]
     event_listener: Option<EventListenerHandle>,
-    #[cfg(feature = "web_sys")]
+    #[cfg(yew_backend = "web_sys")]
     event_listener: Option<EventListener>,
     phantom_data: PhantomData<STATE>,
 }
@@ -56,44 +61,61 @@ impl<T> RouteService<T> {
                     window().location().expect("browser does not support location API")
                 )
             }),
-            feature = "web_sys" => ({
+            yew_backend = "web_sys" => ({
                 let window = web_sys::window().unwrap();
                 (
                     window.history().expect("browser does not support history API"),
                     window.location()
                 )
             }),
+            yew_backend = "static_render" => (History {}, Location {}),
         };
 
         RouteService {
             history,
             location,
+            #[cfg(not(yew_backend = "static_render"))]
             event_listener: None,
             phantom_data: PhantomData,
         }
     }
 
+    /// Creates a route service for rendering outside of the browser, replacing the in-memory
+    /// history of the current runtime with a single entry for `uri`.
+    ///
+    /// Every runtime of Yew has its own history, which starts with the URI of the request the
+    /// runtime renders a page for, so this is only needed to navigate somewhere else. Route
+    /// services used in the same runtime, including the one owned by the `RouteAgent`, share
+    /// the history. Calls to `set_route` and `replace_route` are recorded in it and can be
+    /// inspected with `history`.
+    #[cfg(yew_backend = "static_render")]
+    pub fn from_uri(uri: &Uri) -> RouteService<T> {
+        let service = RouteService::new();
+        service.history.reset(uri);
+        service
+    }
+
     #[inline]
     fn get_route_from_location(location: &Location) -> String {
-        let path = location.pathname().unwrap();
-        let query = location.search().unwrap();
-        let fragment = location.hash().unwrap();
+        let path = get_path(location);
+        let query = get_query(location);
+        let fragment = get_fragment(location);
         format_route_string(&path, &query, &fragment)
     }
 
     /// Gets the path name of the current url.
     pub fn get_path(&self) -> String {
-        self.location.pathname().unwrap()
+        get_path(&self.location)
     }
 
     /// Gets the query string of the current url.
     pub fn get_query(&self) -> String {
-        self.location.search().unwrap()
+        get_query(&self.location)
     }
 
     /// Gets the fragment of the current url.
     pub fn get_fragment(&self) -> String {
-        self.location.hash().unwrap()
+        get_fragment(&self.location)
     }
 }
 
@@ -104,12 +126,13 @@ where
     /// Registers a callback to the route service.
     /// Callbacks will be called when the History API experiences a change such as
     /// popping a state off of its stack when the forward or back buttons are pressed.
+    #[cfg(not(yew_backend = "static_render"))]
     pub fn register_callback(&mut self, callback: Callback<Route<STATE>>) {
         let cb = move |event: PopStateEvent| {
             let state_value: Value = event.state();
             let state_string: String = cfg_match! {
                 feature = "std_web" => String::try_from(state_value).unwrap_or_default(),
-                feature = "web_sys" => state_value.as_string().unwrap_or_default(),
+                yew_backend = "web_sys" => state_value.as_string().unwrap_or_default(),
             };
             let state: STATE = serde_json::from_str(&state_string).unwrap_or_else(|_| {
                 log::error!("Could not deserialize state string");
@@ -120,7 +143,7 @@ where
             // here.
             let location: Location = cfg_match! {
                 feature = "std_web" => window().location().unwrap(),
-                feature = "web_sys" => web_sys::window().unwrap().location(),
+                yew_backend = "web_sys" => web_sys::window().unwrap().location(),
             };
             let route: String = Self::get_route_from_location(&location);
 
@@ -132,7 +155,7 @@ where
                 self.event_listener = Some(window().add_event_listener(move |event: PopStateEvent| {
                     cb(event)
                 }));
-            } else if #[cfg(feature = "web_sys")] {
+            } else if #[cfg(yew_backend = "web_sys")] {
                 self.event_listener = Some(EventListener::new(web_sys::window().unwrap().as_ref(), "popstate", move |event| {
                     let event: PopStateEvent = event.clone().dyn_into().unwrap();
                     cb(event)
@@ -141,6 +164,12 @@ where
         };
     }
 
+    /// Registers a callback to the route service.
+    ///
+    /// Outside of the browser there is no history to navigate, so the callback is never called.
+    #[cfg(yew_backend = "static_render")]
+    pub fn register_callback(&mut self, _callback: Callback<Route<STATE>>) {}
+
     /// Sets the browser's url bar to contain the provided route,
     /// and creates a history entry that can be navigated via the forward and back buttons.
     ///
@@ -154,9 +183,10 @@ where
             feature = "std_web" => ({
                 self.history.push_state(state_string, "", Some(route));
             }),
-            feature = "web_sys" => ({
+            yew_backend = "web_sys" => ({
                 let _ = self.history.push_state_with_url(&Value::from_str(&state_string), "", Some(route));
             }),
+            yew_backend = "static_render" => self.history.push_state(state_string, route),
         };
     }
 
@@ -171,35 +201,54 @@ where
             feature = "std_web" => ({
                 let _ = self.history.replace_state(state_string, "", Some(route));
             }),
-            feature = "web_sys" => ({
+            yew_backend = "web_sys" => ({
                 let _ = self.history.replace_state_with_url(&Value::from_str(&state_string), "", Some(route));
             }),
+            yew_backend = "static_render" => self.history.replace_state(state_string, route),
         };
     }
 
     /// Gets the concatenated path, query, and fragment.
     pub fn get_route(&self) -> Route<STATE> {
         let route_string = Self::get_route_from_location(&self.location);
-        let state: STATE = get_state_string(&self.history)
-            .or_else(|| {
-                log::trace!("History state is empty");
-                None
-            })
-            .and_then(|state_string| -> Option<STATE> {
-                serde_json::from_str(&state_string)
-                    .ok()
-                    .or_else(|| {
-                        log::error!("Could not deserialize state string");
-                        None
-                    })
-                    .and_then(std::convert::identity) // flatten
-            })
-            .unwrap_or_default();
+        let state = parse_state(get_state_string(&self.history));
         Route {
             route: route_string,
             state,
         }
     }
+
+    /// Gets every route of the in-memory history, oldest first. The last route is the current
+    /// one unless the history was navigated back.
+    #[cfg(yew_backend = "static_render")]
+    pub fn history(&self) -> Vec<Route<STATE>> {
+        self.history
+            .entries()
+            .into_iter()
+            .map(|entry| Route {
+                route: entry.route,
+                state: parse_state(entry.state),
+            })
+            .collect()
+    }
+}
+
+fn parse_state<STATE: RouteState>(state_string: Option<String>) -> STATE {
+    state_string
+        .or_else(|| {
+            log::trace!("History state is empty");
+            None
+        })
+        .and_then(|state_string| -> Option<STATE> {
+            serde_json::from_str(&state_string)
+                .ok()
+                .or_else(|| {
+                    log::error!("Could not deserialize state string");
+                    None
+                })
+                .and_then(std::convert::identity) // flatten
+        })
+        .unwrap_or_default()
 }
 
 /// Formats a path, query, and fragment into a string.
@@ -215,18 +264,91 @@ pub(crate) fn format_route_string(path: &str, query: &str, fragment: &str) -> St
     )
 }
 
+fn get_path(location: &Location) -> String {
+    cfg_match! {
+        yew_backend = "static_render" => location.pathname(),
+        _ => location.pathname().unwrap(),
+    }
+}
+
+fn get_query(location: &Location) -> String {
+    cfg_match! {
+        yew_backend = "static_render" => location.search(),
+        _ => location.search().unwrap(),
+    }
+}
+
+fn get_fragment(location: &Location) -> String {
+    cfg_match! {
+        yew_backend = "static_render" => location.hash(),
+        _ => location.hash().unwrap(),
+    }
+}
+
+#[cfg(not(yew_backend = "static_render"))]
 fn get_state(history: &History) -> Value {
     cfg_match! {
         feature = "std_web" => js!(
             return @{history}.state;
         ),
-        feature = "web_sys" => history.state().unwrap(),
+        yew_backend = "web_sys" => history.state().unwrap(),
     }
 }
 
 fn get_state_string(history: &History) -> Option<String> {
     cfg_match! {
         feature = "std_web" => get_state(history).try_into().ok(),
-        feature = "web_sys" => get_state(history).as_string(),
+        yew_backend = "web_sys" => get_state(history).as_string(),
+        yew_backend = "static_render" => history.state(),
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_seeded_with_the_request_uri() {
+        let service = RouteService::<()>::from_uri(&Uri::from_static(
+            "https://example.com/docs/intro?lang=en",
+        ));
+        assert_eq!(service.get_path(), "/docs/intro");
+        assert_eq!(service.get_query(), "?lang=en");
+        assert_eq!(service.get_fragment(), "");
+        assert_eq!(
+            service.get_route(),
+            Route::new_no_state("/docs/intro?lang=en")
+        );
+
+        // The location of Yew's window belongs to the app, not to the router.
+        let location = yew::utils::window().location();
+        assert_eq!(location.href(), "http://localhost/");
+    }
+
+    #[test]
+    fn it_records_navigation() {
+        let mut service = RouteService::<Option<u32>>::from_uri(&Uri::from_static("/"));
+        service.set_route("/a#top", Some(1));
+        service.set_route("/b", Some(2));
+        service.replace_route("/c", None);
+
+        assert_eq!(service.get_route().route, "/c");
+        assert_eq!(service.get_fragment(), "");
+        let routes: Vec<_> = service
+            .history()
+            .into_iter()
+            .map(|route| (route.route, route.state))
+            .collect();
+        assert_eq!(
+            routes,
+            vec![
+                ("/".to_owned(), None),
+                ("/a#top".to_owned(), Some(1)),
+                ("/c".to_owned(), None),
+            ]
+        );
+
+        // A new service shares the history of the runtime.
+        assert_eq!(RouteService::<Option<u32>>::new().get_path(), "/c");
     }
 }
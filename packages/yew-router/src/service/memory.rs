@@ -0,0 +1,169 @@
+//! An in-memory session history which stands in for the browser's History and Location APIs
+//! when routes are rendered outside of the browser.
+//!
+//! Every runtime of Yew has a history of its own, so pages rendered on the same thread can't
+//! see each other's routes.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::http::Uri;
+use yew::runtime::Runtime;
+
+/// Returns the session history of the current runtime. It starts with an entry for the URI of
+/// the request the runtime renders a page for, or for `/`.
+fn stack() -> Rc<RefCell<Stack>> {
+    let runtime = Runtime::current();
+    runtime.local(|| {
+        RefCell::new(match runtime.request() {
+            Some(request) => Stack::new(request.uri()),
+            None => Stack::default(),
+        })
+    })
+}
+
+/// An entry of the session history.
+#[derive(Debug, Clone)]
+pub(crate) struct Entry {
+    /// The path, query and fragment of the entry
+    pub(crate) route: String,
+    /// The serialized state of the entry, if any was stored
+    pub(crate) state: Option<String>,
+}
+
+#[derive(Debug)]
+struct Stack {
+    entries: Vec<Entry>,
+    index: usize,
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Stack {
+            entries: vec![Entry {
+                route: "/".to_owned(),
+                state: None,
+            }],
+            index: 0,
+        }
+    }
+}
+
+impl Stack {
+    fn new(uri: &Uri) -> Self {
+        let route = uri
+            .path_and_query()
+            .map_or_else(|| "/".to_owned(), |path| path.to_string());
+        Stack {
+            entries: vec![Entry { route, state: None }],
+            index: 0,
+        }
+    }
+
+    fn current(&self) -> &Entry {
+        &self.entries[self.index]
+    }
+}
+
+/// Handle to the session history of the current runtime.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct History {}
+
+impl History {
+    /// Replaces the whole history with a single entry for `uri`.
+    pub(crate) fn reset(&self, uri: &Uri) {
+        *stack().borrow_mut() = Stack::new(uri);
+    }
+
+    /// Adds an entry after the current one, discarding all entries which could be reached by
+    /// navigating forward.
+    pub(crate) fn push_state(&self, state: String, route: &str) {
+        if let Some(route) = parse_route(route) {
+            let stack = stack();
+            let mut stack = stack.borrow_mut();
+            let index = stack.index + 1;
+            stack.entries.truncate(index);
+            stack.entries.push(Entry {
+                route,
+                state: Some(state),
+            });
+            stack.index = index;
+        }
+    }
+
+    /// Replaces the current entry.
+    pub(crate) fn replace_state(&self, state: String, route: &str) {
+        if let Some(route) = parse_route(route) {
+            let stack = stack();
+            let mut stack = stack.borrow_mut();
+            let index = stack.index;
+            stack.entries[index] = Entry {
+                route,
+                state: Some(state),
+            };
+        }
+    }
+
+    /// Returns the state of the current entry.
+    pub(crate) fn state(&self) -> Option<String> {
+        stack().borrow().current().state.clone()
+    }
+
+    /// Returns all entries, oldest first.
+    pub(crate) fn entries(&self) -> Vec<Entry> {
+        stack().borrow().entries.clone()
+    }
+}
+
+/// Handle to the route of the current history entry.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Location {}
+
+impl Location {
+    fn with_route<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+        f(&stack().borrow().current().route)
+    }
+
+    pub(crate) fn pathname(&self) -> String {
+        self.with_route(|route| {
+            let end = route.find(['?', '#']).unwrap_or(route.len());
+            route[..end].to_owned()
+        })
+    }
+
+    pub(crate) fn search(&self) -> String {
+        self.with_route(|route| {
+            let route = route.split('#').next().unwrap_or_default();
+            route
+                .find('?')
+                .map_or_else(String::new, |start| route[start..].to_owned())
+        })
+    }
+
+    pub(crate) fn hash(&self) -> String {
+        self.with_route(|route| {
+            route
+                .find('#')
+                .map_or_else(String::new, |start| route[start..].to_owned())
+        })
+    }
+}
+
+/// Returns the path, query and fragment of `route`, which is either an absolute URL or a path
+/// starting with `/`. Logs an error and returns `None` if `route` is not a valid URL, in which
+/// case the browser wouldn't change its history either.
+fn parse_route(route: &str) -> Option<String> {
+    let (url, fragment) = match route.find('#') {
+        Some(start) => route.split_at(start),
+        None => (route, ""),
+    };
+    match url.parse::<Uri>() {
+        Ok(uri) if uri.scheme().is_some() || url.starts_with('/') => {
+            let path = uri.path_and_query().map_or("/", |path| path.as_str());
+            Some(format!("{}{}", path, fragment))
+        }
+        _ => {
+            log::error!("Could not set route: {} is not a valid URL", route);
+            None
+        }
+    }
+}
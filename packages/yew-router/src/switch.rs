@@ -63,8 +63,8 @@ pub trait Switch: Sized {
     /// a default item can be provided instead.
     ///
     /// Its primary motivation for existing is to allow implementing Switch for Option.
-    /// This doesn't make sense at the moment because this only works for the individual key section
-    /// - any surrounding literals are pretty much guaranteed to make the parse step fail.
+    /// This doesn't make sense at the moment because this only works for the individual key
+    /// section - any surrounding literals are pretty much guaranteed to make the parse step fail.
     /// because of this, this functionality might be removed in favor of using a nested Switch enum,
     /// or multiple variants.
     fn key_not_available() -> Option<Self> {
@@ -139,11 +139,11 @@ impl<U: Switch + std::fmt::Debug> Switch for AllowMissing<U> {
 
         if inner.is_some() {
             (Some(AllowMissing(inner)), inner_state)
-        } else if &route == ""
-            || (&route).starts_with('/')
-            || (&route).starts_with('?')
-            || (&route).starts_with('&')
-            || (&route).starts_with('#')
+        } else if route.is_empty()
+            || route.starts_with('/')
+            || route.starts_with('?')
+            || route.starts_with('&')
+            || route.starts_with('#')
         {
             (Some(AllowMissing(None)), inner_state)
         } else {
@@ -0,0 +1,178 @@
+#![cfg(yew_backend = "static_render")]
+
+use yew::http::{HeaderMap, Method, Uri};
+use yew::request::RequestContext;
+use yew::response::{ServerResponse, StatusCode};
+use yew::runtime::Runtime;
+use yew::{html, Component, ComponentLink, Html, ShouldRender};
+use yew_router::{prelude::*, service::RouteService};
+
+#[derive(Clone, Debug, PartialEq, Switch)]
+enum AppRoute {
+    #[to = "/posts/{id}"]
+    Post(u32),
+    #[to = "/!"]
+    Home,
+}
+
+struct App;
+
+impl Component for App {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        App
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <Router<AppRoute>
+                render=Router::render(|route: AppRoute| match route {
+                    AppRoute::Home => html! {
+                        <RouterAnchor<AppRoute> route=AppRoute::Post(1)>{ "First post" }</RouterAnchor<AppRoute>>
+                    },
+                    AppRoute::Post(id) => html! { <h1>{ format!("Post {}", id) }</h1> },
+                })
+            />
+        }
+    }
+}
+
+fn runtime(uri: &'static str) -> Runtime {
+    Runtime::with_request(RequestContext::new(
+        Method::GET,
+        Uri::from_static(uri),
+        HeaderMap::new(),
+    ))
+}
+
+fn render_in<COMP: Component<Properties = ()>>(runtime: Runtime) -> (ServerResponse, String) {
+    let (response, html) = yew::App::<COMP>::new()
+        .with_runtime(runtime)
+        .render_to_response()
+        .unwrap();
+    (response, html.into())
+}
+
+fn render(uri: &'static str) -> String {
+    render_in::<App>(runtime(uri)).1
+}
+
+#[test]
+fn router_renders_the_route_of_the_request() {
+    assert_eq!(render("/posts/7"), "<h1>Post 7</h1>");
+    assert_eq!(
+        render("http://example.com/"),
+        "<a href=\"&#x2F;posts&#x2F;1\"><!---->First post</a>"
+    );
+
+    // The routes of the requests stay in their runtimes
+    assert_eq!(RouteService::<()>::new().get_path(), "/");
+}
+
+#[test]
+fn unmatched_routes_are_not_found() {
+    let (response, html) = render_in::<App>(runtime("/posts/first"));
+    assert_eq!(html, "No route matched");
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let (response, _) = render_in::<App>(runtime("/posts/1"));
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[test]
+fn router_redirects_are_recorded() {
+    struct Redirecting;
+
+    impl Component for Redirecting {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Redirecting
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <Router<AppRoute>
+                    render=Router::render(|_| html! { "Home" })
+                    redirect=Router::redirect(|_| AppRoute::Home)
+                />
+            }
+        }
+    }
+
+    let runtime = runtime("/missing");
+    let (response, html) = render_in::<Redirecting>(runtime.clone());
+    assert_eq!(html, "Home");
+    assert_eq!(response.status, StatusCode::FOUND);
+    assert_eq!(response.location(), Some("/"));
+    assert_eq!(
+        runtime.enter(|| RouteService::<()>::new().history()),
+        vec![Route::new_no_state("/")]
+    );
+}
+
+#[test]
+fn router_not_found_pages_keep_the_route() {
+    struct Missing;
+
+    impl Component for Missing {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Missing
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <Router<AppRoute>
+                    render=Router::render(|_| html! { "Home" })
+                    not_found=Router::not_found(|route: Route| html! {
+                        <h1>{ format!("{} not found", route.route) }</h1>
+                    })
+                />
+            }
+        }
+    }
+
+    let runtime = runtime("/missing");
+    let (response, html) = render_in::<Missing>(runtime.clone());
+    assert_eq!(html, "<h1>/missing not found</h1>");
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    assert_eq!(response.location(), None);
+    assert_eq!(
+        runtime.enter(|| RouteService::<()>::new().history()),
+        vec![Route::new_no_state("/missing")]
+    );
+
+    let (response, html) = render_in::<Missing>(self::runtime("/"));
+    assert_eq!(html, "Home");
+    assert_eq!(response.status, StatusCode::OK);
+}
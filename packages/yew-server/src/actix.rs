@@ -0,0 +1,78 @@
+//! Extractor and responder for `actix-web` handlers.
+//!
+//! `actix-web` uses its own version of the `http` types, so requests and responses are
+//! converted header by header.
+
+use crate::{SsrBody, SsrRequest, SsrResponse};
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::{http as actix_http, FromRequest, HttpRequest, HttpResponse, Responder};
+use bytes::Bytes;
+use futures_core::Stream;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use http::{Method, Uri};
+use http_body::Body;
+use std::future::{ready, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use yew::smr::HtmlRenderError;
+
+impl FromRequest for SsrRequest {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let method =
+            Method::from_bytes(request.method().as_str().as_bytes()).unwrap_or(Method::GET);
+        let uri = request
+            .uri()
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| Uri::from_static("/"));
+        let mut headers = HeaderMap::new();
+        for (name, value) in request.headers().iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_str().as_bytes()),
+                HeaderValue::from_bytes(value.as_bytes()),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        ready(Ok(SsrRequest::new(method, uri, headers)))
+    }
+}
+
+impl Responder for SsrResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<BoxBody> {
+        let status = actix_http::StatusCode::from_u16(self.status.as_u16())
+            .unwrap_or(actix_http::StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = HttpResponse::build(status);
+        for (name, value) in self.headers.iter() {
+            response.append_header((name.as_str(), value.as_bytes()));
+        }
+        response.streaming(BodyStream(self.body))
+    }
+}
+
+/// Adapts an [`SsrBody`] to the stream of chunks `actix-web` expects.
+struct BodyStream(SsrBody);
+
+impl Stream for BodyStream {
+    type Item = Result<Bytes, HtmlRenderError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            return match Pin::new(&mut self.0).poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                    Ok(data) => Poll::Ready(Some(Ok(data))),
+                    Err(_) => continue,
+                },
+                Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
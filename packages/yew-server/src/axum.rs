@@ -0,0 +1,22 @@
+//! Extractor and responder for `axum` handlers.
+
+use crate::{SsrRequest, SsrResponse};
+use axum_core::body::Body;
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::request::Parts;
+use std::convert::Infallible;
+
+impl<S: Send + Sync> FromRequestParts<S> for SsrRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Infallible> {
+        Ok(SsrRequest::from(&*parts))
+    }
+}
+
+impl IntoResponse for SsrResponse {
+    fn into_response(self) -> Response {
+        SsrResponse::into_response(self).map(Body::new)
+    }
+}
@@ -0,0 +1,181 @@
+//! The response body which pages are streamed into.
+
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use std::fmt;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::{mpsc, oneshot};
+use yew::response::{ResponseContext, ServerResponse};
+use yew::smr::{HtmlRenderError, HtmlWrite};
+
+/// Number of chunks which may be rendered ahead of the client. Once they are all waiting, the
+/// blocking thread the page is rendered on waits for the client as well, see
+/// [`SsrService::render`](crate::SsrService::render).
+const CHANNEL_CAPACITY: usize = 16;
+
+type Chunk = Result<Bytes, HtmlRenderError>;
+type Start = Result<ServerResponse, HtmlRenderError>;
+
+/// The body of an [`SsrResponse`](crate::SsrResponse).
+///
+/// It yields the chunks of HTML as the renderer flushes them. If rendering fails after the
+/// response has been started, the body ends with the error, which makes the server abort the
+/// response.
+pub struct SsrBody {
+    kind: Kind,
+}
+
+enum Kind {
+    Full(Option<Bytes>),
+    Channel(mpsc::Receiver<Chunk>),
+}
+
+impl SsrBody {
+    /// Creates a body which consists of a single chunk.
+    pub fn full(data: impl Into<Bytes>) -> Self {
+        SsrBody {
+            kind: Kind::Full(Some(data.into())),
+        }
+    }
+}
+
+impl Body for SsrBody {
+    type Data = Bytes;
+    type Error = HtmlRenderError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, HtmlRenderError>>> {
+        match &mut self.get_mut().kind {
+            Kind::Full(data) => Poll::Ready(data.take().map(|data| Ok(Frame::data(data)))),
+            Kind::Channel(receiver) => receiver
+                .poll_recv(cx)
+                .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.kind, Kind::Full(None))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.kind {
+            Kind::Full(Some(data)) => SizeHint::with_exact(data.len() as u64),
+            Kind::Full(None) => SizeHint::with_exact(0),
+            Kind::Channel(_) => SizeHint::default(),
+        }
+    }
+}
+
+impl fmt::Debug for SsrBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            Kind::Full(_) => "full",
+            Kind::Channel(_) => "stream",
+        };
+        f.debug_struct("SsrBody").field("kind", &kind).finish()
+    }
+}
+
+/// The sending half of an [`SsrBody`], which is moved to the thread the page is rendered on.
+pub(crate) struct Channel {
+    started: oneshot::Sender<Start>,
+    sender: mpsc::Sender<Chunk>,
+}
+
+impl Channel {
+    /// Creates the channel, the receiver of the start of the response and the body.
+    pub(crate) fn new() -> (Self, oneshot::Receiver<Start>, SsrBody) {
+        let (started, start) = oneshot::channel();
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let body = SsrBody {
+            kind: Kind::Channel(receiver),
+        };
+        (Channel { started, sender }, start, body)
+    }
+
+    /// Creates the writer which starts the response with the status and headers in `response`.
+    pub(crate) fn into_writer(self, response: ResponseContext) -> ChannelWriter {
+        ChannelWriter {
+            buffer: String::new(),
+            started: Some(self.started),
+            sender: self.sender,
+            response,
+            finished: false,
+        }
+    }
+}
+
+/// Sends the HTML written by the renderer to an [`SsrBody`].
+///
+/// Output is buffered until the renderer flushes it. The first flush starts the response with
+/// the status and headers set by the components so far, errors before it are reported instead
+/// so they can be answered with an error page.
+pub(crate) struct ChannelWriter {
+    buffer: String,
+    started: Option<oneshot::Sender<Start>>,
+    sender: mpsc::Sender<Chunk>,
+    response: ResponseContext,
+    finished: bool,
+}
+
+impl ChannelWriter {
+    /// Runs the render `f` and reports its result.
+    pub(crate) fn finish(mut self, f: impl FnOnce(&mut Self) -> Result<(), HtmlRenderError>) {
+        let result = f(&mut self).and_then(|()| self.flush());
+        self.finished = true;
+        match (result, self.started.take()) {
+            (Ok(()), Some(started)) => {
+                let _ = started.send(Ok(self.response.response()));
+            }
+            (Ok(()), None) => {}
+            (Err(error), Some(started)) => {
+                let _ = started.send(Err(error));
+            }
+            (Err(error), None) => {
+                log::error!("cannot finish page: {}", error);
+                let _ = self.sender.blocking_send(Err(error));
+            }
+        }
+    }
+}
+
+impl HtmlWrite for ChannelWriter {
+    fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+        self.buffer.push_str(html);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), HtmlRenderError> {
+        if let Some(started) = self.started.take() {
+            if started.send(Ok(self.response.response())).is_err() {
+                return Err(closed());
+            }
+        }
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = Bytes::from(mem::take(&mut self.buffer));
+        self.sender.blocking_send(Ok(chunk)).map_err(|_| closed())
+    }
+}
+
+impl Drop for ChannelWriter {
+    fn drop(&mut self) {
+        // The render panicked. Without a start the handler answers with an error page,
+        // otherwise the body has to end with an error instead of looking complete.
+        if !self.finished && self.started.is_none() {
+            let error = io::Error::other("the render panicked");
+            let _ = self.sender.try_send(Err(error.into()));
+        }
+    }
+}
+
+/// The error which stops a render whose response was dropped.
+fn closed() -> HtmlRenderError {
+    io::Error::new(io::ErrorKind::BrokenPipe, "the response was dropped").into()
+}
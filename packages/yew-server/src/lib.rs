@@ -0,0 +1,278 @@
+//! HTTP server adapters for rendering Yew apps on the server.
+//!
+//! An [`SsrService`] turns HTTP requests into rendered pages: it renders the app in a runtime
+//! for the request, whose in-memory history of `yew-router` starts at the requested URI, and
+//! streams the HTML into the response body with `Content-Type: text/html`. Errors which happen
+//! before the first chunk of the page was produced are answered with
+//! `500 Internal Server Error`.
+//!
+//! The status and headers of the response are the ones the components set through
+//! `yew::response::ResponseContext` while the page is created, e.g. `404 Not Found` for routes
+//! the router doesn't match or `302 Found` with a `Location` for its redirects.
+//!
+//! Components aren't `Send`, so every page is rendered on a blocking thread of the Tokio
+//! runtime the server runs on, and the app is created there by a factory which receives the
+//! [`SsrRequest`]. The app is rendered in a runtime which carries the request, so components
+//! can read its headers and cookies with `ComponentLink::request_context`.
+//!
+//! The service can be used with every server which accepts a `tower::Service`, such as
+//! `hyper` or `axum` (feature `tower`, enabled by default). The features `axum` and `actix`
+//! add extractors for [`SsrRequest`] and responders for [`SsrResponse`] to write handlers for
+//! these frameworks:
+//!
+//! ```ignore
+//! async fn page(State(service): State<SsrService<Site>>, request: SsrRequest) -> SsrResponse {
+//!     service.render(request).await
+//! }
+//! ```
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+mod body;
+#[cfg(feature = "tower")]
+mod tower;
+
+pub use body::SsrBody;
+
+use body::Channel;
+use http::header::{self, HeaderMap, HeaderValue};
+use http::request::Parts;
+use http::{Method, Response, StatusCode, Uri};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use yew::request::RequestContext;
+use yew::response::{ResponseContext, ServerResponse};
+use yew::smr::{block_on, HtmlWrite};
+use yew::{App, Component};
+
+/// The parts of an HTTP request which are available while a page is rendered.
+#[derive(Debug, Clone)]
+pub struct SsrRequest {
+    /// The method of the request
+    pub method: Method,
+    /// The absolute URI of the request. The scheme and authority are taken from the `Host` and
+    /// `X-Forwarded-Proto` headers if the request only carried a path.
+    pub uri: Uri,
+    /// The headers of the request
+    pub headers: HeaderMap,
+}
+
+impl SsrRequest {
+    /// Creates the request from its parts.
+    pub fn new(method: Method, uri: Uri, headers: HeaderMap) -> Self {
+        let uri = absolute_uri(uri, &headers);
+        SsrRequest {
+            method,
+            uri,
+            headers,
+        }
+    }
+}
+
+impl From<&Parts> for SsrRequest {
+    fn from(parts: &Parts) -> Self {
+        SsrRequest::new(
+            parts.method.clone(),
+            parts.uri.clone(),
+            parts.headers.clone(),
+        )
+    }
+}
+
+impl From<&SsrRequest> for RequestContext {
+    fn from(request: &SsrRequest) -> Self {
+        RequestContext::new(
+            request.method.clone(),
+            request.uri.clone(),
+            request.headers.clone(),
+        )
+    }
+}
+
+/// Adds the scheme and authority to `uri` if it only holds a path.
+fn absolute_uri(uri: Uri, headers: &HeaderMap) -> Uri {
+    if uri.authority().is_some() {
+        return uri;
+    }
+    let host = match headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+    {
+        Some(host) => host,
+        None => return uri,
+    };
+    let scheme = match headers
+        .get("x-forwarded-proto")
+        .and_then(|proto| proto.to_str().ok())
+    {
+        Some("https") => "https",
+        _ => "http",
+    };
+    let path = uri.path_and_query().map_or("/", |path| path.as_str());
+    format!("{}://{}{}", scheme, host, path)
+        .parse()
+        .unwrap_or(uri)
+}
+
+/// A rendered page, or the error page if rendering failed.
+#[derive(Debug)]
+pub struct SsrResponse {
+    /// The status of the response
+    pub status: StatusCode,
+    /// The headers of the response
+    pub headers: HeaderMap,
+    /// The streamed HTML
+    pub body: SsrBody,
+}
+
+impl SsrResponse {
+    /// Creates the response for a page with the status and headers set by its components.
+    fn html(response: ServerResponse, body: SsrBody) -> Self {
+        let ServerResponse {
+            status,
+            mut headers,
+        } = response;
+        headers
+            .entry(header::CONTENT_TYPE)
+            .or_insert(HeaderValue::from_static("text/html; charset=utf-8"));
+        SsrResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    fn internal_server_error() -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; charset=utf-8"),
+        );
+        SsrResponse {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            headers,
+            body: SsrBody::full("Internal Server Error"),
+        }
+    }
+
+    /// Converts the response into an `http::Response`.
+    pub fn into_response(self) -> Response<SsrBody> {
+        let mut response = Response::new(self.body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+type MakeApp<COMP> =
+    dyn Fn(&SsrRequest) -> (App<COMP>, <COMP as Component>::Properties) + Send + Sync;
+
+/// Renders the component `COMP` for HTTP requests.
+///
+/// The service is cheap to clone and can be shared between the threads of a server.
+pub struct SsrService<COMP: Component> {
+    make_app: Arc<MakeApp<COMP>>,
+    timeout: Option<Duration>,
+}
+
+impl<COMP: Component> SsrService<COMP> {
+    /// Creates a service which renders the app `make_app` returns for a request with the
+    /// default props, e.g. `SsrService::new(|_| App::new().with_document(document()))`.
+    ///
+    /// The app is moved to a runtime for the request with `App::with_request`, so a runtime
+    /// set with `App::with_runtime` is replaced.
+    pub fn new<F>(make_app: F) -> Self
+    where
+        F: Fn(&SsrRequest) -> App<COMP> + Send + Sync + 'static,
+        COMP::Properties: Default,
+    {
+        Self::with_props(move |request| (make_app(request), COMP::Properties::default()))
+    }
+
+    /// Creates a service which renders the app `make_app` returns for a request with the props
+    /// it returns alongside.
+    pub fn with_props<F>(make_app: F) -> Self
+    where
+        F: Fn(&SsrRequest) -> (App<COMP>, COMP::Properties) + Send + Sync + 'static,
+    {
+        SsrService {
+            make_app: Arc::new(make_app),
+            timeout: None,
+        }
+    }
+
+    /// Waits for the futures components spawn through their links, at most for `timeout`,
+    /// before the page is rendered. The page is then sent in one piece instead of being
+    /// streamed.
+    pub fn await_futures(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Renders the page for `request`.
+    ///
+    /// This has to be called from within a Tokio runtime, which renders the page on one of its
+    /// blocking threads.
+    ///
+    /// The blocking thread is held until the whole page has been handed to the body. At most
+    /// 16 chunks are rendered ahead of the client, afterwards the render waits for the client
+    /// to read them. A slow client therefore occupies a blocking thread for as long as it takes
+    /// to read its page, and the size of the blocking thread pool, see
+    /// `tokio::runtime::Builder::max_blocking_threads`, limits how many pages are streamed at
+    /// once. Servers which face slow clients should time out their connections, which drops
+    /// the body and ends the render.
+    pub async fn render(&self, request: SsrRequest) -> SsrResponse {
+        let (channel, started, body) = Channel::new();
+        let make_app = Arc::clone(&self.make_app);
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || {
+            let (app, props) = make_app(&request);
+            let context = ResponseContext::new();
+            let app = app
+                .with_request(RequestContext::from(&request))
+                .with_response(context.clone());
+            channel.into_writer(context).finish(|writer| match timeout {
+                Some(timeout) => {
+                    let html = block_on(app.render_to_string_with_props_async(props, timeout))?;
+                    writer.write_html(html.as_str())?;
+                    writer.flush()
+                }
+                None => app.render_to_writer_with_props(props, writer),
+            })
+        });
+
+        match started.await {
+            Ok(Ok(response)) => SsrResponse::html(response, body),
+            Ok(Err(error)) => {
+                log::error!("cannot render page: {}", error);
+                SsrResponse::internal_server_error()
+            }
+            Err(_) => {
+                log::error!("cannot render page: the render panicked");
+                SsrResponse::internal_server_error()
+            }
+        }
+    }
+}
+
+impl<COMP: Component> Clone for SsrService<COMP> {
+    fn clone(&self) -> Self {
+        SsrService {
+            make_app: Arc::clone(&self.make_app),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<COMP: Component> fmt::Debug for SsrService<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SsrService")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
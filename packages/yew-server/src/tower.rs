@@ -0,0 +1,30 @@
+//! `tower::Service` implementation, which is used by `hyper` and `axum`.
+
+use crate::{SsrBody, SsrRequest, SsrService};
+use http::{Request, Response};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_service::Service;
+use yew::Component;
+
+impl<COMP, B> Service<Request<B>> for SsrService<COMP>
+where
+    COMP: Component,
+{
+    type Response = Response<SsrBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let (parts, _) = request.into_parts();
+        let request = SsrRequest::from(&parts);
+        let service = self.clone();
+        Box::pin(async move { Ok(service.render(request).await.into_response()) })
+    }
+}
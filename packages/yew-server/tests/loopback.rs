@@ -0,0 +1,221 @@
+use hyper::server::conn::http1;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use yew::response::{ResponseContext, StatusCode};
+use yew::smr::HtmlDocument;
+use yew::{html, App, Component, ComponentLink, Html, ShouldRender};
+use yew_router::{prelude::*, service::RouteService};
+use yew_server::SsrService;
+
+#[derive(Clone, Debug, PartialEq, Switch)]
+enum AppRoute {
+    #[to = "/posts/{id}"]
+    Post(u32),
+    #[to = "/!"]
+    Home,
+}
+
+struct Site {
+    link: ComponentLink<Self>,
+}
+
+impl Component for Site {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Site { link }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let route = RouteService::<()>::new().get_route();
+        match AppRoute::switch(route) {
+            Some(AppRoute::Home) => {
+                let agent = self
+                    .link
+                    .request_context()
+                    .and_then(|request| request.user_agent().map(ToOwned::to_owned));
+                html! { <h1>{ "Home" }{ for agent }</h1> }
+            }
+            Some(AppRoute::Post(id)) => html! { <h1>{ format!("Post {}", id) }</h1> },
+            None => {
+                if let Some(response) = ResponseContext::current() {
+                    response.set_status(StatusCode::NOT_FOUND);
+                    response.set_cache_control("no-store");
+                }
+                html! { <h1>{ "Not found" }</h1> }
+            }
+        }
+    }
+}
+
+fn service() -> SsrService<Site> {
+    SsrService::new(|request| {
+        let document = if request.uri.path() == "/broken" {
+            HtmlDocument::new().html_attribute("a b", "")
+        } else {
+            HtmlDocument::new().title(request.uri.host().unwrap_or_default().to_owned())
+        };
+        App::new().with_document(document)
+    })
+}
+
+/// Serves `service` with hyper on a loopback port.
+async fn serve(service: SsrService<Site>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = TowerToHyperService::new(service.clone());
+            tokio::spawn(async move {
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    });
+    addr
+}
+
+/// Sends a GET request for `path` and returns the head and the body of the response.
+async fn get(addr: SocketAddr, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: example.com\r\nUser-Agent: loopback\r\n\
+         Connection: close\r\n\r\n",
+        path
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    if !head.contains("transfer-encoding: chunked") {
+        return (head.to_owned(), body.to_owned());
+    }
+    let (mut rest, mut decoded) = (body, String::new());
+    loop {
+        let (size, chunk) = rest.split_once("\r\n").unwrap();
+        let size = usize::from_str_radix(size, 16).unwrap();
+        if size == 0 {
+            return (head.to_owned(), decoded);
+        }
+        decoded.push_str(&chunk[..size]);
+        rest = &chunk[size + 2..];
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_are_rendered_for_the_requested_route() {
+    let addr = serve(service()).await;
+
+    let (head, body) = get(addr, "/posts/7").await;
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+    assert!(head.contains("content-type: text/html; charset=utf-8"));
+    assert_eq!(
+        body,
+        "<!DOCTYPE html><html><head><title data-yew-head=\"title\">example.com</title></head>\
+         <body><div id=\"app\"><h1>Post 7</h1></div></body></html>"
+    );
+
+    let (_, body) = get(addr, "/").await;
+    assert!(body.contains("<h1>Home<!--,-->loopback</h1>"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn components_set_the_status_and_headers() {
+    let addr = serve(service()).await;
+
+    let (head, body) = get(addr, "/missing").await;
+    assert!(head.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", head);
+    assert!(head.contains("cache-control: no-store"));
+    assert!(head.contains("content-type: text/html; charset=utf-8"));
+    assert!(body.contains("<h1>Not found</h1>"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn render_errors_are_answered_with_500() {
+    let addr = serve(service()).await;
+
+    let (head, body) = get(addr, "/broken").await;
+    assert!(
+        head.starts_with("HTTP/1.1 500 Internal Server Error\r\n"),
+        "{}",
+        head
+    );
+    assert!(head.contains("content-type: text/plain; charset=utf-8"));
+    assert_eq!(body, "Internal Server Error");
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test(flavor = "multi_thread")]
+async fn axum_handlers_render_pages() {
+    use axum::extract::State;
+    use axum::routing::get as route_get;
+    use axum::Router;
+    use yew_server::{SsrRequest, SsrResponse};
+
+    async fn page(State(service): State<SsrService<Site>>, request: SsrRequest) -> SsrResponse {
+        service.render(request).await
+    }
+
+    let router = Router::new()
+        .route("/posts/{id}", route_get(page))
+        .fallback_service(service())
+        .with_state(service());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let (head, body) = get(addr, "/posts/3").await;
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+    assert!(body.contains("<h1>Post 3</h1>"));
+
+    let (head, body) = get(addr, "/missing").await;
+    assert!(head.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", head);
+    assert!(body.contains("<h1>Not found</h1>"));
+}
+
+#[cfg(feature = "actix")]
+#[actix_rt::test]
+async fn actix_handlers_render_pages() {
+    use actix_web::{test, web, App as ActixApp};
+    use yew_server::{SsrRequest, SsrResponse};
+
+    async fn page(service: web::Data<SsrService<Site>>, request: SsrRequest) -> SsrResponse {
+        service.render(request).await
+    }
+
+    let app = test::init_service(
+        ActixApp::new()
+            .app_data(web::Data::new(service()))
+            .default_service(web::to(page)),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/posts/9")
+        .insert_header(("host", "example.com"))
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body = test::read_body(response).await;
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .contains("<title data-yew-head=\"title\">example.com</title>"));
+
+    let request = test::TestRequest::get().uri("/broken").to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status().as_u16(), 500);
+}
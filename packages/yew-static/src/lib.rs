@@ -0,0 +1,538 @@
+//! Static site generation for Yew apps.
+//!
+//! [`generate`] renders a root component once for every route of a site and writes each page
+//! to `<route>/index.html` inside an output directory, so the site can be served by any static
+//! file server. Every route is rendered in a runtime of its own for a `GET` request of the route,
+//! so the `RouteService` of `yew-router` reports it as the current location, which lets a
+//! `Switch` pick the matching page.
+//!
+//! Large sites can be generated on several threads with a [`Generator`].
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::Any;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Component as PathComponent, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use thiserror::Error as ThisError;
+use yew::http::{HeaderMap, Method};
+use yew::request::RequestContext;
+use yew::smr::HtmlRenderError;
+use yew::{App, Component};
+use yew_router::route::Route;
+
+/// A page which was written by [`generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// The route the page was rendered for
+    pub route: String,
+    /// The file the page was written to
+    pub path: PathBuf,
+}
+
+/// Represents errors which can occur while generating a single page.
+#[derive(Debug, ThisError)]
+pub enum GenerateError {
+    /// The route can't be mapped to a file inside the output directory.
+    #[error("`{0}` is not a route which can be generated: routes start with `/`, have no query or fragment and stay inside the output directory")]
+    InvalidRoute(String),
+
+    /// The root component could not be rendered.
+    #[error(transparent)]
+    Render(#[from] HtmlRenderError),
+
+    /// The page could not be written.
+    #[error("cannot write page: {0}")]
+    Io(#[from] io::Error),
+
+    /// The props of the page could not be serialized or deserialized.
+    #[error("cannot convert props: {0}")]
+    Props(#[from] serde_json::Error),
+
+    /// A component panicked while the page was rendered.
+    #[error("rendering panicked: {0}")]
+    Panic(String),
+}
+
+/// A route which could not be generated.
+#[derive(Debug)]
+pub struct RouteError {
+    /// The route which failed
+    pub route: String,
+    /// The reason it failed
+    pub error: GenerateError,
+}
+
+/// The outcome of [`generate`].
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The pages which were written, in the order of their routes
+    pub pages: Vec<Page>,
+    /// The routes which failed, in the order of their routes
+    pub errors: Vec<RouteError>,
+}
+
+impl Report {
+    /// Returns `true` if every route was generated.
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn push(&mut self, route: String, result: Result<PathBuf, GenerateError>) {
+        match result {
+            Ok(path) => self.pages.push(Page { route, path }),
+            Err(error) => self.errors.push(RouteError { route, error }),
+        }
+    }
+}
+
+/// Renders `COMP` for every route in `routes` and writes the pages to `out_dir`.
+///
+/// Routes are either strings such as `String::from("/about")` or values of a type implementing
+/// `Switch`, which are turned into strings with `Switch::build_route_section`. The page for `/`
+/// is written to `out_dir/index.html` and the page for `/docs/intro` to
+/// `out_dir/docs/intro/index.html`.
+///
+/// A route which fails or panics doesn't stop the build: its error is recorded in the returned
+/// [`Report`] and the next route is generated.
+pub fn generate<COMP, I>(routes: I, out_dir: impl AsRef<Path>) -> Report
+where
+    COMP: Component,
+    COMP::Properties: Default,
+    I: IntoIterator,
+    I::Item: Into<Route>,
+{
+    let out_dir = out_dir.as_ref();
+    let mut report = Report::default();
+    for route in routes {
+        let route = route.into().route;
+        let result = catch_generate_page::<COMP>(&route, || Ok(Default::default()), out_dir);
+        report.push(route, result);
+    }
+    report
+}
+
+/// Generates pages on several threads.
+///
+/// Components aren't `Send`, so every worker thread renders its pages itself, each in a runtime
+/// of its own like with [`generate`]. Workers take the next route from the shared list until it is
+/// exhausted, and their results are merged into a single [`Report`] which lists pages and
+/// errors in the order of the routes, just like [`generate`].
+///
+/// ```no_run
+/// # use yew::prelude::*;
+/// # struct Site;
+/// # impl Component for Site {
+/// #     type Message = ();
+/// #     type Properties = ();
+/// #     fn create(_: (), _: ComponentLink<Self>) -> Self { Site }
+/// #     fn update(&mut self, _: ()) -> ShouldRender { false }
+/// #     fn change(&mut self, _: ()) -> ShouldRender { false }
+/// #     fn view(&self) -> Html { html! {} }
+/// # }
+/// use yew_static::Generator;
+///
+/// let routes = (0..5000).map(|page| format!("/docs/{}", page));
+/// let report = Generator::new("dist").threads(8).generate::<Site, _>(routes);
+/// assert!(report.is_success());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Generator {
+    out_dir: PathBuf,
+    threads: usize,
+}
+
+impl Generator {
+    /// Creates a generator which writes pages to `out_dir`, using one thread per CPU.
+    pub fn new(out_dir: impl Into<PathBuf>) -> Self {
+        Generator {
+            out_dir: out_dir.into(),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Sets the number of worker threads. At least one thread is used.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Renders `COMP` with its default props for every route in `routes`.
+    ///
+    /// See [`generate`] for the routes which are accepted and the files which are written.
+    pub fn generate<COMP, I>(&self, routes: I) -> Report
+    where
+        COMP: Component,
+        COMP::Properties: Default,
+        I: IntoIterator,
+        I::Item: Into<Route>,
+    {
+        let jobs = routes
+            .into_iter()
+            .enumerate()
+            .map(|(index, route)| Job {
+                index,
+                route: route.into().route,
+                props: String::new(),
+            })
+            .collect();
+        self.run::<COMP, _>(jobs, Vec::new(), |_| Ok(COMP::Properties::default()))
+    }
+
+    /// Renders `COMP` for every route in `pages` with the props given alongside the route.
+    ///
+    /// The props are serialized to JSON on the calling thread and deserialized into the props
+    /// of `COMP` on the worker thread, so they don't have to be `Send`.
+    pub fn generate_with_props<COMP, I, R, P>(&self, pages: I) -> Report
+    where
+        COMP: Component,
+        COMP::Properties: DeserializeOwned,
+        I: IntoIterator<Item = (R, P)>,
+        R: Into<Route>,
+        P: Serialize,
+    {
+        let mut jobs = Vec::new();
+        let mut failed = Vec::new();
+        for (index, (route, props)) in pages.into_iter().enumerate() {
+            let route = route.into().route;
+            match serde_json::to_string(&props) {
+                Ok(props) => jobs.push(Job {
+                    index,
+                    route,
+                    props,
+                }),
+                Err(error) => failed.push((index, route, Err(error.into()))),
+            }
+        }
+        self.run::<COMP, _>(jobs, failed, |props| Ok(serde_json::from_str(props)?))
+    }
+
+    /// Generates `jobs` on the worker threads and merges the outcomes with the routes which
+    /// `failed` before.
+    fn run<COMP, F>(&self, jobs: Vec<Job>, failed: Vec<Outcome>, props: F) -> Report
+    where
+        COMP: Component,
+        F: Fn(&str) -> Result<COMP::Properties, GenerateError> + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let worker = || {
+            let mut outcomes = Vec::new();
+            while let Some(job) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                let props = || props(&job.props);
+                let result = catch_generate_page::<COMP>(&job.route, props, &self.out_dir);
+                outcomes.push((job.index, job.route.clone(), result));
+            }
+            outcomes
+        };
+
+        let mut outcomes = failed;
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.min(jobs.len()))
+                .map(|_| scope.spawn(worker))
+                .collect();
+            for worker in workers {
+                outcomes.extend(worker.join().expect("worker threads catch panics"));
+            }
+        });
+        outcomes.sort_by_key(|(index, _, _)| *index);
+
+        let mut report = Report::default();
+        for (_, route, result) in outcomes {
+            report.push(route, result);
+        }
+        report
+    }
+}
+
+/// A route which is waiting to be generated.
+struct Job {
+    /// The position of the route in the input
+    index: usize,
+    route: String,
+    /// The props serialized to JSON
+    props: String,
+}
+
+/// The position, route and result of a generated page.
+type Outcome = (usize, String, Result<PathBuf, GenerateError>);
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "unknown panic".to_owned(),
+        },
+    }
+}
+
+/// Generates the page for `route` like `generate_page`, with the props returned by `props`. A
+/// panic of a component is turned into an error so that the other routes can still be generated.
+fn catch_generate_page<COMP>(
+    route: &str,
+    props: impl FnOnce() -> Result<COMP::Properties, GenerateError>,
+    out_dir: &Path,
+) -> Result<PathBuf, GenerateError>
+where
+    COMP: Component,
+{
+    let render = || generate_page::<COMP>(route, props()?, out_dir);
+    panic::catch_unwind(AssertUnwindSafe(render))
+        .unwrap_or_else(|payload| Err(GenerateError::Panic(panic_message(payload))))
+}
+
+fn generate_page<COMP>(
+    route: &str,
+    props: COMP::Properties,
+    out_dir: &Path,
+) -> Result<PathBuf, GenerateError>
+where
+    COMP: Component,
+{
+    let path = page_path(route, out_dir)?;
+    let uri = route
+        .parse()
+        .map_err(|_| GenerateError::InvalidRoute(route.to_owned()))?;
+    let request = RequestContext::new(Method::GET, uri, HeaderMap::new());
+    let html = App::<COMP>::new()
+        .with_request(request)
+        .render_to_string_with_props(props)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, html.as_str())?;
+    Ok(path)
+}
+
+/// Returns the file the page for `route` is written to.
+fn page_path(route: &str, out_dir: &Path) -> Result<PathBuf, GenerateError> {
+    let invalid = || GenerateError::InvalidRoute(route.to_owned());
+    if !route.starts_with('/') || route.contains(['?', '#']) {
+        return Err(invalid());
+    }
+
+    let mut path = out_dir.to_owned();
+    for component in Path::new(route.trim_start_matches('/')).components() {
+        match component {
+            PathComponent::Normal(segment) => path.push(segment),
+            PathComponent::CurDir => {}
+            _ => return Err(invalid()),
+        }
+    }
+    path.push("index.html");
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use yew::html::{Html, Properties, ShouldRender};
+    use yew::{html, ComponentLink};
+    use yew_router::service::RouteService;
+    use yew_router::Switch;
+
+    #[derive(Debug, Clone, Switch)]
+    enum AppRoute {
+        #[to = "/docs/{name}"]
+        Docs(String),
+        #[to = "/!"]
+        Home,
+    }
+
+    struct Site;
+
+    impl Component for Site {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Site
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let route = RouteService::<()>::new().get_route();
+            match AppRoute::switch(route) {
+                Some(AppRoute::Home) => html! { <h1>{ "Home" }</h1> },
+                Some(AppRoute::Docs(name)) => html! { <h1>{ name }</h1> },
+                None => html! { <h1>{ "Not found" }</h1> },
+            }
+        }
+    }
+
+    #[derive(Clone, Default, Properties, Serialize, Deserialize)]
+    struct PostProps {
+        title: String,
+    }
+
+    struct Post {
+        props: PostProps,
+    }
+
+    impl Component for Post {
+        type Message = ();
+        type Properties = PostProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            if props.title.is_empty() {
+                panic!("posts need a title");
+            }
+            Post { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, props: Self::Properties) -> ShouldRender {
+            self.props = props;
+            true
+        }
+
+        fn view(&self) -> Html {
+            let route = RouteService::<()>::new().get_route().route;
+            html! { <h1 data-route=route>{ &self.props.title }</h1> }
+        }
+    }
+
+    fn out_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yew-static-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn it_writes_a_page_per_route() {
+        let dir = out_dir("pages");
+        let report = generate::<Site, _>(
+            vec![AppRoute::Home, AppRoute::Docs("intro".to_owned())],
+            &dir,
+        );
+
+        assert!(report.is_success());
+        assert_eq!(
+            report.pages,
+            vec![
+                Page {
+                    route: "/".to_owned(),
+                    path: dir.join("index.html"),
+                },
+                Page {
+                    route: "/docs/intro".to_owned(),
+                    path: dir.join("docs/intro/index.html"),
+                },
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("index.html")).unwrap(),
+            "<h1>Home</h1>"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("docs/intro/index.html")).unwrap(),
+            "<h1>intro</h1>"
+        );
+        // The routes were only current in the runtimes of their pages
+        assert_eq!(RouteService::<()>::new().get_route().route, "/");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn it_reports_failed_routes_and_continues() {
+        let dir = out_dir("errors");
+        let routes = vec!["/../escape", "/search?q=yew", "/missing"];
+        let report = generate::<Site, _>(routes.into_iter().map(String::from), &dir);
+
+        let failed: Vec<_> = report.errors.iter().map(|e| e.route.as_str()).collect();
+        assert_eq!(failed, vec!["/../escape", "/search?q=yew"]);
+        assert!(report
+            .errors
+            .iter()
+            .all(|e| matches!(e.error, GenerateError::InvalidRoute(_))));
+        assert_eq!(
+            fs::read_to_string(dir.join("missing/index.html")).unwrap(),
+            "<h1>Not found</h1>"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn panicking_routes_are_reported() {
+        let dir = out_dir("panics");
+        let report = generate::<Post, _>(vec!["/untitled".to_owned()], &dir);
+
+        assert!(report.pages.is_empty());
+        assert_eq!(report.errors.len(), 1);
+        assert!(matches!(
+            &report.errors[0].error,
+            GenerateError::Panic(message) if message == "posts need a title"
+        ));
+        assert!(!dir.join("untitled/index.html").exists());
+    }
+
+    #[test]
+    fn threads_share_the_routes_and_keep_their_order() {
+        let dir = out_dir("threads");
+        let routes: Vec<_> = (0..40).map(|i| AppRoute::Docs(i.to_string())).collect();
+        let report = Generator::new(&dir).threads(4).generate::<Site, _>(routes);
+
+        assert!(report.is_success());
+        let generated: Vec<_> = report.pages.iter().map(|page| page.route.clone()).collect();
+        let expected: Vec<_> = (0..40).map(|i| format!("/docs/{}", i)).collect();
+        assert_eq!(generated, expected);
+        for i in 0..40 {
+            assert_eq!(
+                fs::read_to_string(dir.join(format!("docs/{}/index.html", i))).unwrap(),
+                format!("<h1>{}</h1>", i)
+            );
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn pages_are_rendered_with_their_props() {
+        let dir = out_dir("props");
+        let post = |title: &str| PostProps {
+            title: title.to_owned(),
+        };
+        let pages = vec![
+            ("/hello".to_owned(), post("Hello")),
+            ("/untitled".to_owned(), post("")),
+            ("/bye".to_owned(), post("Bye")),
+        ];
+        let report = Generator::new(&dir)
+            .threads(2)
+            .generate_with_props::<Post, _, _, _>(pages);
+
+        let generated: Vec<_> = report
+            .pages
+            .iter()
+            .map(|page| page.route.as_str())
+            .collect();
+        assert_eq!(generated, vec!["/hello", "/bye"]);
+        assert_eq!(
+            fs::read_to_string(dir.join("bye/index.html")).unwrap(),
+            "<h1 data-route=\"&#x2F;bye\">Bye</h1>"
+        );
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].route, "/untitled");
+        assert!(matches!(
+            &report.errors[0].error,
+            GenerateError::Panic(message) if message == "posts need a title"
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+use std::env;
+
+/// Selects the DOM backend, which the crate checks with `cfg(yew_backend = "...")`.
+///
+/// Browsers use the `web_sys` backend. Everywhere else the in-memory DOM of the
+/// `static_render` feature is used if it is enabled, so that a crate with the default features
+/// can be built for the client as well as for a server which renders it.
+///
+/// The choice is published as the `backend` metadata of the `yew` link, so build scripts of
+/// crates which depend on yew can read it from `DEP_YEW_BACKEND` and follow it.
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(yew_backend, values(\"web_sys\", \"static_render\"))");
+
+    let web_sys = env::var_os("CARGO_FEATURE_WEB_SYS").is_some();
+    let static_render = env::var_os("CARGO_FEATURE_STATIC_RENDER").is_some();
+    let wasm = env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32");
+
+    let backend = if web_sys && (wasm || !static_render) {
+        "web_sys"
+    } else if static_render {
+        "static_render"
+    } else {
+        return;
+    };
+    println!("cargo:rustc-cfg=yew_backend=\"{}\"", backend);
+    println!("cargo:backend={}", backend);
+}
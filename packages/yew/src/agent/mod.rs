@@ -3,6 +3,7 @@
 mod link;
 mod local;
 mod pool;
+#[cfg(any(feature = "std_web", yew_backend = "web_sys"))]
 mod worker;
 
 pub use link::AgentLink;
@@ -10,6 +11,7 @@ pub(crate) use link::*;
 pub use local::{Context, Job};
 pub(crate) use pool::*;
 pub use pool::{Dispatched, Dispatcher};
+#[cfg(any(feature = "std_web", yew_backend = "web_sys"))]
 pub use worker::{Private, Public, Threaded};
 
 use crate::callback::Callback;
@@ -1,15 +1,67 @@
 //! This module contains the `App` struct, which is used to bootstrap
 //! a component in an isolated scope.
 
-// use crate::backend::Element;
+use crate::backend::{DomBackend, Element, Renderer};
+#[cfg(feature = "static_render")]
+use crate::head::HeadCollector;
+#[cfg(feature = "static_render")]
+use crate::html::Scoped;
 use crate::html::{Component, ComponentLink, NodeRef, Scope};
+use crate::hydration::{self, MismatchPolicy};
+use crate::island;
+#[cfg(feature = "static_render")]
+use crate::island::IslandIds;
+#[cfg(feature = "static_render")]
+use crate::request::RequestContext;
+#[cfg(feature = "static_render")]
+use crate::response::{ResponseContext, ServerResponse};
+use crate::runtime::Runtime;
+#[cfg(feature = "static_render")]
+use crate::smr::{
+    FmtWriter, HtmlDocument, HtmlRenderError, HtmlString, HtmlWrite, RenderOptions, TaskSet, ToHtml,
+};
+use crate::state::{self, StateError};
+#[cfg(feature = "static_render")]
+use crate::state::{Capture, EmbeddedState, StateFormat};
+use crate::utils::document;
+use crate::virtual_dom::remove_unclaimed;
+use serde::de::DeserializeOwned;
+#[cfg(feature = "static_render")]
+use serde::Serialize;
+#[cfg(feature = "static_render")]
+use std::future;
+#[cfg(feature = "static_render")]
+use std::rc::Rc;
+#[cfg(feature = "static_render")]
+use std::task::Poll;
+#[cfg(feature = "static_render")]
+use std::time::{Duration, Instant};
 
 /// An instance of an application.
 #[derive(Debug)]
-
 pub struct App<COMP: Component> {
     /// `Scope` holder
     scope: Scope<COMP>,
+    /// What happens to markup which doesn't match when hydrating
+    mismatch_policy: MismatchPolicy,
+    /// The runtime passed to `with_runtime`
+    #[cfg(feature = "static_render")]
+    runtime: Option<Runtime>,
+    /// Options for rendering to HTML
+    #[cfg(feature = "static_render")]
+    render_options: RenderOptions,
+    /// Props which are embedded into the rendered HTML
+    #[cfg(feature = "static_render")]
+    embedded_state: EmbeddedState<COMP>,
+    /// Collects the tags rendered into the head
+    #[cfg(feature = "static_render")]
+    head: HeadCollector,
+    /// The page the rendered HTML is wrapped in
+    #[cfg(feature = "static_render")]
+    document: Option<HtmlDocument>,
+    /// Receives the status and headers set by components
+    #[cfg(feature = "static_render")]
+    response: ResponseContext,
 }
 
 impl<COMP> Default for App<COMP>
@@ -45,7 +97,7 @@ where
     /// Alias to `mount("body", ...)`.
     pub fn mount_to_body(self) -> ComponentLink<COMP> {
         // Bootstrap the component for `Window` environment only (not for `Worker`)
-        let element = get_document()
+        let element = document()
             .query_selector("body")
             .expect("can't get body node for rendering")
             .expect("can't unwrap body node");
@@ -57,16 +109,15 @@ where
     /// need to manipulate the body element. For example, adding/removing app-wide
     /// CSS classes of the body element.
     pub fn mount_as_body(self) -> ComponentLink<COMP> {
-        let html_element = get_document()
+        let html_element = document()
             .query_selector("html")
             .expect("can't get html node for rendering")
             .expect("can't unwrap html node");
-        let body_element = get_document()
+        let body_element = document()
             .query_selector("body")
             .expect("can't get body node for rendering")
             .expect("can't unwrap body node");
-        html_element
-            .remove_child(&body_element.into())
+        Renderer::element_remove_child(&html_element, &Renderer::element_as_node(&body_element))
             .expect("can't remove body child");
         self.scope.mount_in_place(
             html_element,
@@ -76,6 +127,48 @@ where
             COMP::Properties::default(),
         )
     }
+
+    /// Alternative to `mount` which takes over markup that is already present in `element`,
+    /// e.g. because it was rendered on the server, instead of clearing it. The DOM is walked
+    /// alongside the first render of the component: matching nodes are adopted and get their
+    /// listeners attached, and any part of the markup that doesn't match is rendered anew.
+    /// If you would like to pass props, use the `hydrate_with_props` method.
+    pub fn hydrate(self, element: Element) -> ComponentLink<COMP> {
+        self.hydrate_with_props(element, COMP::Properties::default())
+    }
+
+    /// Renders the component to HTML without mounting it to the document. If you would like
+    /// to pass props, use the `render_to_string_with_props` method.
+    #[cfg(feature = "static_render")]
+    pub fn render_to_string(self) -> Result<HtmlString, HtmlRenderError> {
+        self.render_to_string_with_props(COMP::Properties::default())
+    }
+
+    /// Renders the component to HTML and streams it into `writer` without mounting it to the
+    /// document. If you would like to pass props, use the `render_to_writer_with_props` method.
+    #[cfg(feature = "static_render")]
+    pub fn render_to_writer<W: HtmlWrite>(self, writer: W) -> Result<(), HtmlRenderError> {
+        self.render_to_writer_with_props(COMP::Properties::default(), writer)
+    }
+
+    /// Renders the component to HTML and returns it with the response set by its components.
+    /// If you would like to pass props, use the `render_to_response_with_props` method.
+    #[cfg(feature = "static_render")]
+    pub fn render_to_response(self) -> Result<(ServerResponse, HtmlString), HtmlRenderError> {
+        self.render_to_response_with_props(COMP::Properties::default())
+    }
+
+    /// Renders the component to HTML once the futures its components spawned have completed,
+    /// waiting at most `timeout`. If you would like to pass props, use the
+    /// `render_to_string_with_props_async` method.
+    #[cfg(feature = "static_render")]
+    pub async fn render_to_string_async(
+        self,
+        timeout: Duration,
+    ) -> Result<HtmlString, HtmlRenderError> {
+        self.render_to_string_with_props_async(COMP::Properties::default(), timeout)
+            .await
+    }
 }
 
 impl<COMP> App<COMP>
@@ -85,7 +178,41 @@ where
     /// Creates a new `App` with a component in a context.
     pub fn new() -> Self {
         let scope = Scope::new(None);
-        App { scope }
+        App {
+            scope,
+            mismatch_policy: MismatchPolicy::default(),
+            #[cfg(feature = "static_render")]
+            runtime: None,
+            #[cfg(feature = "static_render")]
+            render_options: RenderOptions::default(),
+            #[cfg(feature = "static_render")]
+            embedded_state: EmbeddedState::default(),
+            #[cfg(feature = "static_render")]
+            head: HeadCollector::default(),
+            #[cfg(feature = "static_render")]
+            document: None,
+            #[cfg(feature = "static_render")]
+            response: ResponseContext::default(),
+        }
+    }
+
+    /// Creates the component in `runtime` instead of the runtime of the thread. On the server
+    /// the app is rendered in this runtime instead of a new one.
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.scope = runtime.enter(|| Scope::new(None));
+        #[cfg(feature = "static_render")]
+        {
+            self.runtime = Some(runtime);
+        }
+        self
+    }
+
+    /// Decides what happens to markup which doesn't match the first render of the component
+    /// when hydrating, see `MismatchPolicy`. Mismatches are patched by default. In debug builds
+    /// every mismatch is also logged as a warning.
+    pub fn with_mismatch_policy(mut self, policy: MismatchPolicy) -> Self {
+        self.mismatch_policy = policy;
+        self
     }
 
     /// The main entry point of a Yew program which also allows passing properties. It works
@@ -102,10 +229,34 @@ where
             .mount_in_place(element, NodeRef::default(), None, NodeRef::default(), props)
     }
 
+    /// Alternative to `mount_with_props` which takes over markup that is already present in
+    /// `element`, e.g. because it was rendered on the server, instead of clearing it. The props
+    /// have to be the same ones the markup was rendered with, otherwise the differing parts
+    /// are rendered anew.
+    pub fn hydrate_with_props(
+        self,
+        element: Element,
+        props: COMP::Properties,
+    ) -> ComponentLink<COMP> {
+        let scope = self.scope;
+        hydration::run(self.mismatch_policy, &element, || {
+            let mut cursor = Renderer::element_first_child(&element);
+            let link = scope.hydrate_in_place(
+                element.clone(),
+                NodeRef::default(),
+                NodeRef::default(),
+                props,
+                &mut cursor,
+            );
+            remove_unclaimed(Some(&link.clone().into()), &element, cursor);
+            link
+        })
+    }
+
     /// Alias to `mount_with_props("body", ...)`.
     pub fn mount_to_body_with_props(self, props: COMP::Properties) -> ComponentLink<COMP> {
         // Bootstrap the component for `Window` environment only (not for `Worker`)
-        let element = get_document()
+        let element = document()
             .query_selector("body")
             .expect("can't get body node for rendering")
             .expect("can't unwrap body node");
@@ -117,16 +268,15 @@ where
     /// when you need to manipulate the body element. For example, adding/removing app-wide
     /// CSS classes of the body element.
     pub fn mount_as_body_with_props(self, props: COMP::Properties) -> ComponentLink<COMP> {
-        let html_element = get_document()
+        let html_element = document()
             .query_selector("html")
             .expect("can't get html node for rendering")
             .expect("can't unwrap html node");
-        let body_element = get_document()
+        let body_element = document()
             .query_selector("body")
             .expect("can't get body node for rendering")
             .expect("can't unwrap body node");
-        html_element
-            .remove_child(&body_element.into())
+        Renderer::element_remove_child(&html_element, &Renderer::element_as_node(&body_element))
             .expect("can't remove body child");
         self.scope.mount_in_place(
             html_element,
@@ -138,11 +288,599 @@ where
     }
 }
 
+impl<COMP> App<COMP>
+where
+    COMP: Component,
+    COMP::Properties: DeserializeOwned,
+{
+    /// Alternative to `hydrate_with_props` for pages which were rendered with
+    /// `with_embedded_props`. The props are decoded from the first state block of a root
+    /// component in the document and the element containing that block is hydrated.
+    ///
+    /// State blocks are kept in the document, so child props can still be read with
+    /// `yew::state::embedded_props` afterwards.
+    pub fn hydrate_from_document(self) -> Result<ComponentLink<COMP>, StateError> {
+        let script = state::root_block()?;
+        let props = state::decode_block(&script)?;
+        let element = script
+            .parent_element()
+            .ok_or_else(|| StateError::NotFound("the root element".into()))?;
+        Ok(self.hydrate_with_state(element, props))
+    }
+
+    /// Hydrates an island which was rendered by `Island<COMP>` on the server, see
+    /// `yew::island`. `boundary` is the `<yew-island>` element of the island, which can be
+    /// looked up with `find_island` or `find_islands`. The props are decoded from the state
+    /// block of the island and the markup outside of the boundary is left alone.
+    pub fn hydrate_island(self, boundary: Element) -> Result<ComponentLink<COMP>, StateError> {
+        let props = island::decode_props::<COMP>(&boundary)?;
+        Ok(self.hydrate_with_state(boundary, props))
+    }
+
+    /// Hydrates `element`, keeping the state blocks which follow the markup of the component.
+    fn hydrate_with_state(self, element: Element, props: COMP::Properties) -> ComponentLink<COMP> {
+        let scope = self.scope;
+        hydration::run(self.mismatch_policy, &element, || {
+            let mut cursor = Renderer::element_first_child(&element);
+            let link = scope.hydrate_in_place(
+                element.clone(),
+                NodeRef::default(),
+                NodeRef::default(),
+                props,
+                &mut cursor,
+            );
+            let link_scope = link.clone().into();
+            while let Some(node) = cursor {
+                cursor = Renderer::node_next_sibling(&node);
+                let keep = Renderer::node_as_element(&node)
+                    .is_some_and(|element| state::is_state_block(&element));
+                if !keep {
+                    hydration::report(
+                        Some(&link_scope),
+                        &element,
+                        Some(&node),
+                        "nothing".to_owned(),
+                    );
+                    Renderer::element_remove_child(&element, &node).expect("can't remove a child");
+                }
+            }
+            link
+        })
+    }
+}
+
+#[cfg(feature = "static_render")]
+impl<COMP> App<COMP>
+where
+    COMP: Component,
+    COMP::Properties: Serialize,
+{
+    /// Embeds the props of the component into the rendered HTML, encoded with `format`, so
+    /// that the client can pick them up with `hydrate_from_document`. The same format is used
+    /// for the props of child components selected with `with_embedded_child_props`.
+    pub fn with_embedded_props(mut self, format: StateFormat) -> Self {
+        self.embedded_state.embed_root(format);
+        self
+    }
+}
+
+#[cfg(feature = "static_render")]
+impl<COMP> App<COMP>
+where
+    COMP: Component,
+{
+    /// Embeds the props of every `CHILD` component which is created while rendering into the
+    /// rendered HTML under `key`, so that the client can read them with
+    /// `yew::state::embedded_props`. The props are encoded with the format passed to
+    /// `with_embedded_props`, JSON otherwise.
+    pub fn with_embedded_child_props<CHILD>(mut self, key: &'static str) -> Self
+    where
+        CHILD: Component,
+        CHILD::Properties: Serialize,
+    {
+        self.embedded_state.embed_child::<CHILD>(key);
+        self
+    }
+
+    /// Sets the options which are used when rendering the component to HTML.
+    pub fn with_render_options(mut self, options: RenderOptions) -> Self {
+        self.render_options = options;
+        self
+    }
+
+    /// Collects the tags which `Head`, `Title` and `Meta` components render into the head into
+    /// `collector`, so they can be written into the head of the page after rendering. The tags
+    /// are discarded otherwise.
+    pub fn with_head(mut self, collector: HeadCollector) -> Self {
+        self.head = collector;
+        self
+    }
+
+    /// Renders a complete HTML page, laid out by `document`, instead of a fragment. The head of
+    /// the page holds the tags of `Head`, `Title` and `Meta` components and the embedded state
+    /// follows the element the component is rendered into.
+    pub fn with_document(mut self, document: HtmlDocument) -> Self {
+        self.document = Some(document);
+        self
+    }
+
+    /// Renders the app for `request`, which its components can read with
+    /// `ComponentLink::request_context`. This is a shorthand for
+    /// `with_runtime(Runtime::with_request(request))`.
+    pub fn with_request(self, request: RequestContext) -> Self {
+        self.with_runtime(Runtime::with_request(request))
+    }
+
+    /// Lets components set the status and headers of the response in `context`, so they can
+    /// be sent once the app has been rendered. Components are rendered into the page until the
+    /// first write to the writer, so a streamed response can be started then.
+    pub fn with_response(mut self, context: ResponseContext) -> Self {
+        self.response = context;
+        self
+    }
+
+    /// Renders the component with `props` to HTML without mounting it to the document.
+    ///
+    /// The component tree is created in a detached element and the scheduler is driven until
+    /// every component has been created and rendered. Afterwards the tree is serialized and all
+    /// components are destroyed again. Futures spawned through component links are not
+    /// awaited, use `render_to_string_with_props_async` for that.
+    pub fn render_to_string_with_props(
+        self,
+        props: COMP::Properties,
+    ) -> Result<HtmlString, HtmlRenderError> {
+        let mut html = String::new();
+        self.render_to_writer_with_props(props, FmtWriter::new(&mut html))?;
+        Ok(HtmlString::new(html))
+    }
+
+    /// Renders the component with `props` to HTML and returns it with the response set by its
+    /// components, e.g. the status of a page which wasn't found or the location of a redirect.
+    pub fn render_to_response_with_props(
+        mut self,
+        props: COMP::Properties,
+    ) -> Result<(ServerResponse, HtmlString), HtmlRenderError> {
+        let context = ResponseContext::new();
+        self.response = context.clone();
+        let html = self.render_to_string_with_props(props)?;
+        Ok((context.response(), html))
+    }
+
+    /// Renders the component with `props` to HTML and streams it into `writer` without
+    /// mounting it to the document.
+    ///
+    /// Output is written as the tree is walked and `writer` is flushed after every component,
+    /// so the beginning of a page can be sent while the rest is still being serialized.
+    /// Embedded state blocks follow the markup.
+    pub fn render_to_writer_with_props<W: HtmlWrite>(
+        self,
+        props: COMP::Properties,
+        writer: W,
+    ) -> Result<(), HtmlRenderError> {
+        self.start_render(props)?.finish(writer)
+    }
+
+    /// Renders the component with `props` to HTML once the futures its components spawned
+    /// through their links have completed.
+    ///
+    /// Messages produced by the futures are handled like on the client, so components can load
+    /// their data in `create` or `update` before they are serialized. Futures spawned while
+    /// handling those messages are awaited as well. If they haven't all completed after
+    /// `timeout`, the tree is serialized as it is and the remaining futures are dropped.
+    pub async fn render_to_string_with_props_async(
+        self,
+        props: COMP::Properties,
+        timeout: Duration,
+    ) -> Result<HtmlString, HtmlRenderError> {
+        let mut render = self.start_render(props)?;
+        render.settle(Instant::now() + timeout).await;
+        let mut html = String::new();
+        render.finish(FmtWriter::new(&mut html))?;
+        Ok(HtmlString::new(html))
+    }
+
+    fn start_render(self, props: COMP::Properties) -> Result<Render<COMP>, HtmlRenderError> {
+        let App {
+            scope,
+            mismatch_policy: _,
+            runtime,
+            render_options,
+            embedded_state,
+            head,
+            document,
+            response,
+        } = self;
+        // Unless a runtime was picked, every render gets its own
+        let scope = match runtime {
+            Some(_) => scope,
+            None => Runtime::new().enter(|| Scope::new(None)),
+        };
+        let root_state = embedded_state.encode_root(&props)?;
+        let mut capture = embedded_state.start_capture();
+        let tasks = Rc::new(TaskSet::default());
+        let islands = IslandIds::default();
+        let element = Renderer::create_element("div");
+        let scope = tasks.enter(|| {
+            response.run(|| {
+                head.run(|| {
+                    capture.run(|| {
+                        islands.run(|| {
+                            scope.mount_in_place(
+                                element,
+                                NodeRef::default(),
+                                None,
+                                NodeRef::default(),
+                                props,
+                            )
+                        })
+                    })
+                })
+            })
+        });
+        Ok(Render {
+            scope,
+            render_options,
+            embedded_state,
+            root_state,
+            capture,
+            head,
+            document,
+            response,
+            islands,
+            tasks,
+        })
+    }
+}
+
+/// A component tree which is being rendered to HTML.
+#[cfg(feature = "static_render")]
+struct Render<COMP: Component> {
+    scope: Scope<COMP>,
+    render_options: RenderOptions,
+    embedded_state: EmbeddedState<COMP>,
+    root_state: Option<String>,
+    capture: Capture,
+    head: HeadCollector,
+    document: Option<HtmlDocument>,
+    response: ResponseContext,
+    /// Gives out the ids of the islands
+    islands: IslandIds,
+    /// Futures spawned by the components, dropped together with the render
+    tasks: Rc<TaskSet>,
+}
+
+#[cfg(feature = "static_render")]
+impl<COMP: Component> Render<COMP> {
+    /// Drives the spawned futures until they have all completed or `deadline` has passed.
+    async fn settle(&mut self, deadline: Instant) {
+        let (tasks, capture, head, response, islands) = (
+            &self.tasks,
+            &mut self.capture,
+            &self.head,
+            &self.response,
+            &self.islands,
+        );
+        // Dropped together with the future, which cancels the wake-up once the tasks completed
+        let mut timer = None;
+        future::poll_fn(|cx| {
+            let poll = response.run(|| head.run(|| capture.run(|| islands.run(|| tasks.poll(cx)))));
+            if poll.is_ready() || Instant::now() >= deadline {
+                return Poll::Ready(());
+            }
+            timer.get_or_insert_with(|| tasks.wake_at(deadline));
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Serializes the component tree into `writer` and destroys it.
+    fn finish<W: HtmlWrite>(self, mut writer: W) -> Result<(), HtmlRenderError> {
+        let Render {
+            mut scope,
+            render_options,
+            embedded_state,
+            root_state,
+            capture,
+            head,
+            document,
+            ..
+        } = self;
+        let options = &render_options;
+        let result = (|| {
+            if let Some(document) = &document {
+                document.write_start(&head, &mut writer, options)?;
+                writer.flush()?;
+            }
+            if let Some(root_vnode) = scope.root_vnode() {
+                root_vnode.write_html_with_options(&mut writer, options)?;
+            }
+            if let Some(document) = &document {
+                document.write_mount_end(&mut writer)?;
+            }
+            embedded_state.write_blocks(root_state, capture, &mut writer, options)?;
+            if let Some(document) = &document {
+                document.write_end(&mut writer, options)?;
+            }
+            writer.flush()
+        })();
+        scope.destroy();
+        result
+    }
+}
+
 /// Removes anything from the given element.
 fn clear_element(element: &Element) {
-    while let Some(child) = element.last_child() {
+    while let Some(child) = Renderer::element_last_child(element) {
+        Renderer::element_remove_child(element, &child).expect("can't remove a child");
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::backend::{Event, Node};
+    use crate::html::{Html, Properties, ShouldRender};
+    use crate::{html, utils::document};
+    use serde::Deserialize;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Counter {
+        link: ComponentLink<Self>,
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+            Counter { link, count: 0 }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            self.count += 1;
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <div class="counter">
+                    <span>{ self.count }</span>
+                    <button onclick=self.link.callback(|_| ())>{ "+1" }</button>
+                    <Label />
+                </div>
+            }
+        }
+    }
+
+    struct Label;
+
+    impl Component for Label {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Label
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p>{ "clicks" }</p> }
+        }
+    }
+
+    fn element(parent: &Element, tag: &str, text: &str) -> Element {
+        let element = document().create_element(tag).unwrap();
+        if !text.is_empty() {
+            element
+                .append_child(&document().create_text_node(text))
+                .unwrap();
+        }
+        parent.append_child(&element).unwrap();
         element
-            .remove_child(&child.into())
-            .expect("can't remove a child");
+    }
+
+    /// Builds the markup `Counter` renders on the server.
+    fn server_markup() -> Element {
+        let root = document().create_element("main").unwrap();
+        let div = element(&root, "div", "");
+        div.set_attribute("class", "counter").unwrap();
+        element(&div, "span", "0");
+        element(&div, "button", "+1");
+        element(&div, "p", "clicks");
+        root
+    }
+
+    #[test]
+    fn hydrate_adopts_server_markup() {
+        let root = server_markup();
+        let nodes: Vec<Node> = root
+            .query_selector_all("div, span, button, p")
+            .unwrap()
+            .into_iter()
+            .map(Node::from)
+            .collect();
+        let count_text = root.query_selector("span").unwrap().unwrap().first_child();
+
+        let link = App::<Counter>::new().hydrate(root.clone());
+
+        let hydrated: Vec<Node> = root
+            .query_selector_all("div, span, button, p")
+            .unwrap()
+            .into_iter()
+            .map(Node::from)
+            .collect();
+        assert_eq!(nodes, hydrated);
+        assert_eq!(root.child_nodes().len(), 1);
+
+        let button = root.query_selector("button").unwrap().unwrap();
+        button.dispatch_event(&Event::new("click"));
+        let span = root.query_selector("span").unwrap().unwrap();
+        assert_eq!(span.first_child(), count_text);
+        assert_eq!(span.text_content(), Some("1".to_owned()));
+        assert_eq!(link.get_component().unwrap().count, 1);
+    }
+
+    #[test]
+    fn hydrate_replaces_mismatched_markup() {
+        let root = document().create_element("main").unwrap();
+        let div = element(&root, "div", "");
+        div.set_attribute("class", "stale").unwrap();
+        div.set_attribute("data-stale", "").unwrap();
+        element(&div, "em", "0");
+        element(&div, "button", "-1");
+        element(&div, "p", "clicks");
+        element(&div, "footer", "");
+        element(&root, "aside", "");
+
+        App::<Counter>::new().hydrate(root.clone());
+
+        assert!(root
+            .query_selector("div")
+            .unwrap()
+            .unwrap()
+            .is_same_node(Some(&div)));
+        assert_eq!(div.get_attribute_names(), vec!["class"]);
+        assert_eq!(div.class_name(), "counter");
+        let tags: Vec<String> = div.children().iter().map(Element::local_name).collect();
+        assert_eq!(tags, vec!["span", "button", "p"]);
+        assert_eq!(div.text_content(), Some("0+1clicks".to_owned()));
+        assert_eq!(root.child_nodes().len(), 1);
+
+        let button = root.query_selector("button").unwrap().unwrap();
+        button.dispatch_event(&Event::new("click"));
+        assert_eq!(div.text_content(), Some("1+1clicks".to_owned()));
+    }
+
+    #[derive(Clone, Properties, Serialize, Deserialize)]
+    struct GreetingProps {
+        name: String,
+    }
+
+    struct Greeting {
+        props: GreetingProps,
+    }
+
+    impl Component for Greeting {
+        type Message = ();
+        type Properties = GreetingProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Greeting { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p>{ format!("Hello, {}", self.props.name) }</p> }
+        }
+    }
+
+    struct Greetings;
+
+    impl Component for Greetings {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Greetings
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <>
+                    <Greeting name="Ferris" />
+                    <Greeting name="</script>" />
+                </>
+            }
+        }
+    }
+
+    #[test]
+    fn it_embeds_props() {
+        let props = GreetingProps {
+            name: "Ferris".into(),
+        };
+        let html = App::<Greeting>::new()
+            .with_embedded_props(StateFormat::Json)
+            .render_to_string_with_props(props)
+            .unwrap();
+        assert_eq!(
+            html.as_str(),
+            "<p>Hello, Ferris</p>\
+             <script type=\"application&#x2F;yew&#x2D;state\" data-format=\"json\" data-root=\"\">\
+             {\"name\":\"Ferris\"}</script>"
+        );
+    }
+
+    #[test]
+    fn it_embeds_child_props() {
+        let html = App::<Greetings>::new()
+            .with_embedded_child_props::<Greeting>("greeting")
+            .render_to_string()
+            .unwrap();
+        assert!(html.as_str().ends_with(
+            "<script type=\"application&#x2F;yew&#x2D;state\" data-format=\"json\" data-key=\"greeting\">\
+             [{\"name\":\"Ferris\"},{\"name\":\"\\u003c/script>\"}]</script>"
+        ));
+    }
+
+    #[test]
+    fn hydrate_from_document_decodes_embedded_props() {
+        let root = element(&document().body().unwrap(), "main", "");
+        let greeting = element(&root, "p", "Hello, Ferris");
+        let script = element(&root, "script", r#"{"name":"Ferris"}"#);
+        script
+            .set_attribute("type", state::STATE_SCRIPT_TYPE)
+            .unwrap();
+        script.set_attribute("data-format", "json").unwrap();
+        script.set_attribute("data-root", "").unwrap();
+
+        let link = App::<Greeting>::new().hydrate_from_document().unwrap();
+
+        assert_eq!(link.get_component().unwrap().props.name, "Ferris");
+        assert!(root
+            .query_selector("p")
+            .unwrap()
+            .unwrap()
+            .is_same_node(Some(&greeting)));
+        assert!(root.children().last().unwrap().is_same_node(Some(&script)));
+        document().body().unwrap().remove_child(&root).unwrap();
+    }
+
+    #[test]
+    fn hydrate_from_document_requires_embedded_props() {
+        let result = App::<Greeting>::new().hydrate_from_document();
+        assert!(matches!(result, Err(StateError::NotFound(_))));
     }
 }
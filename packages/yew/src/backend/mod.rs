@@ -3,12 +3,20 @@
 //!     - std_web
 //!     - web_sys
 //!     - static_render
+//!
+//! If both `web_sys` and `static_render` are enabled, builds for a browser (`wasm32`) use
+//! `web_sys` and all other builds use the in-memory DOM, see `build.rs`. Code which depends on
+//! the selected backend checks `cfg(yew_backend = "web_sys")` or
+//! `cfg(yew_backend = "static_render")`.
 
 use cfg_if::cfg_if;
+#[cfg(yew_backend = "web_sys")]
 use cfg_match::cfg_match;
+#[cfg(yew_backend = "web_sys")]
 use wasm_bindgen::JsCast;
-use crate::NodeRef;
 
+/// The operations Yew's virtual DOM needs from a DOM implementation.
+#[allow(clippy::result_unit_err)]
 pub trait DomBackend {
     type ButtonElement;
     type ChangeData;
@@ -35,30 +43,77 @@ pub trait DomBackend {
     /// Returns the `host` for the current document. Useful for connecting to the server which serves the app.
     fn get_host() -> Result<String, anyhow::Error>;
 
+    // Node creation
+    fn create_element(tag: &str) -> Self::Element;
+    fn create_element_ns(namespace: Option<&str>, tag: &str) -> Self::Element;
+    fn create_text_node(text: &str) -> Self::TextNode;
+    /// Parses a fragment of HTML into detached nodes.
+    fn parse_html(html: &str) -> Vec<Self::Node>;
+
+    // Text-related methods
+    fn text_as_node(text: &Self::TextNode) -> Self::Node;
+    fn text_data(text: &Self::TextNode) -> String;
+    fn text_set_data(text: &Self::TextNode, data: &str);
+
+    // Node traversal, used to walk existing markup when hydrating
+    fn node_next_sibling(node: &Self::Node) -> Option<Self::Node>;
+    fn node_as_element(node: &Self::Node) -> Option<Self::Element>;
+    fn node_as_text(node: &Self::Node) -> Option<Self::TextNode>;
+    /// Returns the data of `node` if it is a comment.
+    fn node_comment_data(node: &Self::Node) -> Option<String>;
+
     // Element-related methods
     fn element_as_node(element: &Self::Element) -> Self::Node;
-    fn element_last_child(element: &Self::Element) -> Option<Self::Element>;
-    fn element_remove_child(
+    fn element_first_child(element: &Self::Element) -> Option<Self::Node>;
+    fn element_last_child(element: &Self::Element) -> Option<Self::Node>;
+    fn element_insert_before(
         element: &Self::Element,
-        child: &Self::Element,
-    ) -> Result<Self::Node, ()>;
-    fn cast_node_ref<INTO>(node_ref: &NodeRef) -> Option<INTO>;
+        node: &Self::Node,
+        next_sibling: Option<&Self::Node>,
+    ) -> Result<(), ()>;
+    fn element_remove_child(element: &Self::Element, child: &Self::Node) -> Result<Self::Node, ()>;
+    fn element_namespace_uri(element: &Self::Element) -> Option<String>;
+    fn element_local_name(element: &Self::Element) -> String;
+    fn element_attribute_names(element: &Self::Element) -> Vec<String>;
+    fn element_get_attribute(element: &Self::Element, key: &str) -> Option<String>;
+    fn element_set_attribute(element: &Self::Element, key: &str, value: &str) -> Result<(), ()>;
+    fn element_remove_attribute(element: &Self::Element, key: &str) -> Result<(), ()>;
+
+    // Form control methods
+    fn element_as_input(element: &Self::Element) -> Option<Self::InputElement>;
+    fn element_as_textarea(element: &Self::Element) -> Option<Self::TextAreaElement>;
+    fn element_as_button(element: &Self::Element) -> Option<Self::ButtonElement>;
+    fn input_value(input: &Self::InputElement) -> String;
+    fn input_set_value(input: &Self::InputElement, value: &str);
+    fn input_set_type(input: &Self::InputElement, kind: &str);
+    fn input_set_checked(input: &Self::InputElement, checked: bool);
+    fn textarea_value(textarea: &Self::TextAreaElement) -> String;
+    fn textarea_set_value(textarea: &Self::TextAreaElement, value: &str);
+    fn button_set_type(button: &Self::ButtonElement, kind: &str);
 
     fn oninput_handler(this: &Self::Element, event: Self::InputEvent) -> Self::InputData;
     fn onchange_handler(this: &Self::Element) -> Self::ChangeData;
 }
 
 cfg_if! {
-    if #[cfg(feature = "web_sys")] {
+    if #[cfg(yew_backend = "web_sys")] {
         use ::web_sys::{FileList, HtmlSelectElement as SelectElement};
         mod web_sys;
         pub use self::web_sys::{ Renderer };
+    } else if #[cfg(yew_backend = "static_render")] {
+        mod smr;
+        pub use self::smr::{
+            AnimationEvent, DomError, DragEvent, ErrorEvent, Event, FileList, FocusEvent,
+            KeyboardEvent, Location, MouseEvent, PointerEvent, ProgressEvent, Renderer,
+            SelectElement, Text, TouchEvent, TransitionEvent, UiEvent, WheelEvent,
+        };
     }
 }
 
 // Re-export types from the specific renderer backend
 
 pub type ChangeData = <Renderer as DomBackend>::ChangeData;
+pub type Document = <Renderer as DomBackend>::Document;
 pub type EventListener = <Renderer as DomBackend>::EventListener;
 pub type Element = <Renderer as DomBackend>::Element;
 pub type Node = <Renderer as DomBackend>::Node;
@@ -68,7 +123,9 @@ pub type InputElement = <Renderer as DomBackend>::InputElement;
 pub type InputEvent = <Renderer as DomBackend>::InputEvent;
 pub type ButtonElement = <Renderer as DomBackend>::ButtonElement;
 pub type TextAreaElement = <Renderer as DomBackend>::TextAreaElement;
+pub type Window = <Renderer as DomBackend>::Window;
 
+#[cfg(yew_backend = "web_sys")]
 fn base_onchange_handler(this: &Element) -> ChangeData {
     match this.node_name().as_ref() {
         "INPUT" => {
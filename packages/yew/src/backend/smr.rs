@@ -1,644 +1,2271 @@
-// use super::DomBackend;
-// use std::cell::Cell;
-// use std::rc::Rc;
-
-// // pub CURRENT_NODE_ID: u32 = 1;
-
-// enum NodeData {
-//     Element(ElementData),
-//     Text(TextData),
-// }
-
-// struct ElementData {}
-
-// struct TextData {}
-
-// /// Represents a single rendering backend
-// #[derive(Debug)]
-// pub struct Renderer {}
-
-// impl DomBackend for Renderer {
-//     type Element = Element;
-//     type Node = Node;
-//     type Document = Document;
-//     type Window = Window;
-
-//     fn element_as_node(element: &Self::Element) -> Self::Node {
-//         todo!()
-//     }
-
-//     fn element_last_child(element: &Self::Element) -> Option<Self::Element> {
-//         todo!()
-//     }
-
-//     fn element_remove_child(element: &Self::Element, child: &Self::Element) -> Result<Node, ()> {
-//         todo!()
-//     }
-
-//     fn cast_node_ref<INTO>(node_ref: &crate::NodeRef) -> Option<INTO> {
-//         todo!()
-//     }
-
-//     fn get_document() -> Self::Document {
-//         todo!()
-//     }
-
-//     fn get_window() -> Self::Window {
-//         todo!()
-//     }
-
-//     fn get_origin() -> Result<String, anyhow::Error> {
-//         todo!()
-//     }
-
-//     fn get_host() -> Result<String, anyhow::Error> {
-//         todo!()
-//     }
-// }
-
-// /// Represents a mocked, mostly nonfunctional Event Listener
-// #[derive(Debug)]
-// pub struct EventListener;
-
-// /// Represents a mocked, mostly nonfunctional Input Event
-// #[derive(Debug)]
-// pub struct InputEvent {}
-
-// /// Represents a mocked, mostly nonfunctional version of a File List
-// /// See: https://developer.mozilla.org/en-US/docs/Web/API/FileList
-// #[derive(Debug)]
-// pub struct FileList {}
-
-// /// Represents an Input element
-// #[derive(Debug)]
-// pub struct InputElement {}
-
-// impl InputElement {
-//     pub fn set_type(&self, value: &str) {
-//         todo!()
-//     }
-// }
-
-// /// Represents a Select element
-// #[derive(Debug)]
-// pub struct SelectElement {}
-
-// /// Represents a TextArea element
-// #[derive(Debug)]
-// pub struct TextAreaElement {}
-
-// impl TextAreaElement {
-//     pub fn set_value(&self, value: &str) {
-//         todo!()
-//     }
-// }
-
-// impl InputElement {
-//     pub fn set_value(&self, value: &str) {
-//         todo!()
-//     }
-// }
-
-// /// Represents a Button element
-// #[derive(Debug)]
-// pub struct ButtonElement {}
-
-// /// Represents a Text node
-// #[derive(Debug, Clone)]
-// pub struct Text {}
-
-// impl Element {}
-
-// #[derive(Debug, Clone)]
-// pub struct EventListener;
-
-// #[derive(Debug, Clone)]
-// pub struct Document;
-// impl Text {
-//     pub fn set_node_value(&self, value: Option<&str>) {}
-// }
-
-// /// Represents a generic Element
-// #[derive(Debug, Clone)]
-// pub struct Element {
-//     pub node_data: Rc<Cell<NodeData>>,
-// }
-
-// impl Element {
-//     pub fn remove_attribute(&self, name: &str) -> Result<(), ()> {
-//         todo!("remove attributes")
-//     }
-//     pub fn as_node(&self) -> Node {
-//         Node {}
-//     }
-//     pub fn last_child(&self) -> Option<Element> {
-//         todo!("Enable last child in SSR dom");
-//     }
-//     pub fn remove_child(&self, child: &Node) -> Result<Node, ()> {
-//         todo!("Enable child removal in SSR dom");
-//     }
-//     pub fn set_attribute(&self, name: &str, value: &str) -> Result<(), ()> {
-//         todo!("Enable attributes in SSR dom");
-//     }
-//     pub fn dyn_ref<T>(&self) -> Option<&T> {
-//         todo!("Support dyn ref casting in SSR dom");
-//     }
-
-//     pub fn namespace_uri(&self) -> Option<String> {
-//         todo!()
-//     }
-// }
-
-// impl From<&Element> for &Node {
-//     fn from(element: &Element) -> Self {
-//         todo!("Implement &element -> &node conversion")
-//     }
-// }
-
-// impl From<&Text> for &Node {
-//     fn from(text: &Text) -> Self {
-//         todo!("Implement &Text -> &node conversion")
-//     }
-// }
-
-// impl From<Element> for Node {
-//     fn from(element: Element) -> Self {
-//         todo!("Implement element -> node conversion")
-//     }
-// }
-
-// impl From<Node> for Element {
-//     fn from(node: Node) -> Self {
-//         let node_data = node.node_data;
-//         match (*node_data).get() {
-//             NodeData::Element(_) => {}
-//             _ => {
-//                 panic!("Cannot convert a non-element Node to an Element");
-//             }
-//         }
-//         Element { node_data }
-//     }
-// }
-
-// /// Represents a generic Node
-// #[derive(PartialEq, Debug, Default, Clone)]
-// pub struct Node;
-
-// #[derive(PartialEq, Debug, Default, Clone)]
-// pub struct TextNode;
-
-// #[derive(Debug, Clone)]
-// pub struct Node {
-//     pub node_data: Rc<Cell<NodeData>>,
-// }
-
-// impl Node {
-//     pub fn first_child(&self) -> Option<Node> {
-//         todo!("Implement first child")
-//     }
-// }
-
-// impl From<Option<Element>> for Node {
-//     fn from(_: Option<Element>) -> Self {
-//         todo!()
-//     }
-// }
-
-// impl From<Text> for Node {
-//     fn from(_: Text) -> Self {
-//         todo!()
-//     }
-// }
-
-// /// Represents a Window
-// #[derive(Debug)]
-// pub struct Window;
-
-// impl Window {
-//     pub fn location(&self) -> Option<String> {
-//         Some(format!("blah"))
-//     }
-// }
-
-// pub fn get_window() -> Window {
-//     Window
-// }
-
-// /// Represents a Document
-// #[derive(Debug)]
-// pub struct Document;
-// impl Document {
-//     pub fn location(&self) -> Option<String> {
-//         Some(format!("blah"))
-//     }
-
-//     pub fn query_selector(&self, id: &str) -> Option<Option<Element>> {
-//         Some(Some(Element))
-//     }
-//     pub fn create_element(&self, id: &str) -> Option<Element> {
-//         todo!("Create element");
-//     }
-//     pub fn create_text_node(&self, id: &str) -> Option<Element> {
-//         todo!("Create element");
-//     }
-// }
-
-// impl Node {
-//     pub fn first_child(&self) -> Option<Node> {
-//         todo!("Implement first child")
-//     }
-// }
-
-// impl From<Option<Element>> for Node {
-//     fn from(_: Option<Element>) -> Self {
-//         todo!()
-//     }
-// }
-
-// impl Element {
-//     pub fn as_node(&self) -> Node {
-//         Node {}
-//     }
-//     pub fn last_child(&self) -> Option<Element> {
-//         todo!("Enable last child in SSR dom");
-//         Some(Element)
-//     }
-//     pub fn remove_child(&self, child: &Element) -> Option<()> {
-//         todo!("Enable child removal in SSR dom");
-//         Some(())
-//     }
-
-//     pub fn query_selector(&self, selectors: &str) -> Option<Option<Element>> {
-//         todo!("query selector")
-//     }
-//     pub fn create_element(&self, qualified_name: &str) -> Option<Element> {
-//         todo!("Create element");
-//     }
-//     pub fn create_element_ns(
-//         &self,
-//         namespace: Option<&str>,
-//         qualified_name: &str,
-//     ) -> Option<Element> {
-//         todo!("Create element");
-//     }
-//     pub fn create_text_node(&self, id: &str) -> Text {
-//         todo!("Create element");
-//     }
-// }
-
-// pub fn get_document() -> Document {
-//     Document
-// }
-
-// pub fn get_origin() -> String {
-//     format!("blah")
-// }
-
-// pub fn get_host() -> String {
-//     format!("blah")
-// }
-
-// mod stringify_smr {
-
-//     //! This module contains Yew's implementation of Sans-Mount Rendering (SMR), to support
-//     //! future feature work such as Static Site Generation and Server-Side Rendering (SSR).
-//     //! This functionality allows Yew Components to be rendered to a string without needing
-//     //! to be mounted onto a DOM node first.
-//     //!
-//     //! *This module is only available if the `static_render` feature is enabled.*
-
-//     use crate::virtual_dom::{VComp, VList, VNode, VTag, VText};
-//     use htmlescape;
-//     use std::convert::TryFrom;
-//     use std::fmt::{self, Display, Formatter};
-//     use thiserror::Error as ThisError;
-//     /// Represents a block of HTML string content generated via Sans-Mount Rendering
-//     #[derive(Debug, PartialEq, Eq, Clone)]
-//     pub struct HtmlString(String);
-
-//     impl HtmlString {
-//         fn new(html: String) -> Self {
-//             Self(html)
-//         }
-//     }
-
-//     impl Display for HtmlString {
-//         fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-//             write!(f, "{}", self.0)
-//         }
-//     }
-
-//     /// Represents errors associated with conversion of Yew structures to HTML.
-//     #[derive(Debug, ThisError)]
-//     pub enum HtmlRenderError {
-//         /// Malformed/unserializable attribute name
-//         #[error("cannot serialize invalid attribute name `{0}`")]
-//         InvalidAttributeName(String),
-
-//         /// Malformed/unserializable tag name
-//         #[error("cannot serialize invalid tag name `{0}`")]
-//         InvalidTagName(String),
-
-//         /// Unsupported VRef serialization
-//         #[error("cannot serialize VRef because that is unsupported")]
-//         UnserializableVRef,
-//     }
-
-//     impl TryFrom<VComp> for HtmlString {
-//         type Error = HtmlRenderError;
-
-//         fn try_from(value: VComp) -> Result<HtmlString, HtmlRenderError> {
-//             let html: String = match &value.scope {
-//                 None => "".to_string(),
-//                 Some(scope) => match scope.root_vnode() {
-//                     None => "".to_string(),
-//                     Some(root_vnode) => HtmlString::try_from(root_vnode.clone())?.to_string(),
-//                 },
-//             };
-//             Ok(HtmlString::new(html))
-//         }
-//     }
-
-//     /// HTML output for a VTag is not necessarily deterministic due to the
-//     /// serialization of props which do not have a particular ordering.
-//     impl TryFrom<VTag> for HtmlString {
-//         type Error = HtmlRenderError;
-
-//         fn try_from(value: VTag) -> Result<HtmlString, HtmlRenderError> {
-//             let mut result = "".to_string();
-//             let tag_name = htmlescape::encode_minimal(&value.tag).to_lowercase();
-
-//             result.push_str(&format!("<{}", tag_name));
-
-//             value.attributes.iter().for_each(|(key_unclean, value)| {
-//                 let key = key_unclean.to_lowercase();
-
-//                 // textareas' innerHTML properties are specified via the `value` prop which doesn't
-//                 // exist in HTML, so we defer this prop's serialization until later in the process.
-//                 if tag_name == "textarea" && key == "value" {
-//                     return;
-//                 }
-
-//                 result.push_str(&format!(
-//                     " {}=\"{}\"",
-//                     htmlescape::encode_minimal(&key),
-//                     htmlescape::encode_attribute(&value)
-//                 ));
-//             });
-
-//             if value.checked {
-//                 result.push_str(&" checked")
-//             }
-
-//             if tag_name == "input" {
-//                 if let Some(kind) = &value.kind {
-//                     result.push_str(&format!(
-//                         " type=\"{}\"",
-//                         htmlescape::encode_attribute(&kind)
-//                     ));
-//                 }
-//             }
-
-//             let children_html = match tag_name.as_ref() {
-//                 "textarea" => {
-//                     let vtext = VText::new(value.value.clone().unwrap_or_else(String::new));
-//                     HtmlString::try_from(vtext)
-//                 }
-//                 _ => HtmlString::try_from(value.children),
-//             }?
-//             .to_string();
-
-//             if children_html == "" {
-//                 result.push_str(&" />");
-//             } else {
-//                 result.push_str(&">");
-//                 result.push_str(&children_html);
-//                 result.push_str(&format!("</{}>", tag_name));
-//             }
-
-//             result.shrink_to_fit();
-//             Ok(HtmlString::new(result))
-//         }
-//     }
-
-//     impl TryFrom<VText> for HtmlString {
-//         type Error = HtmlRenderError;
-
-//         fn try_from(value: VText) -> Result<HtmlString, HtmlRenderError> {
-//             Ok(HtmlString::new(htmlescape::encode_minimal(&value.text)))
-//         }
-//     }
-
-//     impl TryFrom<VList> for HtmlString {
-//         type Error = HtmlRenderError;
-
-//         fn try_from(value: VList) -> Result<HtmlString, HtmlRenderError> {
-//             let mut result = "".to_string();
-//             for child in value.children {
-//                 let html = HtmlString::try_from(child)?.to_string();
-//                 result.push_str(&html);
-//             }
-
-//             result.shrink_to_fit();
-//             Ok(HtmlString::new(result))
-//         }
-//     }
-
-//     impl TryFrom<VNode> for HtmlString {
-//         type Error = HtmlRenderError;
-
-//         fn try_from(value: VNode) -> Result<HtmlString, HtmlRenderError> {
-//             Ok(match value {
-//                 VNode::VTag(vtag) => HtmlString::try_from(*vtag)?,
-//                 VNode::VText(vtext) => HtmlString::try_from(vtext)?,
-//                 VNode::VComp(vcomp) => HtmlString::try_from(vcomp)?,
-//                 VNode::VList(vlist) => HtmlString::try_from(vlist)?,
-//                 VNode::VRef(_) => Err(HtmlRenderError::UnserializableVRef)?,
-//             })
-//         }
-//     }
-
-//     #[cfg(test)]
-//     mod test_vtext {
-//         use super::HtmlString;
-//         use crate::html;
-//         use std::convert::TryFrom;
-
-//         #[cfg(feature = "wasm_test")]
-//         use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
-
-//         #[cfg(feature = "wasm_test")]
-//         wasm_bindgen_test_configure!(run_in_browser);
-
-//         #[test]
-//         fn text_as_root_smr() {
-//             let a = html! {
-//                 "Text Node As Root"
-//             };
-
-//             let b = html! {
-//                 { "Text Node As Root" }
-//             };
-
-//             assert_eq!(
-//                 HtmlString::try_from(a.clone()).expect("HTML stringify error"),
-//                 HtmlString::try_from(b.clone()).expect("HTML stringify error")
-//             );
-//             assert!(
-//                 HtmlString::try_from(b)
-//                     .expect("HTML stringify error")
-//                     .to_string()
-//                     == "Text Node As Root"
-//             );
-//         }
-
-//         #[test]
-//         fn special_chars_smr() {
-//             let a = html! {
-//                 "some special-chars\"> here!"
-//             };
-
-//             let b = html! {
-//                 { "some special-chars\"> here!" }
-//             };
-
-//             assert_eq!(
-//                 HtmlString::try_from(a.clone()).expect("HTML stringify error"),
-//                 HtmlString::try_from(b.clone()).expect("HTML stringify error")
-//             );
-//             assert_eq!(
-//                 HtmlString::try_from(b.clone())
-//                     .expect("HTML stringify error")
-//                     .to_string(),
-//                 "some special-chars&quot;&gt; here!"
-//             );
-//         }
-//     }
-
-//     #[cfg(test)]
-//     mod tests_vtag {
-//         use super::*;
-//         use crate::html;
-//         use crate::html::NodeRef;
-//         use std::convert::TryFrom;
-
-//         #[cfg(feature = "wasm_test")]
-//         use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
-
-//         #[cfg(feature = "wasm_test")]
-//         wasm_bindgen_test_configure!(run_in_browser);
-
-//         #[test]
-//         fn it_stringifies_simple() {
-//             let p = html! {
-//                 <p></p>
-//             };
-
-//             if let VNode::VTag(p) = p {
-//                 let p_html = HtmlString::try_from(*p)
-//                     .expect("HTML stringify error")
-//                     .to_string();
-
-//                 assert_eq!(p_html, "<p />");
-//             } else {
-//                 assert!(false);
-//             }
-//         }
-
-//         #[test]
-//         fn it_stringifies_complex() {
-//             let other_sym = "bar";
-//             let div = html! {
-//                 <div class=("foo", other_sym)>
-//                     { "quux" }
-//                 </div>
-//             };
-//             let p = html! {
-//                 <p aria-controls="it-works">
-//                     { "test" }
-//                     {div}
-//                 </p>
-//             };
-
-//             if let VNode::VTag(p) = p {
-//                 let p_html = HtmlString::try_from(*p)
-//                     .expect("HTML stringify error")
-//                     .to_string();
-
-//                 assert_eq!(
-//                 p_html,
-//                 "<p aria-controls=\"it&#x2D;works\">test<div class=\"foo&#x20;bar\">quux</div></p>"
-//             );
-//             } else {
-//                 assert!(false);
-//             }
-//         }
-
-//         #[test]
-//         fn it_stringifies_attrs() {
-//             let div = html! {
-//                 <div a="b" b="a" />
-//             };
-
-//             if let VNode::VTag(div) = div {
-//                 let div_html = HtmlString::try_from(*div)
-//                     .expect("HTML stringify error")
-//                     .to_string();
-//                 let order_1 = "<div a=\"b\" b=\"a\" />";
-//                 let order_2 = "<div b=\"a\" a=\"b\" />";
-//                 assert!(div_html == order_1 || div_html == order_2);
-//             } else {
-//                 assert!(false);
-//             }
-//         }
-
-//         #[test]
-//         fn it_does_not_stringify_special_attrs() {
-//             let node_ref = NodeRef::default();
-
-//             let div = html! {
-//                 <div ref=node_ref />
-//             };
-
-//             if let VNode::VTag(div) = div {
-//                 let div_html = HtmlString::try_from(*div)
-//                     .expect("HTML stringify error")
-//                     .to_string();
-//                 assert_eq!(div_html, "<div />");
-//             } else {
-//                 assert!(false);
-//             }
-//         }
-//     }
-
-//     mod test_mounting {
-//         use crate::html;
-
-//         struct TestApplication {}
-//         impl crate::Component for TestApplication {
-//             type Message = ();
-
-//             type Properties = ();
-
-//             fn create(props: (), link: crate::ComponentLink<Self>) -> Self {
-//                 Self {}
-//             }
-
-//             fn update(&mut self, msg: ()) -> bool {
-//                 true
-//             }
-
-//             fn change(&mut self, _props: ()) -> bool {
-//                 true
-//             }
-
-//             fn view(&self) -> crate::Html {
-//                 html! {
-//                     <div>
-//                         <h1>{"This is rendered without a dom!"}</h1>
-//                     </div>
-//                 }
-//             }
-//         }
-//     }
-// }
+//! This module contains the in-memory DOM used by the `static_render` backend.
+//!
+//! The node tree mirrors the part of the browser DOM that Yew's virtual DOM relies on:
+//! elements, text nodes, attributes, form control state, event listeners and the
+//! parent/child/sibling links between nodes. This lets components be mounted, diffed and
+//! inspected natively, without a browser or a wasm runtime.
+//!
+//! *This module is only available if the in-memory DOM is the selected backend.*
+
+use super::DomBackend;
+use crate::request::RequestContext;
+use crate::runtime::Runtime;
+use crate::virtual_dom::vtag::{
+    children_namespace, element_namespace, is_raw_text_element, is_valid_name, is_void_element,
+    HTML_NAMESPACE,
+};
+use http::Uri;
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::Peekable;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+use std::str::Chars;
+use thiserror::Error as ThisError;
+
+/// The document and location of the window of a runtime. Every runtime has its own, so pages
+/// which are rendered in different runtimes can't see each other's nodes or URLs.
+struct WindowState {
+    document: Document,
+    location: RefCell<Uri>,
+}
+
+impl WindowState {
+    fn current() -> Rc<Self> {
+        let runtime = Runtime::current();
+        runtime.local(|| WindowState {
+            document: Document::new(),
+            location: RefCell::new(
+                runtime
+                    .request()
+                    .and_then(|request| request_url(&request))
+                    .unwrap_or_else(|| Uri::from_static("http://localhost/")),
+            ),
+        })
+    }
+}
+
+/// Returns the URL `request` was sent to, if its URI or `Host` header names the host.
+fn request_url(request: &RequestContext) -> Option<Uri> {
+    let uri = request.uri();
+    if uri.scheme().is_some() {
+        return Some(uri.clone());
+    }
+    let path = uri.path_and_query().map_or("/", |path| path.as_str());
+    Uri::builder()
+        .scheme("http")
+        .authority(request.header(http::header::HOST.as_str())?)
+        .path_and_query(path)
+        .build()
+        .ok()
+}
+
+/// Represents the in-memory rendering backend.
+#[derive(Debug)]
+pub struct Renderer {}
+
+impl DomBackend for Renderer {
+    type ButtonElement = ButtonElement;
+    type ChangeData = ChangeData;
+    type Document = Document;
+    type Element = Element;
+    type EventListener = EventListener;
+    type InputData = InputData;
+    type InputElement = InputElement;
+    type InputEvent = InputEvent;
+    type Node = Node;
+    type TextAreaElement = TextAreaElement;
+    type TextNode = Text;
+    type Window = Window;
+
+    fn get_window() -> Self::Window {
+        Window {}
+    }
+
+    fn get_document() -> Self::Document {
+        WindowState::current().document.clone()
+    }
+
+    fn get_origin() -> Result<String, anyhow::Error> {
+        Ok(Self::get_window().location().origin())
+    }
+
+    fn get_host() -> Result<String, anyhow::Error> {
+        Ok(Self::get_window().location().host())
+    }
+
+    fn create_element(tag: &str) -> Self::Element {
+        Self::get_document()
+            .create_element(tag)
+            .expect("can't create element for vtag")
+    }
+
+    fn create_element_ns(namespace: Option<&str>, tag: &str) -> Self::Element {
+        Self::get_document()
+            .create_element_ns(namespace, tag)
+            .expect("can't create namespaced element for vtag")
+    }
+
+    fn create_text_node(text: &str) -> Self::TextNode {
+        Self::get_document().create_text_node(text)
+    }
+
+    fn parse_html(html: &str) -> Vec<Self::Node> {
+        let container = Self::create_element("div");
+        container.set_inner_html(html);
+        let nodes = container.child_nodes();
+        for node in nodes.iter() {
+            node.detach_from_parent();
+        }
+        nodes
+    }
+
+    fn text_as_node(text: &Self::TextNode) -> Self::Node {
+        text.0.clone()
+    }
+
+    fn text_data(text: &Self::TextNode) -> String {
+        text.data()
+    }
+
+    fn text_set_data(text: &Self::TextNode, data: &str) {
+        text.set_data(data);
+    }
+
+    fn node_next_sibling(node: &Self::Node) -> Option<Self::Node> {
+        node.next_sibling()
+    }
+
+    fn node_as_element(node: &Self::Node) -> Option<Self::Element> {
+        Element::try_from(node.clone()).ok()
+    }
+
+    fn node_as_text(node: &Self::Node) -> Option<Self::TextNode> {
+        Text::try_from(node.clone()).ok()
+    }
+
+    fn node_comment_data(node: &Self::Node) -> Option<String> {
+        match &node.0.borrow().kind {
+            NodeKind::Comment(data) => Some(data.clone()),
+            _ => None,
+        }
+    }
+
+    fn element_as_node(element: &Self::Element) -> Self::Node {
+        element.0.clone()
+    }
+
+    fn element_first_child(element: &Self::Element) -> Option<Self::Node> {
+        element.first_child()
+    }
+
+    fn element_last_child(element: &Self::Element) -> Option<Self::Node> {
+        element.last_child()
+    }
+
+    fn element_insert_before(
+        element: &Self::Element,
+        node: &Self::Node,
+        next_sibling: Option<&Self::Node>,
+    ) -> Result<(), ()> {
+        element
+            .insert_before(node, next_sibling)
+            .map(drop)
+            .map_err(drop)
+    }
+
+    fn element_remove_child(element: &Self::Element, child: &Self::Node) -> Result<Self::Node, ()> {
+        element.remove_child(child).map_err(drop)
+    }
+
+    fn element_namespace_uri(element: &Self::Element) -> Option<String> {
+        element.namespace_uri()
+    }
+
+    fn element_local_name(element: &Self::Element) -> String {
+        element.local_name()
+    }
+
+    fn element_attribute_names(element: &Self::Element) -> Vec<String> {
+        element.get_attribute_names()
+    }
+
+    fn element_get_attribute(element: &Self::Element, key: &str) -> Option<String> {
+        element.get_attribute(key)
+    }
+
+    fn element_set_attribute(element: &Self::Element, key: &str, value: &str) -> Result<(), ()> {
+        element.set_attribute(key, value).map_err(drop)
+    }
+
+    fn element_remove_attribute(element: &Self::Element, key: &str) -> Result<(), ()> {
+        element.remove_attribute(key).map_err(drop)
+    }
+
+    fn element_as_input(element: &Self::Element) -> Option<Self::InputElement> {
+        InputElement::try_from(element.clone()).ok()
+    }
+
+    fn element_as_textarea(element: &Self::Element) -> Option<Self::TextAreaElement> {
+        TextAreaElement::try_from(element.clone()).ok()
+    }
+
+    fn element_as_button(element: &Self::Element) -> Option<Self::ButtonElement> {
+        ButtonElement::try_from(element.clone()).ok()
+    }
+
+    fn input_value(input: &Self::InputElement) -> String {
+        input.value()
+    }
+
+    fn input_set_value(input: &Self::InputElement, value: &str) {
+        input.set_value(value);
+    }
+
+    fn input_set_type(input: &Self::InputElement, kind: &str) {
+        input.set_type(kind);
+    }
+
+    fn input_set_checked(input: &Self::InputElement, checked: bool) {
+        input.set_checked(checked);
+    }
+
+    fn textarea_value(textarea: &Self::TextAreaElement) -> String {
+        textarea.value()
+    }
+
+    fn textarea_set_value(textarea: &Self::TextAreaElement, value: &str) {
+        textarea.set_value(value);
+    }
+
+    fn button_set_type(button: &Self::ButtonElement, kind: &str) {
+        button.set_type(kind);
+    }
+
+    fn oninput_handler(this: &Self::Element, event: Self::InputEvent) -> Self::InputData {
+        let value = InputElement::try_from(this.clone())
+            .map(|input| input.value())
+            .or_else(|_| TextAreaElement::try_from(this.clone()).map(|tae| tae.value()))
+            .ok()
+            .or_else(|| this.text_content())
+            .expect("only an InputElement or TextAreaElement or an element with contenteditable=true can have an oninput event listener");
+        InputData { value, event }
+    }
+
+    fn onchange_handler(this: &Self::Element) -> Self::ChangeData {
+        if let Ok(input) = InputElement::try_from(this.clone()) {
+            if input.type_().eq_ignore_ascii_case("file") {
+                ChangeData::Files(FileList {})
+            } else {
+                ChangeData::Value(input.value())
+            }
+        } else if let Ok(tae) = TextAreaElement::try_from(this.clone()) {
+            ChangeData::Value(tae.value())
+        } else if let Ok(se) = SelectElement::try_from(this.clone()) {
+            ChangeData::Select(se)
+        } else {
+            panic!("only an InputElement, TextAreaElement or SelectElement can have an onchange event listener");
+        }
+    }
+}
+
+/// Represents errors raised by in-memory DOM operations.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum DomError {
+    /// A tag or attribute name contains characters which are not allowed in markup.
+    #[error("`{0}` is not a valid name")]
+    InvalidCharacter(String),
+
+    /// The operation would produce a node tree that is not well-formed.
+    #[error("the operation would yield an incorrect node tree")]
+    HierarchyRequest,
+
+    /// The referenced node is not a child of this node.
+    #[error("the node to be removed or used as a reference is not a child of this node")]
+    NotFound,
+
+    /// A selector string could not be parsed.
+    #[error("`{0}` is not a valid selector")]
+    Syntax(String),
+
+    /// A URL could not be parsed.
+    #[error("`{0}` is not a valid URL")]
+    InvalidUrl(String),
+}
+
+type NodeCell = RefCell<NodeData>;
+
+struct NodeData {
+    kind: NodeKind,
+    parent: Option<Weak<NodeCell>>,
+    /// The position of the node in the children of its parent, so siblings are found without
+    /// searching the parent
+    index: usize,
+    children: Vec<Node>,
+}
+
+enum NodeKind {
+    Document(DocumentData),
+    Element(ElementData),
+    Text(String),
+    Comment(String),
+}
+
+#[derive(Default)]
+struct DocumentData {
+    active_element: Option<Weak<NodeCell>>,
+}
+
+struct ElementData {
+    local_name: String,
+    namespace: Option<String>,
+    attributes: IndexMap<String, String>,
+    /// The "dirty" value of a form control, set through `set_value` rather than the attribute.
+    value: Option<String>,
+    /// The "dirty" checkedness of an input, set through `set_checked` rather than the attribute.
+    checked: Option<bool>,
+    listeners: Vec<Registration>,
+    next_listener_id: usize,
+}
+
+impl ElementData {
+    fn is_html(&self) -> bool {
+        self.namespace.as_deref() == Some(HTML_NAMESPACE)
+    }
+
+    /// Attribute names of HTML elements are case-insensitive and stored in lowercase.
+    fn attribute_key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.is_html() {
+            Cow::Owned(name.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+}
+
+struct Registration {
+    id: usize,
+    kind: Cow<'static, str>,
+    callback: Rc<dyn Fn(&Event)>,
+}
+
+/// A node in the in-memory DOM.
+///
+/// Cloning a `Node` creates another handle to the same node, and two nodes compare equal only
+/// if they are the same node, just like references to nodes in a browser.
+#[derive(Clone)]
+pub struct Node(Rc<NodeCell>);
+
+impl Node {
+    fn new(kind: NodeKind) -> Self {
+        Node(Rc::new(RefCell::new(NodeData {
+            kind,
+            parent: None,
+            index: 0,
+            children: Vec::new(),
+        })))
+    }
+
+    fn is_document(&self) -> bool {
+        matches!(self.0.borrow().kind, NodeKind::Document(_))
+    }
+
+    fn is_element(&self) -> bool {
+        matches!(self.0.borrow().kind, NodeKind::Element(_))
+    }
+
+    /// Returns the name of the node: the upper-cased tag name of an HTML element, the tag name
+    /// of any other element, `#text` for text nodes, `#comment` for comments and `#document`
+    /// for the document.
+    pub fn node_name(&self) -> String {
+        match &self.0.borrow().kind {
+            NodeKind::Document(_) => "#document".to_owned(),
+            NodeKind::Element(data) if data.is_html() => data.local_name.to_ascii_uppercase(),
+            NodeKind::Element(data) => data.local_name.clone(),
+            NodeKind::Text(_) => "#text".to_owned(),
+            NodeKind::Comment(_) => "#comment".to_owned(),
+        }
+    }
+
+    /// Returns the text of a text node or comment, or `None` for any other node.
+    pub fn node_value(&self) -> Option<String> {
+        match &self.0.borrow().kind {
+            NodeKind::Text(text) | NodeKind::Comment(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Sets the text of a text node or comment. Has no effect on other nodes.
+    pub fn set_node_value(&self, value: Option<&str>) {
+        if let NodeKind::Text(text) | NodeKind::Comment(text) = &mut self.0.borrow_mut().kind {
+            *text = value.unwrap_or_default().to_owned();
+        }
+    }
+
+    /// Returns the concatenated text of this node and all of its descendants, leaving out
+    /// comments. The text content of a comment is its data and the document itself has none.
+    pub fn text_content(&self) -> Option<String> {
+        fn collect(node: &Node, out: &mut String) {
+            let data = node.0.borrow();
+            match &data.kind {
+                NodeKind::Text(text) => out.push_str(text),
+                NodeKind::Comment(_) => {}
+                _ => data.children.iter().for_each(|child| collect(child, out)),
+            }
+        }
+
+        match &self.0.borrow().kind {
+            NodeKind::Document(_) => return None,
+            NodeKind::Comment(data) => return Some(data.clone()),
+            _ => {}
+        }
+        let mut out = String::new();
+        collect(self, &mut out);
+        Some(out)
+    }
+
+    /// Returns the parent of this node, if it has one.
+    pub fn parent_node(&self) -> Option<Node> {
+        self.0.borrow().parent.as_ref()?.upgrade().map(Node)
+    }
+
+    /// Returns the parent of this node if it is an element.
+    pub fn parent_element(&self) -> Option<Element> {
+        Element::try_from(self.parent_node()?).ok()
+    }
+
+    /// Returns a snapshot of the children of this node.
+    pub fn child_nodes(&self) -> Vec<Node> {
+        self.0.borrow().children.clone()
+    }
+
+    /// Returns `true` if this node has any children.
+    pub fn has_child_nodes(&self) -> bool {
+        !self.0.borrow().children.is_empty()
+    }
+
+    /// Returns the first child of this node.
+    pub fn first_child(&self) -> Option<Node> {
+        self.0.borrow().children.first().cloned()
+    }
+
+    /// Returns the last child of this node.
+    pub fn last_child(&self) -> Option<Node> {
+        self.0.borrow().children.last().cloned()
+    }
+
+    /// Returns the node immediately following this one in its parent's children.
+    pub fn next_sibling(&self) -> Option<Node> {
+        let parent = self.parent_node()?;
+        let index = self.0.borrow().index;
+        let data = parent.0.borrow();
+        data.children.get(index + 1).cloned()
+    }
+
+    /// Returns the node immediately preceding this one in its parent's children.
+    pub fn previous_sibling(&self) -> Option<Node> {
+        let parent = self.parent_node()?;
+        let index = self.0.borrow().index;
+        let data = parent.0.borrow();
+        index
+            .checked_sub(1)
+            .and_then(|i| data.children.get(i).cloned())
+    }
+
+    /// Returns `true` if `other` is this node or one of its descendants.
+    pub fn contains(&self, other: Option<&Node>) -> bool {
+        let mut current = other.cloned();
+        while let Some(node) = current {
+            if &node == self {
+                return true;
+            }
+            current = node.parent_node();
+        }
+        false
+    }
+
+    /// Returns `true` if `other` is this very node.
+    pub fn is_same_node(&self, other: Option<&Node>) -> bool {
+        other.is_some_and(|other| other == self)
+    }
+
+    /// Appends `node` to the children of this node, removing it from its current parent first.
+    pub fn append_child(&self, node: &Node) -> Result<Node, DomError> {
+        self.insert_before(node, None)
+    }
+
+    /// Inserts `node` before `child`, or at the end of the children of this node if `child` is
+    /// `None`. The node is removed from its current parent first.
+    pub fn insert_before(&self, node: &Node, child: Option<&Node>) -> Result<Node, DomError> {
+        if !(self.is_element() || self.is_document()) || node.is_document() {
+            return Err(DomError::HierarchyRequest);
+        }
+        if node.contains(Some(self)) {
+            return Err(DomError::HierarchyRequest);
+        }
+        if let Some(child) = child {
+            if self.index_of(child).is_none() {
+                return Err(DomError::NotFound);
+            }
+        }
+
+        // Inserting a node before itself is the same as inserting it before its next sibling.
+        let reference = match child {
+            Some(child) if child == node => node.next_sibling(),
+            child => child.cloned(),
+        };
+
+        node.detach_from_parent();
+        let index = match &reference {
+            Some(reference) => self.index_of(reference).ok_or(DomError::NotFound)?,
+            None => self.0.borrow().children.len(),
+        };
+        node.0.borrow_mut().parent = Some(Rc::downgrade(&self.0));
+        let mut data = self.0.borrow_mut();
+        data.children.insert(index, node.clone());
+        update_indices(&data.children[index..], index);
+        Ok(node.clone())
+    }
+
+    /// Removes `child` from the children of this node.
+    pub fn remove_child(&self, child: &Node) -> Result<Node, DomError> {
+        let index = self.index_of(child).ok_or(DomError::NotFound)?;
+        let mut data = self.0.borrow_mut();
+        let removed = data.children.remove(index);
+        update_indices(&data.children[index..], index);
+        removed.0.borrow_mut().parent = None;
+        Ok(removed)
+    }
+
+    /// Returns the position of `child` in the children of this node.
+    fn index_of(&self, child: &Node) -> Option<usize> {
+        let data = child.0.borrow();
+        let parent = data.parent.as_ref()?.upgrade()?;
+        if Rc::ptr_eq(&parent, &self.0) {
+            Some(data.index)
+        } else {
+            None
+        }
+    }
+
+    fn detach_from_parent(&self) {
+        if let Some(parent) = self.parent_node() {
+            let _ = parent.remove_child(self);
+        }
+    }
+}
+
+/// Stores the positions of `children`, the first of which is at `start`.
+fn update_indices(children: &[Node], start: usize) {
+    for (index, child) in (start..).zip(children) {
+        child.0.borrow_mut().index = index;
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0.borrow().kind {
+            NodeKind::Document(_) => f.write_str("#document"),
+            NodeKind::Element(data) => write!(f, "<{}>", data.local_name),
+            NodeKind::Text(text) => write!(f, "{:?}", text),
+            NodeKind::Comment(data) => write!(f, "<!--{}-->", data),
+        }
+    }
+}
+
+/// An element in the in-memory DOM.
+#[derive(Clone, PartialEq)]
+pub struct Element(Node);
+
+impl Element {
+    fn data<R>(&self, f: impl FnOnce(&ElementData) -> R) -> R {
+        match &self.0 .0.borrow().kind {
+            NodeKind::Element(data) => f(data),
+            _ => unreachable!("an `Element` always wraps an element node"),
+        }
+    }
+
+    fn data_mut<R>(&self, f: impl FnOnce(&mut ElementData) -> R) -> R {
+        match &mut self.0 .0.borrow_mut().kind {
+            NodeKind::Element(data) => f(data),
+            _ => unreachable!("an `Element` always wraps an element node"),
+        }
+    }
+
+    fn is_html(&self, local_name: &str) -> bool {
+        self.data(|data| data.is_html() && data.local_name == local_name)
+    }
+
+    /// Returns the tag name of the element, upper-cased for HTML elements.
+    pub fn tag_name(&self) -> String {
+        self.node_name()
+    }
+
+    /// Returns the local name of the element as it was created.
+    pub fn local_name(&self) -> String {
+        self.data(|data| data.local_name.clone())
+    }
+
+    /// Returns the namespace of the element.
+    pub fn namespace_uri(&self) -> Option<String> {
+        self.data(|data| data.namespace.clone())
+    }
+
+    /// Returns the value of the attribute `name`.
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        self.data(|data| data.attributes.get(&*data.attribute_key(name)).cloned())
+    }
+
+    /// Returns `true` if the element has an attribute `name`.
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.data(|data| data.attributes.contains_key(&*data.attribute_key(name)))
+    }
+
+    /// Returns the names of all attributes in the order they were first set.
+    pub fn get_attribute_names(&self) -> Vec<String> {
+        self.data(|data| data.attributes.keys().cloned().collect())
+    }
+
+    /// Sets the attribute `name` to `value`.
+    pub fn set_attribute(&self, name: &str, value: &str) -> Result<(), DomError> {
+        if !is_valid_name(name) {
+            return Err(DomError::InvalidCharacter(name.to_owned()));
+        }
+        self.data_mut(|data| {
+            let key = data.attribute_key(name).into_owned();
+            data.attributes.insert(key, value.to_owned());
+        });
+        Ok(())
+    }
+
+    /// Removes the attribute `name`. Removing an attribute which is not set is not an error.
+    pub fn remove_attribute(&self, name: &str) -> Result<(), DomError> {
+        self.data_mut(|data| {
+            let key = data.attribute_key(name).into_owned();
+            data.attributes.shift_remove(&key);
+        });
+        Ok(())
+    }
+
+    /// Returns the `id` attribute of the element.
+    pub fn id(&self) -> String {
+        self.get_attribute("id").unwrap_or_default()
+    }
+
+    /// Returns the `class` attribute of the element.
+    pub fn class_name(&self) -> String {
+        self.get_attribute("class").unwrap_or_default()
+    }
+
+    /// Returns the element children of this element.
+    pub fn children(&self) -> Vec<Element> {
+        self.child_nodes()
+            .into_iter()
+            .filter_map(|node| Element::try_from(node).ok())
+            .collect()
+    }
+
+    /// Returns the first descendant element which matches `selectors`.
+    pub fn query_selector(&self, selectors: &str) -> Result<Option<Element>, DomError> {
+        Ok(self.query_selector_all(selectors)?.into_iter().next())
+    }
+
+    /// Returns all descendant elements which match `selectors` in document order.
+    pub fn query_selector_all(&self, selectors: &str) -> Result<Vec<Element>, DomError> {
+        query_selector_all(&self.0, selectors)
+    }
+
+    /// Replaces the children of this element with the nodes parsed from `html`.
+    ///
+    /// Elements, attributes, text, comments and character references are supported, doctypes
+    /// are dropped. Like in a browser, elements inside `<svg>` and `<math>` are
+    /// created in their namespaces, stray end tags are ignored and elements which aren't
+    /// closed end with their parent.
+    pub fn set_inner_html(&self, html: &str) {
+        for child in self.child_nodes() {
+            child.detach_from_parent();
+        }
+        parse_fragment(self, html);
+    }
+
+    /// Registers `callback` to be called whenever an event of type `kind` is dispatched to this
+    /// element. The listener is removed when the returned handle is dropped.
+    pub fn add_event_listener<F>(
+        &self,
+        kind: impl Into<Cow<'static, str>>,
+        callback: F,
+    ) -> EventListener
+    where
+        F: Fn(&Event) + 'static,
+    {
+        let kind = kind.into();
+        let id = self.data_mut(|data| {
+            let id = data.next_listener_id;
+            data.next_listener_id += 1;
+            data.listeners.push(Registration {
+                id,
+                kind: kind.clone(),
+                callback: Rc::new(callback),
+            });
+            id
+        });
+        EventListener {
+            target: Rc::downgrade(&self.0 .0),
+            kind,
+            id,
+        }
+    }
+
+    /// Calls the listeners registered on this element for the type of `event`. If the event
+    /// bubbles, the listeners of the ancestors of the element are called afterwards, from the
+    /// parent up to the root, until a listener calls `stop_propagation`.
+    ///
+    /// Returns `false` if any listener called `prevent_default`.
+    pub fn dispatch_event(&self, event: &Event) -> bool {
+        event.0.target.replace(Some(self.clone()));
+        let mut current = Some(self.clone());
+        while let Some(element) = current {
+            event.0.current_target.replace(Some(element.clone()));
+            // Collect the callbacks first: a listener may well add or remove listeners itself.
+            let callbacks: Vec<_> = element.data(|data| {
+                data.listeners
+                    .iter()
+                    .filter(|registration| registration.kind == event.0.kind)
+                    .map(|registration| registration.callback.clone())
+                    .collect()
+            });
+            for callback in callbacks {
+                callback(event);
+            }
+            if !event.bubbles() || event.0.propagation_stopped.get() {
+                break;
+            }
+            current = element.parent_element();
+        }
+        event.0.current_target.replace(None);
+        !event.default_prevented()
+    }
+}
+
+impl Deref for Element {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.0
+    }
+}
+
+impl AsRef<Node> for Element {
+    fn as_ref(&self) -> &Node {
+        &self.0
+    }
+}
+
+impl From<Element> for Node {
+    fn from(element: Element) -> Self {
+        element.0
+    }
+}
+
+impl TryFrom<Node> for Element {
+    type Error = Node;
+
+    fn try_from(node: Node) -> Result<Self, Node> {
+        if node.is_element() {
+            Ok(Element(node))
+        } else {
+            Err(node)
+        }
+    }
+}
+
+impl fmt::Debug for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A text node in the in-memory DOM.
+#[derive(Clone, PartialEq)]
+pub struct Text(Node);
+
+impl Text {
+    /// Returns the text held by the node.
+    pub fn data(&self) -> String {
+        self.node_value().unwrap_or_default()
+    }
+
+    /// Replaces the text held by the node.
+    pub fn set_data(&self, data: &str) {
+        self.set_node_value(Some(data));
+    }
+}
+
+impl Deref for Text {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.0
+    }
+}
+
+impl AsRef<Node> for Text {
+    fn as_ref(&self) -> &Node {
+        &self.0
+    }
+}
+
+impl From<Text> for Node {
+    fn from(text: Text) -> Self {
+        text.0
+    }
+}
+
+impl TryFrom<Node> for Text {
+    type Error = Node;
+
+    fn try_from(node: Node) -> Result<Self, Node> {
+        if matches!(node.0.borrow().kind, NodeKind::Text(_)) {
+            Ok(Text(node))
+        } else {
+            Err(node)
+        }
+    }
+}
+
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! html_element {
+    ($(#[$attr:meta])* $name:ident => $local_name:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name(Element);
+
+        impl Deref for $name {
+            type Target = Element;
+
+            fn deref(&self) -> &Element {
+                &self.0
+            }
+        }
+
+        impl AsRef<Node> for $name {
+            fn as_ref(&self) -> &Node {
+                &self.0
+            }
+        }
+
+        impl From<$name> for Element {
+            fn from(element: $name) -> Self {
+                element.0
+            }
+        }
+
+        impl From<$name> for Node {
+            fn from(element: $name) -> Self {
+                element.0.into()
+            }
+        }
+
+        impl TryFrom<Element> for $name {
+            type Error = Element;
+
+            fn try_from(element: Element) -> Result<Self, Element> {
+                if element.is_html($local_name) {
+                    Ok($name(element))
+                } else {
+                    Err(element)
+                }
+            }
+        }
+
+        impl TryFrom<Node> for $name {
+            type Error = Node;
+
+            fn try_from(node: Node) -> Result<Self, Node> {
+                let element = Element::try_from(node)?;
+                $name::try_from(element).map_err(Node::from)
+            }
+        }
+    };
+}
+
+html_element!(
+    /// An `<input>` element in the in-memory DOM.
+    InputElement => "input"
+);
+html_element!(
+    /// A `<textarea>` element in the in-memory DOM.
+    TextAreaElement => "textarea"
+);
+html_element!(
+    /// A `<button>` element in the in-memory DOM.
+    ButtonElement => "button"
+);
+html_element!(
+    /// A `<select>` element in the in-memory DOM.
+    SelectElement => "select"
+);
+
+impl InputElement {
+    /// Returns the current value of the input, falling back to its `value` attribute.
+    pub fn value(&self) -> String {
+        self.data(|data| data.value.clone())
+            .or_else(|| self.get_attribute("value"))
+            .unwrap_or_default()
+    }
+
+    /// Sets the current value of the input without touching its `value` attribute.
+    pub fn set_value(&self, value: &str) {
+        self.data_mut(|data| data.value = Some(value.to_owned()));
+    }
+
+    /// Returns the current checkedness of the input, falling back to its `checked` attribute.
+    pub fn checked(&self) -> bool {
+        self.data(|data| data.checked)
+            .unwrap_or_else(|| self.has_attribute("checked"))
+    }
+
+    /// Sets the current checkedness of the input without touching its `checked` attribute.
+    pub fn set_checked(&self, checked: bool) {
+        self.data_mut(|data| data.checked = Some(checked));
+    }
+
+    /// Returns the `type` of the input, `text` if none was set.
+    pub fn type_(&self) -> String {
+        self.get_attribute("type")
+            .unwrap_or_else(|| "text".to_owned())
+    }
+
+    /// Sets the `type` attribute of the input.
+    pub fn set_type(&self, kind: &str) {
+        self.set_attribute("type", kind)
+            .expect("`type` is a valid attribute name");
+    }
+
+    /// Makes this input the active element of the document.
+    pub fn focus(&self) -> Result<(), DomError> {
+        Renderer::get_document().set_active_element(Some(&self.0));
+        Ok(())
+    }
+}
+
+impl TextAreaElement {
+    /// Returns the current value of the textarea, falling back to its text content.
+    pub fn value(&self) -> String {
+        self.data(|data| data.value.clone())
+            .or_else(|| self.text_content())
+            .unwrap_or_default()
+    }
+
+    /// Sets the current value of the textarea without touching its children.
+    pub fn set_value(&self, value: &str) {
+        self.data_mut(|data| data.value = Some(value.to_owned()));
+    }
+}
+
+impl ButtonElement {
+    /// Returns the `type` of the button, `submit` if none was set.
+    pub fn type_(&self) -> String {
+        self.get_attribute("type")
+            .unwrap_or_else(|| "submit".to_owned())
+    }
+
+    /// Sets the `type` attribute of the button.
+    pub fn set_type(&self, kind: &str) {
+        self.set_attribute("type", kind)
+            .expect("`type` is a valid attribute name");
+    }
+}
+
+impl SelectElement {
+    fn options(&self) -> Vec<Element> {
+        self.query_selector_all("option")
+            .expect("`option` is a valid selector")
+    }
+
+    /// Returns the index of the first selected option, or of the first option if none is
+    /// selected. Returns `-1` if the select has no options.
+    pub fn selected_index(&self) -> i32 {
+        let options = self.options();
+        if options.is_empty() {
+            return -1;
+        }
+        options
+            .iter()
+            .position(|option| option.has_attribute("selected"))
+            .unwrap_or(0) as i32
+    }
+
+    /// Selects the option at `index`, or none if it is out of range.
+    pub fn set_selected_index(&self, index: i32) {
+        for (position, option) in self.options().iter().enumerate() {
+            if position as i32 == index {
+                option
+                    .set_attribute("selected", "")
+                    .expect("`selected` is a valid attribute name");
+            } else if option.has_attribute("selected") {
+                option
+                    .remove_attribute("selected")
+                    .expect("`selected` is a valid attribute name");
+            }
+        }
+    }
+
+    /// Returns the value of the selected option.
+    pub fn value(&self) -> String {
+        let index = self.selected_index();
+        if index < 0 {
+            return String::new();
+        }
+        option_value(&self.options()[index as usize])
+    }
+
+    /// Selects the first option with the value `value`, or none if there is no such option.
+    pub fn set_value(&self, value: &str) {
+        let index = self
+            .options()
+            .iter()
+            .position(|option| option_value(option) == value)
+            .map_or(-1, |index| index as i32);
+        self.set_selected_index(index);
+    }
+}
+
+fn option_value(option: &Element) -> String {
+    option
+        .get_attribute("value")
+        .or_else(|| option.text_content())
+        .unwrap_or_default()
+}
+
+/// The in-memory document, which owns the `<html>`, `<head>` and `<body>` elements.
+#[derive(Clone, PartialEq)]
+pub struct Document(Node);
+
+impl Document {
+    fn new() -> Self {
+        let document = Document(Node::new(NodeKind::Document(DocumentData::default())));
+        let html = document.create_element("html").unwrap();
+        let head = document.create_element("head").unwrap();
+        let body = document.create_element("body").unwrap();
+        html.append_child(&head).unwrap();
+        html.append_child(&body).unwrap();
+        document.append_child(&html).unwrap();
+        document
+    }
+
+    /// Creates an HTML element. The tag name is lower-cased.
+    pub fn create_element(&self, local_name: &str) -> Result<Element, DomError> {
+        self.create_element_ns(Some(HTML_NAMESPACE), &local_name.to_ascii_lowercase())
+    }
+
+    /// Creates an element with the given namespace. The tag name is kept as is.
+    pub fn create_element_ns(
+        &self,
+        namespace: Option<&str>,
+        qualified_name: &str,
+    ) -> Result<Element, DomError> {
+        if !is_valid_name(qualified_name) {
+            return Err(DomError::InvalidCharacter(qualified_name.to_owned()));
+        }
+        Ok(Element(Node::new(NodeKind::Element(ElementData {
+            local_name: qualified_name.to_owned(),
+            namespace: namespace.map(str::to_owned),
+            attributes: IndexMap::new(),
+            value: None,
+            checked: None,
+            listeners: Vec::new(),
+            next_listener_id: 0,
+        }))))
+    }
+
+    /// Creates a text node.
+    pub fn create_text_node(&self, data: &str) -> Text {
+        Text(Node::new(NodeKind::Text(data.to_owned())))
+    }
+
+    /// Creates a comment.
+    pub fn create_comment(&self, data: &str) -> Node {
+        Node::new(NodeKind::Comment(data.to_owned()))
+    }
+
+    /// Returns the root `<html>` element.
+    pub fn document_element(&self) -> Option<Element> {
+        self.child_nodes()
+            .into_iter()
+            .find_map(|node| Element::try_from(node).ok())
+    }
+
+    /// Returns the `<head>` element.
+    pub fn head(&self) -> Option<Element> {
+        self.query_selector("html > head").ok().flatten()
+    }
+
+    /// Returns the `<body>` element.
+    pub fn body(&self) -> Option<Element> {
+        self.query_selector("html > body").ok().flatten()
+    }
+
+    /// Returns the element which was focused last, if it is still attached to the document.
+    pub fn active_element(&self) -> Option<Element> {
+        let element = match &self.0 .0.borrow().kind {
+            NodeKind::Document(data) => data.active_element.as_ref()?.upgrade().map(Node),
+            _ => None,
+        }?;
+        if self.contains(Some(&element)) {
+            Element::try_from(element).ok()
+        } else {
+            None
+        }
+    }
+
+    fn set_active_element(&self, element: Option<&Element>) {
+        if let NodeKind::Document(data) = &mut self.0 .0.borrow_mut().kind {
+            data.active_element = element.map(|element| Rc::downgrade(&element.0 .0));
+        }
+    }
+
+    /// Returns the first element in the document which matches `selectors`.
+    pub fn query_selector(&self, selectors: &str) -> Result<Option<Element>, DomError> {
+        Ok(self.query_selector_all(selectors)?.into_iter().next())
+    }
+
+    /// Returns all elements in the document which match `selectors` in document order.
+    pub fn query_selector_all(&self, selectors: &str) -> Result<Vec<Element>, DomError> {
+        query_selector_all(&self.0, selectors)
+    }
+}
+
+impl Deref for Document {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Document")
+    }
+}
+
+/// Represents the window of the in-memory DOM.
+#[derive(Clone, Debug)]
+pub struct Window {}
+
+impl Window {
+    /// Returns the document of the window.
+    pub fn document(&self) -> Option<Document> {
+        Some(Renderer::get_document())
+    }
+
+    /// Returns the location of the window.
+    pub fn location(&self) -> Location {
+        Location {}
+    }
+}
+
+/// The location of the in-memory window. Defaults to the URL of the request the runtime renders
+/// a page for, or to `http://localhost/`.
+///
+/// Like in the browser, a `Location` always reflects the current URL of the window, so
+/// navigating with `set_href` is visible through every handle. Every runtime has a location of
+/// its own, which a handle reads when it is used.
+#[derive(Clone, Debug)]
+pub struct Location {}
+
+impl Location {
+    fn with_uri<R>(&self, f: impl FnOnce(&Uri) -> R) -> R {
+        f(&WindowState::current().location.borrow())
+    }
+
+    /// Returns the full URL.
+    pub fn href(&self) -> String {
+        self.with_uri(Uri::to_string)
+    }
+
+    /// Navigates to `href`. A path starting with `/` is resolved against the current origin.
+    pub fn set_href(&self, href: &str) -> Result<(), DomError> {
+        let invalid = || DomError::InvalidUrl(href.to_owned());
+        let uri: Uri = href.parse().map_err(|_| invalid())?;
+        let uri = if uri.scheme().is_some() {
+            uri
+        } else if href.starts_with('/') {
+            let mut parts = self.with_uri(|current| current.clone()).into_parts();
+            parts.path_and_query = uri.into_parts().path_and_query;
+            Uri::from_parts(parts).map_err(|_| invalid())?
+        } else {
+            return Err(invalid());
+        };
+        *WindowState::current().location.borrow_mut() = uri;
+        Ok(())
+    }
+
+    /// Returns the scheme, host and port of the URL.
+    pub fn origin(&self) -> String {
+        self.with_uri(|uri| format!("{}://{}", uri.scheme_str().unwrap_or("http"), self.host()))
+    }
+
+    /// Returns the host and port of the URL.
+    pub fn host(&self) -> String {
+        self.with_uri(|uri| {
+            uri.authority()
+                .map(|authority| authority.to_string())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the path of the URL.
+    pub fn pathname(&self) -> String {
+        self.with_uri(|uri| uri.path().to_owned())
+    }
+
+    /// Returns the query string of the URL including the leading `?`, or an empty string.
+    pub fn search(&self) -> String {
+        self.with_uri(|uri| {
+            uri.query()
+                .map(|query| format!("?{}", query))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the fragment of the URL. Fragments are never sent to a server, so this is always
+    /// an empty string.
+    pub fn hash(&self) -> String {
+        String::new()
+    }
+}
+
+/// An event dispatched through the in-memory DOM.
+///
+/// Cloning an `Event` creates another handle to the same event.
+#[derive(Clone)]
+pub struct Event(Rc<EventData>);
+
+struct EventData {
+    kind: String,
+    bubbles: bool,
+    key: String,
+    target: RefCell<Option<Element>>,
+    current_target: RefCell<Option<Element>>,
+    default_prevented: Cell<bool>,
+    propagation_stopped: Cell<bool>,
+}
+
+impl Event {
+    /// Creates an event of type `kind`, e.g. `click`. Like the events a browser dispatches,
+    /// the event bubbles unless events of its type don't, e.g. `focus` or `mouseenter`.
+    pub fn new(kind: &str) -> Self {
+        Event::with_key(kind, "")
+    }
+
+    /// Creates a keyboard event of type `kind`, e.g. `keydown`, for the key `key`, e.g. `a`
+    /// or `Enter`.
+    pub fn keyboard(kind: &str, key: &str) -> Self {
+        Event::with_key(kind, key)
+    }
+
+    fn with_key(kind: &str, key: &str) -> Self {
+        Event(Rc::new(EventData {
+            kind: kind.to_owned(),
+            bubbles: !is_non_bubbling_event(kind),
+            key: key.to_owned(),
+            target: RefCell::new(None),
+            current_target: RefCell::new(None),
+            default_prevented: Cell::new(false),
+            propagation_stopped: Cell::new(false),
+        }))
+    }
+
+    /// Returns the type of the event.
+    pub fn type_(&self) -> String {
+        self.0.kind.clone()
+    }
+
+    /// Returns `true` if the event is passed on to the ancestors of its target.
+    pub fn bubbles(&self) -> bool {
+        self.0.bubbles
+    }
+
+    /// Returns the key of a keyboard event, or an empty string for other events.
+    pub fn key(&self) -> String {
+        self.0.key.clone()
+    }
+
+    /// Returns the element the event was dispatched to.
+    pub fn target(&self) -> Option<Element> {
+        self.0.target.borrow().clone()
+    }
+
+    /// Returns the element whose listeners are being called.
+    pub fn current_target(&self) -> Option<Element> {
+        self.0.current_target.borrow().clone()
+    }
+
+    /// Keeps the event from being passed on to the ancestors of the current target.
+    pub fn stop_propagation(&self) {
+        self.0.propagation_stopped.set(true);
+    }
+
+    /// Flags the event so that its default action is not taken.
+    pub fn prevent_default(&self) {
+        self.0.default_prevented.set(true);
+    }
+
+    /// Returns `true` if a listener called `prevent_default`.
+    pub fn default_prevented(&self) -> bool {
+        self.0.default_prevented.get()
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Event {{ type: {} }}", self.0.kind)
+    }
+}
+
+/// Returns `true` for the types of events which browsers don't pass on to the ancestors of
+/// their target.
+fn is_non_bubbling_event(kind: &str) -> bool {
+    matches!(
+        kind,
+        "abort"
+            | "blur"
+            | "canplay"
+            | "canplaythrough"
+            | "durationchange"
+            | "emptied"
+            | "ended"
+            | "error"
+            | "focus"
+            | "invalid"
+            | "load"
+            | "loadeddata"
+            | "loadedmetadata"
+            | "loadend"
+            | "loadstart"
+            | "mouseenter"
+            | "mouseleave"
+            | "pause"
+            | "play"
+            | "playing"
+            | "pointerenter"
+            | "pointerleave"
+            | "progress"
+            | "ratechange"
+            | "resize"
+            | "scroll"
+            | "seeked"
+            | "seeking"
+            | "stalled"
+            | "suspend"
+            | "timeupdate"
+            | "toggle"
+            | "volumechange"
+            | "waiting"
+    )
+}
+
+/// An animation event of the in-memory DOM.
+pub type AnimationEvent = Event;
+/// A drag event of the in-memory DOM.
+pub type DragEvent = Event;
+/// An error event of the in-memory DOM.
+pub type ErrorEvent = Event;
+/// A focus event of the in-memory DOM.
+pub type FocusEvent = Event;
+/// An input event of the in-memory DOM.
+pub type InputEvent = Event;
+/// A keyboard event of the in-memory DOM.
+pub type KeyboardEvent = Event;
+/// A mouse event of the in-memory DOM.
+pub type MouseEvent = Event;
+/// A pointer event of the in-memory DOM.
+pub type PointerEvent = Event;
+/// A progress event of the in-memory DOM.
+pub type ProgressEvent = Event;
+/// A touch event of the in-memory DOM.
+pub type TouchEvent = Event;
+/// A transition event of the in-memory DOM.
+pub type TransitionEvent = Event;
+/// A UI event of the in-memory DOM.
+pub type UiEvent = Event;
+/// A wheel event of the in-memory DOM.
+pub type WheelEvent = Event;
+
+/// Handle to an event listener registered on an in-memory element.
+/// The listener is removed when the handle is dropped.
+pub struct EventListener {
+    target: Weak<NodeCell>,
+    kind: Cow<'static, str>,
+    id: usize,
+}
+
+impl fmt::Debug for EventListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventListener {{ kind: {} }}", self.kind)
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.upgrade() {
+            if let NodeKind::Element(data) = &mut target.borrow_mut().kind {
+                data.listeners
+                    .retain(|registration| registration.id != self.id);
+            }
+        }
+    }
+}
+
+/// A type representing data from `oninput` event.
+#[derive(Debug)]
+pub struct InputData {
+    /// Current value of the input or textarea, or the text content of an editable element.
+    pub value: String,
+    /// The InputEvent received.
+    pub event: InputEvent,
+}
+
+/// A type representing change of value(s) of an element after committed by user
+/// ([onchange event](https://developer.mozilla.org/en-US/docs/Web/Events/change)).
+#[derive(Debug)]
+pub enum ChangeData {
+    /// Value of the element in cases of `<input>`, `<textarea>`
+    Value(String),
+    /// SelectElement in case of `<select>` element.
+    Select(SelectElement),
+    /// Files
+    Files(FileList),
+}
+
+/// The list of files selected in an `<input type="file">`.
+/// The in-memory DOM has no file system access, so the list is always empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileList {}
+
+impl FileList {
+    /// Returns the number of files in the list.
+    pub fn length(&self) -> u32 {
+        0
+    }
+}
+
+fn query_selector_all(root: &Node, selectors: &str) -> Result<Vec<Element>, DomError> {
+    fn collect(node: &Node, selector: &Selector, out: &mut Vec<Element>) {
+        for child in node.child_nodes() {
+            if let Ok(element) = Element::try_from(child.clone()) {
+                if selector.matches(&element) {
+                    out.push(element);
+                }
+            }
+            collect(&child, selector, out);
+        }
+    }
+
+    let selector = Selector::parse(selectors)?;
+    let mut out = Vec::new();
+    collect(root, &selector, &mut out);
+    Ok(out)
+}
+
+/// A parsed group of selectors, e.g. `div.item > a[href], #main`.
+///
+/// Type, id, class and attribute selectors are supported as well as the descendant and
+/// child combinators.
+struct Selector {
+    groups: Vec<Vec<(Combinator, Compound)>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    fn parse(selectors: &str) -> Result<Self, DomError> {
+        let error = || DomError::Syntax(selectors.to_owned());
+        let mut chars = selectors.chars().peekable();
+        let mut groups = Vec::new();
+        let mut parts = Vec::new();
+        let mut combinator = Combinator::Descendant;
+
+        loop {
+            skip_whitespace(&mut chars);
+            match chars.peek() {
+                None | Some(',') => {
+                    // A group can neither be empty nor end with a combinator.
+                    if parts.is_empty() || combinator == Combinator::Child {
+                        return Err(error());
+                    }
+                    groups.push(std::mem::take(&mut parts));
+                    if chars.next().is_none() {
+                        break;
+                    }
+                }
+                Some('>') => {
+                    if parts.is_empty() || combinator == Combinator::Child {
+                        return Err(error());
+                    }
+                    chars.next();
+                    combinator = Combinator::Child;
+                }
+                Some(_) => {
+                    let compound = Compound::parse(&mut chars).ok_or_else(error)?;
+                    parts.push((combinator, compound));
+                    combinator = Combinator::Descendant;
+                }
+            }
+        }
+
+        Ok(Selector { groups })
+    }
+
+    fn matches(&self, element: &Element) -> bool {
+        self.groups
+            .iter()
+            .any(|parts| Self::matches_parts(parts, element))
+    }
+
+    fn matches_parts(parts: &[(Combinator, Compound)], element: &Element) -> bool {
+        let ((combinator, compound), rest) = match parts.split_last() {
+            Some(split) => split,
+            None => return true,
+        };
+        if !compound.matches(element) {
+            return false;
+        }
+        if rest.is_empty() {
+            return true;
+        }
+        match combinator {
+            Combinator::Child => element
+                .parent_element()
+                .is_some_and(|parent| Self::matches_parts(rest, &parent)),
+            Combinator::Descendant => {
+                let mut ancestor = element.parent_element();
+                while let Some(current) = ancestor {
+                    if Self::matches_parts(rest, &current) {
+                        return true;
+                    }
+                    ancestor = current.parent_element();
+                }
+                false
+            }
+        }
+    }
+}
+
+impl Compound {
+    fn parse(chars: &mut Peekable<Chars<'_>>) -> Option<Self> {
+        let mut compound = Compound::default();
+        match chars.peek() {
+            Some('*') => {
+                chars.next();
+            }
+            Some(c) if is_ident_char(*c) => compound.tag = Some(parse_ident(chars)?),
+            _ => {}
+        }
+
+        let mut empty = compound.tag.is_none();
+        loop {
+            match chars.peek() {
+                Some('#') => {
+                    chars.next();
+                    compound.id = Some(parse_ident(chars)?);
+                }
+                Some('.') => {
+                    chars.next();
+                    compound.classes.push(parse_ident(chars)?);
+                }
+                Some('[') => {
+                    chars.next();
+                    skip_whitespace(chars);
+                    let name = parse_ident(chars)?;
+                    skip_whitespace(chars);
+                    let value = match chars.next()? {
+                        ']' => None,
+                        '=' => {
+                            skip_whitespace(chars);
+                            let value = parse_value(chars)?;
+                            skip_whitespace(chars);
+                            if chars.next()? != ']' {
+                                return None;
+                            }
+                            Some(value)
+                        }
+                        _ => return None,
+                    };
+                    compound.attributes.push((name, value));
+                }
+                Some(c) if c.is_whitespace() || *c == ',' || *c == '>' => break,
+                None => break,
+                Some(_) if empty => return None,
+                Some(_) => return None,
+            }
+            empty = false;
+        }
+        Some(compound)
+    }
+
+    fn matches(&self, element: &Element) -> bool {
+        if let Some(tag) = &self.tag {
+            let local_name = element.local_name();
+            let matches = if element.data(ElementData::is_html) {
+                local_name.eq_ignore_ascii_case(tag)
+            } else {
+                &local_name == tag
+            };
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(id) = &self.id {
+            if element.get_attribute("id").as_ref() != Some(id) {
+                return false;
+            }
+        }
+        if !self.classes.is_empty() {
+            let class_name = element.class_name();
+            let classes: Vec<_> = class_name.split_whitespace().collect();
+            if !self
+                .classes
+                .iter()
+                .all(|class| classes.contains(&class.as_str()))
+            {
+                return false;
+            }
+        }
+        self.attributes
+            .iter()
+            .all(|(name, value)| match (element.get_attribute(name), value) {
+                (Some(actual), Some(expected)) => &actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn parse_ident(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    let mut ident = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| is_ident_char(*c)) {
+        ident.push(c);
+        chars.next();
+    }
+    if ident.is_empty() {
+        None
+    } else {
+        Some(ident)
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    match chars.peek().copied()? {
+        quote @ '"' | quote @ '\'' => {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    c if c == quote => return Some(value),
+                    c => value.push(c),
+                }
+            }
+        }
+        _ => parse_ident(chars),
+    }
+}
+
+/// Parses `html` into the children of `parent`.
+fn parse_fragment(parent: &Element, html: &str) {
+    let document = Renderer::get_document();
+    let mut open = vec![parent.clone()];
+    let mut rest = html;
+    while !rest.is_empty() {
+        let current = open.last().expect("the parent is never closed").clone();
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            current
+                .append_child(&document.create_comment(&comment[..end]))
+                .expect("can't append parsed comment");
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(end_tag) = rest.strip_prefix("</") {
+            let end = end_tag.find('>').unwrap_or(end_tag.len());
+            let name = end_tag[..end].trim();
+            let index = open
+                .iter()
+                .rposition(|element| element.local_name().eq_ignore_ascii_case(name));
+            if let Some(index) = index.filter(|index| *index > 0) {
+                open.truncate(index);
+            }
+            rest = end_tag.get(end + 1..).unwrap_or_default();
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            rest = parse_start_tag(&document, &mut open, &rest[1..]);
+        } else {
+            let end = rest[1..].find('<').map_or(rest.len(), |index| index + 1);
+            append_text(&current, &decode_character_references(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+}
+
+/// Parses the start tag at the beginning of `rest`, which follows the `<`, and its content if it
+/// is an element whose content is text. Returns the input which follows.
+fn parse_start_tag<'a>(document: &Document, open: &mut Vec<Element>, rest: &'a str) -> &'a str {
+    let is_name_end = |c: char| c.is_whitespace() || c == '/' || c == '>';
+    let end = rest.find(is_name_end).unwrap_or(rest.len());
+    let (name, mut rest) = rest.split_at(end);
+
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            self_closing = true;
+            rest = after;
+            break;
+        } else if let Some(after) = rest.strip_prefix('>') {
+            rest = after;
+            break;
+        } else if rest.is_empty() {
+            break;
+        }
+
+        let end = rest
+            .find(|c: char| is_name_end(c) || c == '=')
+            .unwrap_or(rest.len())
+            .max(1);
+        let key = &rest[..end];
+        rest = rest[end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, after) = match after.chars().next() {
+                    Some(quote @ '"') | Some(quote @ '\'') => {
+                        let value = &after[1..];
+                        let end = value.find(quote).unwrap_or(value.len());
+                        (&value[..end], value.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        after.split_at(end)
+                    }
+                };
+                rest = after;
+                decode_character_references(value)
+            }
+            None => Cow::Borrowed(""),
+        };
+        attributes.push((key, value));
+    }
+
+    let parent = open.last().expect("the parent is never closed");
+    let context = match parent.namespace_uri() {
+        Some(namespace) => children_namespace(&namespace, &parent.local_name()),
+        None => HTML_NAMESPACE,
+    };
+    let namespace = element_namespace(name, context);
+    let is_html = namespace == HTML_NAMESPACE;
+    let element = if is_html {
+        document.create_element(name)
+    } else {
+        document.create_element_ns(Some(namespace), name)
+    };
+    let element = match element {
+        Ok(element) => element,
+        Err(_) => return rest,
+    };
+    for (key, value) in attributes {
+        let _ = element.set_attribute(key, &value);
+    }
+    parent
+        .append_child(&element)
+        .expect("can't append a parsed element");
+
+    let local_name = element.local_name();
+    if self_closing && !is_html || is_html && is_void_element(&local_name) {
+        return rest;
+    }
+    let is_escapable = matches!(local_name.as_str(), "textarea" | "title");
+    if is_html && (is_escapable || is_raw_text_element(&local_name)) {
+        let needle = format!("</{}", local_name);
+        let end = rest
+            .to_ascii_lowercase()
+            .find(&needle)
+            .unwrap_or(rest.len());
+        let text = &rest[..end];
+        if is_escapable {
+            append_text(&element, &decode_character_references(text));
+        } else {
+            append_text(&element, text);
+        }
+        let rest = &rest[end..];
+        return rest.find('>').map_or("", |end| &rest[end + 1..]);
+    }
+    open.push(element);
+    rest
+}
+
+/// Appends `text` to `parent`, merging it with a preceding text node.
+fn append_text(parent: &Element, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match parent.last_child().map(Text::try_from) {
+        Some(Ok(last)) => last.set_data(&(last.data() + text)),
+        _ => {
+            let text = Renderer::create_text_node(text);
+            parent
+                .append_child(&text)
+                .expect("can't append parsed text");
+        }
+    }
+}
+
+/// Replaces the named and numeric character references in `text` with the characters they
+/// stand for. Only the named references which Yew's serializer writes are known.
+fn decode_character_references(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest
+            .find(';')
+            .and_then(|end| Some((decode_character_reference(&rest[1..end])?, end)));
+        match reference {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+fn decode_character_reference(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::{ComponentLink, NodeRef, Scope, ShouldRender};
+    use crate::{html, Component, Html};
+
+    fn document() -> Document {
+        Renderer::get_document()
+    }
+
+    #[test]
+    fn document_has_html_head_and_body() {
+        let document = document();
+        let html = document.document_element().unwrap();
+        assert_eq!(html.tag_name(), "HTML");
+        assert_eq!(
+            document.head().unwrap().parent_element(),
+            Some(html.clone())
+        );
+        assert_eq!(document.body().unwrap().parent_element(), Some(html));
+    }
+
+    #[test]
+    fn insert_and_remove_children() {
+        let document = document();
+        let parent = document.create_element("div").unwrap();
+        let a = document.create_element("a").unwrap();
+        let b = document.create_text_node("b");
+        let c = document.create_element("c").unwrap();
+
+        parent.append_child(&a).unwrap();
+        parent.append_child(&c).unwrap();
+        parent.insert_before(&b, Some(&c)).unwrap();
+        assert_eq!(
+            parent.child_nodes(),
+            vec![a.clone().into(), b.clone().into(), c.clone().into()]
+        );
+        assert_eq!(a.next_sibling(), Some(b.clone().into()));
+        assert_eq!(c.previous_sibling(), Some(b.clone().into()));
+        assert_eq!(b.parent_element(), Some(parent.clone()));
+
+        // Inserting an attached node moves it.
+        parent.insert_before(&c, Some(&a)).unwrap();
+        assert_eq!(parent.first_child(), Some(c.clone().into()));
+        assert_eq!(parent.last_child(), Some(b.clone().into()));
+        assert_eq!(c.next_sibling(), Some(a.clone().into()));
+        assert_eq!(b.previous_sibling(), Some(a.clone().into()));
+
+        assert_eq!(parent.remove_child(&b), Ok(b.clone().into()));
+        assert_eq!(b.parent_node(), None);
+        assert_eq!(b.previous_sibling(), None);
+        assert_eq!(a.next_sibling(), None);
+        assert_eq!(parent.remove_child(&c), Ok(c.clone().into()));
+        assert_eq!(a.previous_sibling(), None);
+        assert_eq!(parent.first_child(), Some(a.clone().into()));
+        parent.remove_child(&a).unwrap();
+        assert_eq!(parent.remove_child(&b), Err(DomError::NotFound));
+        assert_eq!(parent.text_content(), Some(String::new()));
+    }
+
+    #[test]
+    fn location_follows_navigation() {
+        let location = Renderer::get_window().location();
+        location.set_href("/docs/intro?lang=en").unwrap();
+        assert_eq!(location.href(), "http://localhost/docs/intro?lang=en");
+        assert_eq!(Renderer::get_window().location().pathname(), "/docs/intro");
+        assert_eq!(location.search(), "?lang=en");
+
+        location.set_href("https://example.com:8080/").unwrap();
+        assert_eq!(location.origin(), "https://example.com:8080");
+        assert_eq!(
+            location.set_href("docs"),
+            Err(DomError::InvalidUrl("docs".to_owned()))
+        );
+    }
+
+    #[test]
+    fn runtimes_have_their_own_window() {
+        let request = RequestContext::new(
+            http::Method::GET,
+            Uri::from_static("/posts/1?page=2"),
+            std::iter::once((http::header::HOST, "example.com".parse().unwrap())).collect(),
+        );
+        let runtime = Runtime::with_request(request);
+        runtime.enter(|| {
+            let location = Renderer::get_window().location();
+            assert_eq!(location.href(), "http://example.com/posts/1?page=2");
+            location.set_href("/about").unwrap();
+            let body = Renderer::get_document().body().unwrap();
+            body.append_child(&Renderer::create_element("main"))
+                .unwrap();
+        });
+
+        let location = Renderer::get_window().location();
+        assert_eq!(location.href(), "http://localhost/");
+        let body = Renderer::get_document().body().unwrap();
+        assert_eq!(body.first_child(), None);
+
+        runtime.enter(|| {
+            assert_eq!(location.href(), "http://example.com/about");
+            let body = Renderer::get_document().body().unwrap();
+            assert!(body.first_child().is_some());
+        });
+    }
+
+    #[test]
+    fn rejects_malformed_trees() {
+        let document = document();
+        let parent = document.create_element("div").unwrap();
+        let child = document.create_element("span").unwrap();
+        let text = document.create_text_node("text");
+        parent.append_child(&child).unwrap();
+
+        assert_eq!(child.append_child(&parent), Err(DomError::HierarchyRequest));
+        assert_eq!(
+            parent.append_child(&parent),
+            Err(DomError::HierarchyRequest)
+        );
+        assert_eq!(text.append_child(&child), Err(DomError::HierarchyRequest));
+        assert_eq!(
+            child.insert_before(&text, Some(&parent)),
+            Err(DomError::NotFound)
+        );
+        assert!(document.create_element("a b").is_err());
+        assert!(parent.set_attribute("on\"click", "").is_err());
+    }
+
+    #[test]
+    fn attributes_and_form_state() {
+        let document = document();
+        let div = document.create_element("DIV").unwrap();
+        div.set_attribute("Data-Id", "1").unwrap();
+        div.set_attribute("class", "a b").unwrap();
+        assert_eq!(div.local_name(), "div");
+        assert_eq!(div.get_attribute("data-id"), Some("1".to_owned()));
+        assert_eq!(div.get_attribute_names(), vec!["data-id", "class"]);
+        div.remove_attribute("DATA-ID").unwrap();
+        assert!(!div.has_attribute("data-id"));
+
+        let svg = document
+            .create_element_ns(
+                Some(crate::virtual_dom::vtag::SVG_NAMESPACE),
+                "linearGradient",
+            )
+            .unwrap();
+        svg.set_attribute("gradientUnits", "userSpaceOnUse")
+            .unwrap();
+        assert_eq!(svg.tag_name(), "linearGradient");
+        assert!(svg.has_attribute("gradientUnits"));
+        assert!(!svg.has_attribute("gradientunits"));
+
+        let input = InputElement::try_from(document.create_element("input").unwrap()).unwrap();
+        input.set_attribute("value", "initial").unwrap();
+        assert_eq!(input.value(), "initial");
+        input.set_value("typed");
+        assert_eq!(input.value(), "typed");
+        assert_eq!(input.get_attribute("value"), Some("initial".to_owned()));
+        assert!(!input.checked());
+        input.set_checked(true);
+        assert!(input.checked());
+        assert!(InputElement::try_from(div).is_err());
+    }
+
+    #[test]
+    fn query_selectors() {
+        let document = document();
+        let root = document.create_element("div").unwrap();
+        let list = document.create_element("ul").unwrap();
+        list.set_attribute("id", "list").unwrap();
+        root.append_child(&list).unwrap();
+        for (i, class) in ["item", "item active", "other"].iter().enumerate() {
+            let li = document.create_element("li").unwrap();
+            li.set_attribute("class", class).unwrap();
+            li.set_attribute("data-index", &i.to_string()).unwrap();
+            list.append_child(&li).unwrap();
+        }
+
+        let count = |selectors: &str| root.query_selector_all(selectors).unwrap().len();
+        assert_eq!(count("li"), 3);
+        assert_eq!(count("LI.item"), 2);
+        assert_eq!(count(".item.active"), 1);
+        assert_eq!(count("#list > li"), 3);
+        // Like in a browser, ancestors outside of the queried subtree are considered as well.
+        assert_eq!(count("div li"), 3);
+        assert_eq!(count("section li"), 0);
+        assert_eq!(count("ul li[data-index=\"2\"]"), 1);
+        assert_eq!(count("li[data-index], ul"), 4);
+        assert_eq!(count("*"), 4);
+        assert!(root.query_selector("li >").is_err());
+        assert!(root.query_selector("").is_err());
+    }
+
+    #[test]
+    fn listeners_are_removed_on_drop() {
+        let document = document();
+        let button = document.create_element("button").unwrap();
+        let clicks = Rc::new(Cell::new(0));
+
+        let listener = {
+            let clicks = clicks.clone();
+            button.add_event_listener("click", move |_| clicks.set(clicks.get() + 1))
+        };
+        assert!(button.dispatch_event(&Event::new("click")));
+        button.dispatch_event(&Event::new("focus"));
+        assert_eq!(clicks.get(), 1);
+
+        drop(listener);
+        button.dispatch_event(&Event::new("click"));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn events_bubble_to_ancestors() {
+        let document = document();
+        let form = document.create_element("form").unwrap();
+        let button = document.create_element("button").unwrap();
+        form.append_child(&button).unwrap();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let record = |element: &Element, name: &'static str| {
+            let seen = seen.clone();
+            element.add_event_listener("click", move |event: &Event| {
+                let current = event.current_target().unwrap().local_name();
+                seen.borrow_mut().push(format!("{} on {}", name, current));
+            })
+        };
+        let _button = record(&button, "button");
+        let _form = record(&form, "form");
+        let _focus = {
+            let seen = seen.clone();
+            form.add_event_listener("focus", move |_| seen.borrow_mut().push("focus".into()))
+        };
+
+        button.dispatch_event(&Event::new("click"));
+        button.dispatch_event(&Event::new("focus"));
+        assert_eq!(*seen.borrow(), ["button on button", "form on form"]);
+
+        let _stop = button.add_event_listener("click", |event: &Event| event.stop_propagation());
+        seen.borrow_mut().clear();
+        let event = Event::keyboard("click", "Enter");
+        button.dispatch_event(&event);
+        assert_eq!(*seen.borrow(), ["button on button"]);
+        assert_eq!(event.key(), "Enter");
+        assert!(event.target().unwrap().is_same_node(Some(&button)));
+        assert!(event.current_target().is_none());
+    }
+
+    #[test]
+    fn selects_options_by_value() {
+        let select = document().create_element("select").unwrap();
+        select.set_inner_html(
+            r#"<option value="a">A</option><option selected>B</option><option>C</option>"#,
+        );
+        let select = SelectElement::try_from(select).unwrap();
+        assert_eq!(select.value(), "B");
+
+        select.set_value("C");
+        assert_eq!(select.selected_index(), 2);
+        select.set_value("a");
+        assert_eq!(select.value(), "a");
+        assert_eq!(select.query_selector_all("[selected]").unwrap().len(), 1);
+    }
+
+    struct Counter {
+        link: ComponentLink<Self>,
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+            Counter { link, count: 0 }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            self.count += 1;
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <div class="counter">
+                    <button onclick=self.link.callback(|_| ())>{ "+1" }</button>
+                    <input type="text" value=self.count.to_string() />
+                    { self.count }
+                </div>
+            }
+        }
+    }
+
+    #[test]
+    fn mounts_components() {
+        let parent = document().create_element("main").unwrap();
+        let scope = Scope::<Counter>::new(None).mount_in_place(
+            parent.clone(),
+            NodeRef::default(),
+            None,
+            NodeRef::default(),
+            (),
+        );
+
+        let div = parent.query_selector("div.counter").unwrap().unwrap();
+        assert_eq!(div.text_content(), Some("+10".to_owned()));
+
+        let button = parent.query_selector("button").unwrap().unwrap();
+        button.dispatch_event(&Event::new("click"));
+        button.dispatch_event(&Event::new("click"));
+        assert_eq!(div.text_content(), Some("+12".to_owned()));
+
+        let input = parent.query_selector("input").unwrap().unwrap();
+        let input = InputElement::try_from(input).unwrap();
+        assert_eq!(input.type_(), "text");
+        assert_eq!(input.value(), "2");
+        assert_eq!(scope.get_component().unwrap().count, 2);
+    }
+
+    #[test]
+    fn parses_inner_html() {
+        use crate::virtual_dom::vtag::SVG_NAMESPACE;
+
+        let div = document().create_element("div").unwrap();
+        div.append_child(&document().create_text_node("old"))
+            .unwrap();
+        div.set_inner_html(
+            "<!-- note --><P class='a &amp; b' hidden>1 &lt; 2 &#x26; 3<br>x</p></span>\
+             <svg viewBox=\"0 0 1 1\"><linearGradient/><foreignObject><i>i</i></foreignObject></svg>\
+             <script>if (a < b) {}</script><noscript><p>on</p></noscript><ul><li>open",
+        );
+
+        let children: Vec<String> = div.child_nodes().iter().map(Node::node_name).collect();
+        assert_eq!(
+            children,
+            ["#comment", "P", "svg", "SCRIPT", "NOSCRIPT", "UL"]
+        );
+        let p = div.query_selector("p").unwrap().unwrap();
+        assert_eq!(p.get_attribute("class").as_deref(), Some("a & b"));
+        assert!(p.has_attribute("hidden"));
+        assert_eq!(p.text_content().as_deref(), Some("1 < 2 & 3x"));
+        assert_eq!(p.child_nodes().len(), 3);
+
+        let svg = div.query_selector("svg").unwrap().unwrap();
+        assert_eq!(svg.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+        let names: Vec<String> = svg.children().iter().map(Element::local_name).collect();
+        assert_eq!(names, ["linearGradient", "foreignObject"]);
+        let i = div.query_selector("i").unwrap().unwrap();
+        assert_eq!(i.namespace_uri().as_deref(), Some(HTML_NAMESPACE));
+
+        let script = div.query_selector("script").unwrap().unwrap();
+        assert_eq!(script.text_content().as_deref(), Some("if (a < b) {}"));
+        let noscript = div.query_selector("noscript").unwrap().unwrap();
+        assert_eq!(noscript.text_content().as_deref(), Some("<p>on</p>"));
+        let li = div.query_selector("ul > li").unwrap().unwrap();
+        assert_eq!(li.text_content().as_deref(), Some("open"));
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::anyhow;
 use wasm_bindgen::JsCast;
 use web_sys::{
+    Comment,
     Document,
     Window,
     Element,
@@ -15,7 +16,10 @@ use web_sys::{
     FileList
 };
 use crate::backend::{DomBackend, base_onchange_handler};
+use crate::virtual_dom::vtag::XLINK_NAMESPACE;
 
+/// Represents the `web-sys` rendering backend.
+#[derive(Debug)]
 pub struct Renderer {}
 
 /// A type representing data from `oninput` event.
@@ -63,20 +67,163 @@ impl DomBackend for Renderer {
     type TextAreaElement = TextAreaElement;
     type TextNode = TextNode;
 
+    fn create_element(tag: &str) -> Self::Element {
+        Self::get_document()
+            .create_element(tag)
+            .expect("can't create element for vtag")
+    }
+
+    fn create_element_ns(namespace: Option<&str>, tag: &str) -> Self::Element {
+        Self::get_document()
+            .create_element_ns(namespace, tag)
+            .expect("can't create namespaced element for vtag")
+    }
+
+    fn create_text_node(text: &str) -> Self::TextNode {
+        Self::get_document().create_text_node(text)
+    }
+
+    fn parse_html(html: &str) -> Vec<Self::Node> {
+        let container = Self::create_element("div");
+        container.set_inner_html(html);
+        let mut nodes = Vec::new();
+        while let Some(node) = container.first_child() {
+            container
+                .remove_child(&node)
+                .expect("can't remove a parsed node");
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    fn text_as_node(text: &Self::TextNode) -> Self::Node {
+        text.clone().into()
+    }
+
+    fn text_data(text: &Self::TextNode) -> String {
+        text.data()
+    }
+
+    fn text_set_data(text: &Self::TextNode, data: &str) {
+        text.set_node_value(Some(data));
+    }
+
+    fn node_next_sibling(node: &Self::Node) -> Option<Self::Node> {
+        node.next_sibling()
+    }
+
+    fn node_as_element(node: &Self::Node) -> Option<Self::Element> {
+        node.dyn_ref::<Element>().cloned()
+    }
+
+    fn node_as_text(node: &Self::Node) -> Option<Self::TextNode> {
+        node.dyn_ref::<TextNode>().cloned()
+    }
+
+    fn node_comment_data(node: &Self::Node) -> Option<String> {
+        node.dyn_ref::<Comment>().map(|comment| comment.data())
+    }
+
     fn element_as_node(element: &Self::Element) -> Self::Node {
-        todo!()
+        element.clone().into()
+    }
+
+    fn element_first_child(element: &Self::Element) -> Option<Self::Node> {
+        element.first_child()
+    }
+
+    fn element_last_child(element: &Self::Element) -> Option<Self::Node> {
+        element.last_child()
+    }
+
+    fn element_insert_before(
+        element: &Self::Element,
+        node: &Self::Node,
+        next_sibling: Option<&Self::Node>,
+    ) -> Result<(), ()> {
+        element
+            .insert_before(node, next_sibling)
+            .map(drop)
+            .map_err(drop)
+    }
+
+    fn element_remove_child(element: &Self::Element, child: &Self::Node) -> Result<Self::Node, ()> {
+        element.remove_child(child).map_err(drop)
+    }
+
+    fn element_namespace_uri(element: &Self::Element) -> Option<String> {
+        element.namespace_uri()
+    }
+
+    fn element_local_name(element: &Self::Element) -> String {
+        element.local_name()
+    }
+
+    fn element_attribute_names(element: &Self::Element) -> Vec<String> {
+        element
+            .get_attribute_names()
+            .iter()
+            .filter_map(|name| name.as_string())
+            .collect()
+    }
+
+    fn element_get_attribute(element: &Self::Element, key: &str) -> Option<String> {
+        element.get_attribute(key)
+    }
+
+    fn element_set_attribute(element: &Self::Element, key: &str, value: &str) -> Result<(), ()> {
+        // `xlink:href` and friends only take effect in the XLink namespace
+        if key.starts_with("xlink:") {
+            element
+                .set_attribute_ns(Some(XLINK_NAMESPACE), key, value)
+                .map_err(drop)
+        } else {
+            element.set_attribute(key, value).map_err(drop)
+        }
+    }
+
+    fn element_remove_attribute(element: &Self::Element, key: &str) -> Result<(), ()> {
+        element.remove_attribute(key).map_err(drop)
+    }
+
+    fn element_as_input(element: &Self::Element) -> Option<Self::InputElement> {
+        element.dyn_ref::<InputElement>().cloned()
+    }
+
+    fn element_as_textarea(element: &Self::Element) -> Option<Self::TextAreaElement> {
+        element.dyn_ref::<TextAreaElement>().cloned()
+    }
+
+    fn element_as_button(element: &Self::Element) -> Option<Self::ButtonElement> {
+        element.dyn_ref::<ButtonElement>().cloned()
+    }
+
+    fn input_value(input: &Self::InputElement) -> String {
+        input.value()
+    }
+
+    fn input_set_value(input: &Self::InputElement, value: &str) {
+        input.set_value(value)
+    }
+
+    fn input_set_type(input: &Self::InputElement, kind: &str) {
+        input.set_type(kind)
+    }
+
+    fn input_set_checked(input: &Self::InputElement, checked: bool) {
+        input.set_checked(checked)
     }
 
-    fn element_last_child(element: &Self::Element) -> Option<Self::Element> {
-        todo!()
+    fn textarea_value(textarea: &Self::TextAreaElement) -> String {
+        textarea.value()
     }
 
-    fn element_remove_child(element: &Self::Element, child: &Self::Element) -> Result<Self::Node, ()> {
-        todo!()
+    fn textarea_set_value(textarea: &Self::TextAreaElement, value: &str) {
+        textarea.set_value(value)
     }
 
-    fn cast_node_ref<INTO>(node_ref: &crate::NodeRef) -> Option<INTO> {
-        todo!()
+    fn button_set_type(button: &Self::ButtonElement, kind: &str) {
+        button.set_type(kind)
     }
 
     fn oninput_handler(this: &Self::Element, event: Self::InputEvent) -> Self::InputData {
@@ -96,8 +243,7 @@ impl DomBackend for Renderer {
     }
 
     fn get_document() -> Self::Document {
-        Self::get_window().document().unwrap();
-        todo!()
+        Self::get_window().document().unwrap()
     }
 
     fn get_origin() -> Result<String, anyhow::Error> {
@@ -107,7 +253,7 @@ impl DomBackend for Renderer {
                 .as_string()
                 .unwrap_or_else(|| String::from("error not recoverable")),)
         })?;
-        todo!()
+        Ok(origin)
     }
 
     fn get_host() -> Result<String, anyhow::Error> {
@@ -117,11 +263,10 @@ impl DomBackend for Renderer {
                 .as_string()
                 .unwrap_or_else(|| String::from("error not recoverable")),)
         })?;
-        todo!()
+        Ok(host)
     }
 
     fn get_window() -> Self::Window {
-        web_sys::window().expect("no window available");
-        todo!()
+        web_sys::window().expect("no window available")
     }
 }
@@ -36,7 +36,7 @@ impl<IN> Clone for Callback<IN> {
     }
 }
 
-#[allow(clippy::vtable_address_comparisons)]
+#[allow(ambiguous_wide_pointer_comparisons)]
 impl<IN> PartialEq for Callback<IN> {
     fn eq(&self, other: &Callback<IN>) -> bool {
         match (&self, &other) {
@@ -113,6 +113,7 @@ impl<IN: 'static> Callback<IN> {
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub(crate) mod test_util {
     use super::*;
     use std::cell::RefCell;
@@ -148,9 +149,9 @@ pub(crate) mod test_util {
         }
     }
 
-    impl<T: 'static> Into<Callback<T>> for CallbackFuture<T> {
-        fn into(self) -> Callback<T> {
-            Callback::from(move |r| self.finish(r))
+    impl<T: 'static> From<CallbackFuture<T>> for Callback<T> {
+        fn from(future: CallbackFuture<T>) -> Self {
+            Callback::from(move |r| future.finish(r))
         }
     }
 
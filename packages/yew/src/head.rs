@@ -0,0 +1,725 @@
+//! This module contains components which render into the `<head>` of the document instead of
+//! their place in the component tree, e.g. to set the title or the meta tags of a page.
+//!
+//! Every tag has a key. If several mounted components provide a tag with the same key, the one
+//! which was provided last wins, and the previous one is restored once that component is
+//! destroyed. Keys are derived from the tag: `<title>` and `<meta charset>` are unique, `<meta>`
+//! tags are keyed by their `name`, `property` or `http-equiv` attribute and canonical links by
+//! their `rel`. Any other tag is only deduplicated against identical tags, unless it is given a
+//! `key` explicitly.
+//!
+//! On the client the tags are kept in sync with `document.head`. Tags rendered on the server
+//! carry a `data-yew-head` attribute with their key and are replaced by the client's tags with
+//! the same key. On the server the tags are gathered by a `HeadCollector`.
+//!
+//! ```
+//! # use yew::prelude::*;
+//! use yew::head::{Head, Meta, Title};
+//!
+//! # fn view() -> Html {
+//! html! {
+//!     <>
+//!         <Title>{ "About us" }</Title>
+//!         <Meta name="description" content="Who we are" />
+//!         <Head>
+//!             <link rel="canonical" href="https://example.com/about" />
+//!             <meta property="og:title" content="About us" />
+//!         </Head>
+//!         <h1>{ "About us" }</h1>
+//!     </>
+//! }
+//! # }
+//! ```
+
+use crate::backend::{DomBackend, Element, Renderer};
+use crate::html::{AnyScope, Children, Component, ComponentLink, Html, Properties, ShouldRender};
+use crate::runtime::Runtime;
+use crate::utils::document;
+use crate::virtual_dom::{vdiff::VDiff, VNode, VTag};
+use crate::{html, NodeRef};
+use indexmap::IndexMap;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[cfg(feature = "static_render")]
+pub use self::collector::HeadCollector;
+
+/// The attribute which holds the key of a tag rendered into the head.
+pub const HEAD_KEY_ATTRIBUTE: &str = "data-yew-head";
+
+thread_local! {
+    static NEXT_OWNER: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Properties of the `Head` component.
+#[derive(Clone, Debug, Properties)]
+pub struct HeadProps {
+    /// The tags which are rendered into the head
+    pub children: Children,
+}
+
+/// Renders its children into the head of the document. Children which aren't tags are ignored.
+#[derive(Debug)]
+pub struct Head {
+    tags: HeadTags,
+}
+
+impl Component for Head {
+    type Message = ();
+    type Properties = HeadProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut tags = HeadTags::new(link.into());
+        tags.set(children_tags(&props.children));
+        Head { tags }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.tags.set(children_tags(&props.children));
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {}
+    }
+}
+
+/// Properties of the `Title` component.
+#[derive(Clone, Debug, Properties)]
+pub struct TitleProps {
+    /// The text of the title
+    pub children: Children,
+}
+
+/// Sets the title of the document.
+#[derive(Debug)]
+pub struct Title {
+    tags: HeadTags,
+}
+
+impl Title {
+    fn tag(props: &TitleProps) -> VTag {
+        let mut tag = VTag::new("title");
+        tag.add_children(props.children.iter());
+        tag
+    }
+}
+
+impl Component for Title {
+    type Message = ();
+    type Properties = TitleProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut tags = HeadTags::new(link.into());
+        tags.set(vec![Title::tag(&props)]);
+        Title { tags }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.tags.set(vec![Title::tag(&props)]);
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {}
+    }
+}
+
+/// Properties of the `Meta` component. Empty attributes are left out.
+#[derive(Clone, Debug, Default, Properties)]
+pub struct MetaProps {
+    /// The `name` attribute, e.g. `description`
+    #[prop_or_default]
+    pub name: String,
+    /// The `property` attribute used by Open Graph, e.g. `og:title`
+    #[prop_or_default]
+    pub property: String,
+    /// The `http-equiv` attribute
+    #[prop_or_default]
+    pub http_equiv: String,
+    /// The `charset` attribute
+    #[prop_or_default]
+    pub charset: String,
+    /// The `content` attribute
+    #[prop_or_default]
+    pub content: String,
+}
+
+/// Adds a `<meta>` tag to the head of the document.
+#[derive(Debug)]
+pub struct Meta {
+    tags: HeadTags,
+}
+
+impl Meta {
+    fn tag(props: &MetaProps) -> VTag {
+        let mut tag = VTag::new("meta");
+        let attributes = [
+            ("name", &props.name),
+            ("property", &props.property),
+            ("http-equiv", &props.http_equiv),
+            ("charset", &props.charset),
+            ("content", &props.content),
+        ];
+        for (key, value) in attributes.iter() {
+            if !value.is_empty() {
+                tag.add_attribute(key, value.to_string());
+            }
+        }
+        tag
+    }
+}
+
+impl Component for Meta {
+    type Message = ();
+    type Properties = MetaProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut tags = HeadTags::new(link.into());
+        tags.set(vec![Meta::tag(&props)]);
+        Meta { tags }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.tags.set(vec![Meta::tag(&props)]);
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {}
+    }
+}
+
+fn children_tags(children: &Children) -> Vec<VTag> {
+    children
+        .iter()
+        .filter_map(|child| match child {
+            VNode::VTag(tag) => Some(*tag),
+            _ => {
+                log::warn!("only tags can be rendered into the head");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the key which is used to deduplicate `tag`.
+fn head_key(tag: &VTag) -> String {
+    if let Some(key) = &tag.key {
+        return key.to_string();
+    }
+
+    let attribute = |name: &str| {
+        tag.attributes
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    };
+    match tag.tag() {
+        "title" | "base" => return tag.tag().to_owned(),
+        "meta" => {
+            if attribute("charset").is_some() {
+                return "meta:charset".to_owned();
+            }
+            for name in ["name", "property", "http-equiv", "itemprop"].iter() {
+                if let Some(value) = attribute(name) {
+                    return format!("meta:{}:{}", name, value);
+                }
+            }
+        }
+        "link" if attribute("rel") == Some("canonical") => return "link:canonical".to_owned(),
+        _ => {}
+    }
+
+    let mut key = tag.tag().to_owned();
+    for (name, value) in tag.attributes.iter() {
+        key.push_str(&format!("[{}={}]", name, value));
+    }
+    key
+}
+
+/// A tag provided by a mounted component.
+struct Entry {
+    owner: usize,
+    tag: VTag,
+    scope: AnyScope,
+}
+
+/// The tags of all mounted head components.
+#[derive(Default)]
+struct Registry {
+    /// The tags of every key in the order the keys were first used. The last tag of a key is
+    /// the one in effect.
+    keys: IndexMap<String, Vec<Entry>>,
+}
+
+impl Registry {
+    /// Replaces the tags provided by `owner`. Returns the keys whose tags might have changed.
+    fn set(&mut self, owner: usize, scope: &AnyScope, tags: Vec<VTag>) -> Vec<String> {
+        let mut tags: IndexMap<String, VTag> = tags
+            .into_iter()
+            .map(|mut tag| {
+                let key = head_key(&tag);
+                tag.add_attribute(HEAD_KEY_ATTRIBUTE, key.clone());
+                (key, tag)
+            })
+            .collect();
+
+        let mut changed = Vec::new();
+        for (key, entries) in self.keys.iter_mut() {
+            if let Some(index) = entries.iter().position(|entry| entry.owner == owner) {
+                match tags.swap_remove(key) {
+                    // Keep the position, so a re-rendered parent doesn't override its children.
+                    Some(tag) => entries[index].tag = tag,
+                    None => {
+                        entries.remove(index);
+                    }
+                }
+                changed.push(key.clone());
+            }
+        }
+        for (key, tag) in tags {
+            self.keys.entry(key.clone()).or_default().push(Entry {
+                owner,
+                tag,
+                scope: scope.clone(),
+            });
+            changed.push(key);
+        }
+        self.keys.retain(|_, entries| !entries.is_empty());
+        changed
+    }
+
+    fn current(&self, key: &str) -> Option<&Entry> {
+        self.keys.get(key).and_then(|entries| entries.last())
+    }
+
+    #[cfg(feature = "static_render")]
+    fn tags(&self) -> impl Iterator<Item = &VTag> {
+        self.keys
+            .values()
+            .filter_map(|entries| entries.last())
+            .map(|entry| &entry.tag)
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.keys.keys()).finish()
+    }
+}
+
+/// The tags in effect in `document.head`.
+#[derive(Default)]
+struct DocumentHead {
+    registry: Registry,
+    mounted: HashMap<String, VNode>,
+}
+
+impl DocumentHead {
+    /// Returns the tags of the document of the current runtime.
+    fn current() -> Rc<RefCell<DocumentHead>> {
+        Runtime::current().local(RefCell::default)
+    }
+
+    fn set(owner: usize, scope: &AnyScope, tags: Vec<VTag>) {
+        let changed = Self::current()
+            .borrow_mut()
+            .registry
+            .set(owner, scope, tags);
+        let head = match document().query_selector("head") {
+            Ok(Some(head)) => head,
+            _ => {
+                log::warn!("the document has no head");
+                return;
+            }
+        };
+        for key in changed {
+            Self::sync(&head, &key);
+        }
+    }
+
+    /// Brings the tag for `key` in `head` up to date with the registry.
+    fn sync(head: &Element, key: &str) {
+        // The head isn't borrowed while tags are applied, as they may contain components.
+        let document_head = Self::current();
+        let (current, mounted) = {
+            let mut document_head = document_head.borrow_mut();
+            let current = document_head
+                .registry
+                .current(key)
+                .map(|entry| (entry.tag.clone(), entry.scope.clone()));
+            (current, document_head.mounted.remove(key))
+        };
+
+        match (current, mounted) {
+            (Some((tag, scope)), ancestor) => {
+                if ancestor.is_none() {
+                    remove_server_rendered(head, key);
+                }
+                let mut node = VNode::VTag(Box::new(tag));
+                node.apply(&scope, head, NodeRef::default(), ancestor);
+                document_head
+                    .borrow_mut()
+                    .mounted
+                    .insert(key.to_owned(), node);
+            }
+            (None, Some(mut node)) => node.detach(head),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Removes the tags for `key` which were rendered on the server.
+fn remove_server_rendered(head: &Element, key: &str) {
+    let mut cursor = Renderer::element_first_child(head);
+    while let Some(node) = cursor {
+        cursor = Renderer::node_next_sibling(&node);
+        let rendered = Renderer::node_as_element(&node).is_some_and(|element| {
+            Renderer::element_get_attribute(&element, HEAD_KEY_ATTRIBUTE).as_deref() == Some(key)
+        });
+        if rendered {
+            Renderer::element_remove_child(head, &node).expect("can't remove a child");
+        }
+    }
+}
+
+/// Where the tags of a component go.
+enum Target {
+    Document,
+    #[cfg(feature = "static_render")]
+    Collector(HeadCollector),
+}
+
+/// The tags provided by a single component. They are removed again when it is dropped.
+struct HeadTags {
+    owner: usize,
+    scope: AnyScope,
+    target: Target,
+}
+
+impl HeadTags {
+    fn new(scope: AnyScope) -> Self {
+        let owner = NEXT_OWNER.with(|next| next.replace(next.get() + 1));
+        #[cfg(feature = "static_render")]
+        let target = HeadCollector::current().map_or(Target::Document, Target::Collector);
+        #[cfg(not(feature = "static_render"))]
+        let target = Target::Document;
+        HeadTags {
+            owner,
+            scope,
+            target,
+        }
+    }
+
+    fn set(&mut self, tags: Vec<VTag>) {
+        match &self.target {
+            Target::Document => DocumentHead::set(self.owner, &self.scope, tags),
+            #[cfg(feature = "static_render")]
+            Target::Collector(collector) => collector.set(self.owner, &self.scope, tags),
+        }
+    }
+}
+
+impl Drop for HeadTags {
+    fn drop(&mut self) {
+        match &self.target {
+            Target::Document => self.set(Vec::new()),
+            // Components are destroyed after the page has been rendered. Only the tags of those
+            // which are removed while rendering are dropped.
+            #[cfg(feature = "static_render")]
+            Target::Collector(collector) if collector.is_current() => self.set(Vec::new()),
+            #[cfg(feature = "static_render")]
+            Target::Collector(_) => {}
+        }
+    }
+}
+
+impl fmt::Debug for HeadTags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HeadTags")
+    }
+}
+
+#[cfg(feature = "static_render")]
+mod collector {
+    use super::*;
+    use crate::smr::{FmtWriter, HtmlRenderError, HtmlString, HtmlWrite, RenderOptions, ToHtml};
+    use std::rc::Rc;
+
+    thread_local! {
+        /// The collector of the render which is currently in progress.
+        static CURRENT: RefCell<Option<HeadCollector>> = const { RefCell::new(None) };
+    }
+
+    /// Gathers the head tags of the components which are rendered on the server.
+    ///
+    /// Pass a collector to `App::with_head` and write its tags into the head of the page once
+    /// the app has been rendered.
+    #[derive(Clone, Default)]
+    pub struct HeadCollector {
+        registry: Rc<RefCell<Registry>>,
+    }
+
+    impl HeadCollector {
+        /// Creates an empty collector.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Returns `true` if no tags were collected.
+        pub fn is_empty(&self) -> bool {
+            self.registry.borrow().keys.is_empty()
+        }
+
+        /// Writes the collected tags into `writer`, in the order their keys were first used.
+        pub fn write_html<W: HtmlWrite + ?Sized>(
+            &self,
+            writer: &mut W,
+            options: &RenderOptions,
+        ) -> Result<(), HtmlRenderError> {
+            self.write_html_with_defaults(&[], writer, options)
+        }
+
+        /// Writes `defaults` followed by the collected tags. A default is left out if a tag
+        /// with the same key was collected.
+        pub(crate) fn write_html_with_defaults<W: HtmlWrite + ?Sized>(
+            &self,
+            defaults: &[VTag],
+            writer: &mut W,
+            options: &RenderOptions,
+        ) -> Result<(), HtmlRenderError> {
+            let registry = self.registry.borrow();
+            for tag in defaults {
+                let key = head_key(tag);
+                if !registry.keys.contains_key(&key) {
+                    let mut tag = tag.clone();
+                    tag.add_attribute(HEAD_KEY_ATTRIBUTE, key);
+                    tag.write_html_with_options(writer, options)?;
+                }
+            }
+            for tag in registry.tags() {
+                tag.write_html_with_options(writer, options)?;
+            }
+            Ok(())
+        }
+
+        /// Renders the collected tags to HTML.
+        pub fn to_html(&self) -> Result<HtmlString, HtmlRenderError> {
+            let mut html = String::new();
+            self.write_html(&mut FmtWriter::new(&mut html), &RenderOptions::default())?;
+            Ok(HtmlString::new(html))
+        }
+
+        /// Runs `f` with head components collecting their tags into this collector.
+        pub(crate) fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+            crate::utils::scoped(&CURRENT, self.clone(), f)
+        }
+
+        pub(super) fn current() -> Option<Self> {
+            CURRENT.with(|current| current.borrow().clone())
+        }
+
+        pub(super) fn is_current(&self) -> bool {
+            CURRENT.with(|current| {
+                current
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|current| Rc::ptr_eq(&current.registry, &self.registry))
+            })
+        }
+
+        pub(super) fn set(&self, owner: usize, scope: &AnyScope, tags: Vec<VTag>) {
+            self.registry.borrow_mut().set(owner, scope, tags);
+        }
+    }
+
+    impl fmt::Debug for HeadCollector {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("HeadCollector")
+                .field("keys", &*self.registry.borrow())
+                .finish()
+        }
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::ComponentLink;
+    use crate::App;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Page {
+        show_article: bool,
+    }
+
+    impl Component for Page {
+        type Message = bool;
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page { show_article: true }
+        }
+
+        fn update(&mut self, show_article: Self::Message) -> ShouldRender {
+            self.show_article = show_article;
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <main>
+                    <Title>{ "Blog" }</Title>
+                    <Meta name="description" content="All posts" />
+                    <Meta charset="utf-8" />
+                    { if self.show_article { html! { <Article /> } } else { html! {} } }
+                </main>
+            }
+        }
+    }
+
+    struct Article;
+
+    impl Component for Article {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Article
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <article>
+                    <Title>{ "Hello" }</Title>
+                    <Head>
+                        <meta name="description" content="A first post" />
+                        <link rel="canonical" href="https://example.com/hello" />
+                    </Head>
+                </article>
+            }
+        }
+    }
+
+    fn head_tags(head: &Element) -> Vec<(String, String)> {
+        head.children()
+            .into_iter()
+            .map(|element| {
+                (
+                    element
+                        .get_attribute(HEAD_KEY_ATTRIBUTE)
+                        .unwrap_or_default(),
+                    element
+                        .get_attribute("content")
+                        .or_else(|| element.get_attribute("href"))
+                        .or_else(|| element.text_content())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collector_keeps_the_latest_tag_per_key() {
+        let collector = HeadCollector::new();
+        let html = App::<Page>::new()
+            .with_head(collector.clone())
+            .render_to_string()
+            .unwrap();
+
+        assert_eq!(
+            html.as_str(),
+            "<main><!----><!----><!----><article><!----><!----></article></main>"
+        );
+        assert_eq!(
+            collector.to_html().unwrap().as_str(),
+            "<title data-yew-head=\"title\">Hello</title>\
+             <meta content=\"A&#x20;first&#x20;post\" name=\"description\" \
+             data-yew-head=\"meta&#x3A;name&#x3A;description\">\
+             <meta charset=\"utf&#x2D;8\" data-yew-head=\"meta&#x3A;charset\">\
+             <link href=\"https&#x3A;&#x2F;&#x2F;example&#x2E;com&#x2F;hello\" rel=\"canonical\" \
+             data-yew-head=\"link&#x3A;canonical\">"
+        );
+
+        // Rendering on the server leaves the document alone.
+        let head = document().query_selector("head").unwrap().unwrap();
+        assert!(head.children().is_empty());
+    }
+
+    #[test]
+    fn document_head_follows_mounted_components() {
+        let head = document().query_selector("head").unwrap().unwrap();
+        let server_title = document().create_element("title").unwrap();
+        server_title
+            .set_attribute(HEAD_KEY_ATTRIBUTE, "title")
+            .unwrap();
+        server_title
+            .append_child(&document().create_text_node("Server"))
+            .unwrap();
+        head.append_child(&server_title).unwrap();
+
+        let body = document().query_selector("body").unwrap().unwrap();
+        let link = App::<Page>::new().mount(body);
+        let mut tags = head_tags(&head);
+        tags.sort();
+        assert_eq!(
+            tags,
+            vec![
+                (
+                    "link:canonical".to_owned(),
+                    "https://example.com/hello".to_owned()
+                ),
+                ("meta:charset".to_owned(), "".to_owned()),
+                (
+                    "meta:name:description".to_owned(),
+                    "A first post".to_owned()
+                ),
+                ("title".to_owned(), "Hello".to_owned()),
+            ]
+        );
+
+        link.send_message(false);
+        let mut tags = head_tags(&head);
+        tags.sort();
+        assert_eq!(
+            tags,
+            vec![
+                ("meta:charset".to_owned(), "".to_owned()),
+                ("meta:name:description".to_owned(), "All posts".to_owned()),
+                ("title".to_owned(), "Blog".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! This module contains `ErrorBoundary`, which keeps a failing component from taking the rest of
+//! the app down with it.
+
+use super::{AnyScope, Children, Component, ComponentLink, Html, Properties, ShouldRender};
+use crate::callback::Callback;
+use crate::html;
+use std::any::{self, Any, TypeId};
+use std::error::Error;
+use std::fmt;
+
+/// The error of a component which failed to render.
+///
+/// Components fail by returning an error from `Component::try_view` or by panicking in one of
+/// their lifecycle methods. Any error type can be turned into a `ComponentError` with `?`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentError {
+    message: String,
+    component: Option<&'static str>,
+}
+
+impl ComponentError {
+    /// Creates an error with `message`.
+    pub fn new(message: impl Into<String>) -> Self {
+        ComponentError {
+            message: message.into(),
+            component: None,
+        }
+    }
+
+    /// Returns the message of the error or the panic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the type name of the component which failed.
+    pub fn component(&self) -> Option<&'static str> {
+        self.component
+    }
+
+    /// Turns the payload of a panic into an error.
+    pub(crate) fn from_panic(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "the component panicked".to_owned());
+        ComponentError::new(message)
+    }
+
+    /// Marks the error as an error of `COMP`.
+    pub(crate) fn of<COMP: Component>(mut self) -> Self {
+        self.component.get_or_insert(any::type_name::<COMP>());
+        self
+    }
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.component {
+            Some(component) => write!(f, "{} failed: {}", component, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl<E: Error> From<E> for ComponentError {
+    fn from(error: E) -> Self {
+        ComponentError::new(error.to_string())
+    }
+}
+
+/// Sends `error` to the closest `ErrorBoundary` among `scope` and its ancestors. The error is
+/// handed back if there is none.
+pub(crate) fn report(
+    scope: Option<&AnyScope>,
+    error: ComponentError,
+) -> Result<(), ComponentError> {
+    let mut scope = scope;
+    while let Some(current) = scope {
+        if *current.get_type_id() == TypeId::of::<ErrorBoundary>() {
+            current
+                .clone()
+                .downcast::<ErrorBoundary>()
+                .send_message(error);
+            return Ok(());
+        }
+        scope = current.get_parent();
+    }
+    Err(error)
+}
+
+/// Properties of [`ErrorBoundary`].
+#[derive(Clone, Debug, Properties)]
+pub struct ErrorBoundaryProps {
+    /// The components which are guarded
+    pub children: Children,
+    /// Rendered in place of the children once one of them failed
+    #[prop_or_default]
+    pub fallback: Html,
+    /// Called with the error of a child
+    #[prop_or_default]
+    pub onerror: Callback<ComponentError>,
+}
+
+/// Renders a fallback instead of its children once one of them fails.
+///
+/// A component inside the boundary, however deeply nested, fails if it panics in `create`,
+/// `update`, `change`, `view` or `rendered`, or if its `try_view` returns an error. The failed
+/// children are destroyed, the boundary renders `fallback` and passes the error to `onerror`,
+/// e.g. to log it. The rest of the app keeps working, both on the client and while rendering on
+/// the server. The children are rendered again once the boundary is given new children.
+///
+/// Errors inside the fallback are passed on to the next boundary up the tree. Without a
+/// boundary, errors are logged and the failed component isn't rendered, while panics unwind as
+/// usual. Panics can only be caught where they unwind, which excludes `wasm32` targets, so
+/// components running in the browser should report errors through `try_view`.
+///
+/// # Example
+/// ```
+/// # use yew::prelude::*;
+/// use yew::html::ErrorBoundary;
+///
+/// # fn view() -> Html {
+/// html! {
+///     <ErrorBoundary fallback=html! { <p>{ "The comments couldn't be loaded" }</p> }>
+///         <h2>{ "Comments" }</h2>
+///     </ErrorBoundary>
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ErrorBoundary {
+    props: ErrorBoundaryProps,
+    link: ComponentLink<Self>,
+    error: Option<ComponentError>,
+}
+
+impl Component for ErrorBoundary {
+    type Message = ComponentError;
+    type Properties = ErrorBoundaryProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        ErrorBoundary {
+            props,
+            link,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, error: Self::Message) -> ShouldRender {
+        if self.error.is_some() {
+            // The fallback failed as well
+            if let Err(error) = report(self.link.get_parent(), error) {
+                log::error!("{}", error);
+            }
+            return false;
+        }
+        self.props.onerror.emit(error.clone());
+        self.error = Some(error);
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.children != self.props.children {
+            self.error = None;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        match self.error {
+            Some(_) => self.props.fallback.clone(),
+            None => html! { <>{ self.props.children.clone() }</> },
+        }
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::App;
+    use std::cell::RefCell;
+    use std::panic;
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Failure {
+        None,
+        Create,
+        Update,
+        View,
+    }
+
+    #[derive(Clone, Debug, Properties)]
+    struct WidgetProps {
+        failure: Failure,
+    }
+
+    struct Widget {
+        props: WidgetProps,
+    }
+
+    impl Component for Widget {
+        type Message = ();
+        type Properties = WidgetProps;
+
+        fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+            match props.failure {
+                Failure::Create => panic!("cannot create widget"),
+                Failure::Update => link.send_message(()),
+                _ => {}
+            }
+            Widget { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            panic!("cannot update widget")
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <b>{ "widget" }</b> }
+        }
+
+        fn try_view(&self) -> Result<Html, ComponentError> {
+            if self.props.failure == Failure::View {
+                "x".parse::<u32>()?;
+            }
+            Ok(self.view())
+        }
+    }
+
+    #[derive(Clone, Debug, Properties)]
+    struct PageProps {
+        failure: Failure,
+        errors: Rc<RefCell<Vec<ComponentError>>>,
+    }
+
+    struct Page {
+        props: PageProps,
+    }
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = PageProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let errors = self.props.errors.clone();
+            html! {
+                <main>
+                    <h1>{ "Page" }</h1>
+                    <ErrorBoundary
+                        fallback=html! { <p>{ "fallback" }</p> }
+                        onerror=Callback::from(move |error| errors.borrow_mut().push(error))
+                    >
+                        <Widget failure=self.props.failure />
+                    </ErrorBoundary>
+                </main>
+            }
+        }
+    }
+
+    fn props(failure: Failure) -> PageProps {
+        PageProps {
+            failure,
+            errors: Rc::default(),
+        }
+    }
+
+    fn render(failure: Failure) -> (String, Vec<ComponentError>) {
+        let props = props(failure);
+        let errors = props.errors.clone();
+        let html = crate::render_to_string::<Page>(props).unwrap();
+        let errors = errors.borrow().clone();
+        (html.as_str().to_owned(), errors)
+    }
+
+    #[test]
+    fn children_are_rendered_while_they_work() {
+        let (html, errors) = render(Failure::None);
+        assert_eq!(html, "<main><h1>Page</h1><b>widget</b></main>");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn panics_render_the_fallback() {
+        let (html, errors) = render(Failure::Create);
+        assert_eq!(html, "<main><h1>Page</h1><p>fallback</p></main>");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "cannot create widget");
+        assert!(errors[0].component().unwrap().ends_with("::Widget"));
+    }
+
+    #[test]
+    fn errors_of_the_view_render_the_fallback() {
+        let (html, errors) = render(Failure::View);
+        assert_eq!(html, "<main><h1>Page</h1><p>fallback</p></main>");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn mounted_apps_render_the_fallback() {
+        let element = crate::utils::document().create_element("div").unwrap();
+        let props = props(Failure::Update);
+        let errors = props.errors.clone();
+        App::<Page>::new().mount_with_props(element.clone(), props);
+        assert_eq!(element.text_content().unwrap(), "Pagefallback");
+        assert_eq!(errors.borrow()[0].message(), "cannot update widget");
+    }
+
+    #[test]
+    fn panics_without_a_boundary_leave_the_scheduler_usable() {
+        let mount = |failure| {
+            let element = crate::utils::document().create_element("div").unwrap();
+            App::<Widget>::new().mount_with_props(element.clone(), WidgetProps { failure });
+            element.text_content().unwrap()
+        };
+        assert!(panic::catch_unwind(|| mount(Failure::Create)).is_err());
+        assert_eq!(mount(Failure::None), "widget");
+    }
+}
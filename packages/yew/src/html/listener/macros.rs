@@ -1,4 +1,3 @@
-#[macro_use]
 macro_rules! impl_action {
     ($($action:ident(event: $type:ident) -> $ret:ty => $convert:expr)*) => {$(
         impl_action!($action(name: "", event: $type) -> $ret => $convert);
@@ -17,10 +16,12 @@ macro_rules! impl_action {
                 if #[cfg(feature = "std_web")] {
                     use stdweb::web::event::$type;
                     use stdweb::web::{Element, IEventTarget};
-                } else if #[cfg(feature = "web_sys")] {
+                } else if #[cfg(yew_backend = "web_sys")] {
                     use gloo::events::{EventListener, EventListenerOptions};
                     use wasm_bindgen::JsValue;
                     use web_sys::{$type as WebSysType, Element, EventTarget};
+                } else if #[cfg(yew_backend = "static_render")] {
+                    use crate::backend::{$type as BackendType, Element, Event as BackendEvent, EventListener};
                 }
             }
 
@@ -48,17 +49,18 @@ macro_rules! impl_action {
                 fn attach(&self, element: &Element) -> EventListener {
                     let this = element.clone();
                     let callback = self.callback.clone();
-                    let listener = move |
-                        #[cfg(feature = "std_web")] event: $type,
-                        #[cfg(feature = "web_sys")] event: &web_sys::Event
-                    | {
-                        #[cfg(feature = "web_sys")]
-                        let event: WebSysType = JsValue::from(event).into();
-                        callback.emit($convert(&this, event));
-                    };
                     cfg_match! {
-                        feature = "std_web" => EventListener(Some(element.add_event_listener(listener))),
-                        feature = "web_sys" => ({
+                        feature = "std_web" => ({
+                            let listener = move |event: $type| {
+                                callback.emit($convert(&this, event));
+                            };
+                            EventListener(Some(element.add_event_listener(listener)))
+                        }),
+                        yew_backend = "web_sys" => ({
+                            let listener = move |event: &web_sys::Event| {
+                                let event: WebSysType = JsValue::from(event).into();
+                                callback.emit($convert(&this, event));
+                            };
                             // We should only set passive event listeners for `touchstart` and `touchmove`.
                             // See here: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#Improving_scrolling_performance_with_passive_listeners
                             if $name == "touchstart" || $name == "touchmove" {
@@ -68,6 +70,13 @@ macro_rules! impl_action {
                                 EventListener::new_with_options(&EventTarget::from(element.clone()), $name, options, listener)
                             }
                         }),
+                        yew_backend = "static_render" => ({
+                            let listener = move |event: &BackendEvent| {
+                                let event = BackendType::from(event.clone());
+                                callback.emit($convert(&this, event));
+                            };
+                            element.add_event_listener($name, listener)
+                        }),
                     }
                 }
             }
@@ -3,22 +3,28 @@
 //! Also this module contains declaration of `Component` trait which used
 //! to create own UI-components.
 
+mod error_boundary;
 mod listener;
 mod scope;
 
+pub(crate) use error_boundary::report;
+pub use error_boundary::{ComponentError, ErrorBoundary, ErrorBoundaryProps};
 pub use listener::*;
 pub use scope::{AnyScope, Scope, SendAsMessage};
 pub(crate) use scope::{ComponentUpdate, Scoped};
 pub use yew_macro::Properties;
 
+use crate::backend::Node;
 use crate::callback::Callback;
 use crate::virtual_dom::{VChild, VNode};
-use crate::backend::Node;
 use cfg_if::cfg_if;
-use cfg_match::cfg_match;
 use std::cell::RefCell;
+#[cfg(not(yew_backend = "web_sys"))]
+use std::convert::TryFrom;
 use std::fmt;
 use std::rc::Rc;
+#[cfg(yew_backend = "web_sys")]
+use wasm_bindgen::JsValue;
 
 /// This type indicates that component should be rendered again.
 pub type ShouldRender = bool;
@@ -54,7 +60,7 @@ pub trait Component: Sized + 'static {
     /// }
     ///# }}
     /// ```
-    type Properties: Properties;
+    type Properties: Properties + 'static;
 
     /// Components are created with their properties as well as a `ComponentLink` which
     /// can be used to send messages and create callbacks for triggering updates.
@@ -95,6 +101,15 @@ pub trait Component: Sized + 'static {
     /// documentation](https://yew.rs/docs/concepts/html).
     fn view(&self) -> Html;
 
+    /// Fallible alternative to `view`, which is what is called to render the component.
+    ///
+    /// Components which can fail to render, e.g. because their data is invalid, implement this
+    /// method and return an empty `html! {}` from `view`. An error is passed to the closest
+    /// `ErrorBoundary`, which renders its fallback in place of the failed component.
+    fn try_view(&self) -> Result<Html, ComponentError> {
+        Ok(self.view())
+    }
+
     /// The `rendered` method is called after each time a Component is rendered but
     /// before the browser updates the page.
     /// ## Examples
@@ -348,8 +363,8 @@ impl<T> IntoIterator for ChildrenRenderer<T> {
 /// ```
 /// #[cfg(feature = "std_web")]
 /// use stdweb::web::{html_element::InputElement, IHtmlElement};
-/// #[cfg(feature = "web_sys")]
-/// use web_sys::HtmlInputElement as InputElement;
+/// #[cfg(not(feature = "std_web"))]
+/// use yew::backend::InputElement;
 ///# use yew::prelude::*;
 ///
 /// pub struct Input {
@@ -410,17 +425,20 @@ impl NodeRef {
         inner.node.clone().or_else(|| inner.link.as_ref()?.get())
     }
 
-    /// Try converting the node reference into another form
-    // #[cfg(feature = "std_web")] INTO: TryFrom<Node>,
-    // #[cfg(feature = "web_sys")] INTO: AsRef<Node> + From<JsValue>,
-
-    pub fn cast<INTO: std::convert::TryFrom<Node>>(&self) -> Option<INTO> {
-        let node = self.get();
-        todo!();
-        // cast_node_ref::<INTO>(self)
-        // Renderer::cast_node_ref::<INTO>(self)
-        // Renderer::cast_node_ref(self)
-        None
+    cfg_if! {
+        if #[cfg(yew_backend = "web_sys")] {
+            /// Try converting the node reference into another form
+            pub fn cast<INTO: AsRef<Node> + From<JsValue>>(&self) -> Option<INTO> {
+                let node = self.get();
+                node.map(Into::into).map(INTO::from)
+            }
+        } else {
+            /// Try converting the node reference into another form
+            pub fn cast<INTO: TryFrom<Node>>(&self) -> Option<INTO> {
+                let node = self.get();
+                node.and_then(|node| INTO::try_from(node).ok())
+            }
+        }
     }
 
     /// Wrap an existing `Node` in a `NodeRef`
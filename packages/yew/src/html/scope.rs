@@ -1,12 +1,14 @@
-use super::{Callback, Component, NodeRef};
-use crate::backend::{Renderer, Element};
-use crate::scheduler::{scheduler, ComponentRunnableType, Runnable, Shared};
+use super::{report, Callback, Component, ComponentError, NodeRef};
+use crate::backend::{Element, Node};
+use crate::hydration;
+use crate::request::RequestContext;
+use crate::scheduler::{scheduler, ComponentRunnableType, Runnable, Scheduler, Shared};
 use crate::virtual_dom::{vdiff::VDiff, VNode};
-use cfg_if::cfg_if;
-use std::any::{Any, TypeId};
+use std::any::{self, Any, TypeId};
 use std::cell::{Ref, RefCell};
 use std::fmt;
 use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
 
 pub use anyscope::AnyScope;
@@ -34,16 +36,20 @@ mod anyscope {
     #[derive(Debug, Clone)]
     pub struct AnyScope {
         pub(crate) type_id: TypeId,
+        pub(crate) type_name: &'static str,
         pub(crate) parent: Option<Rc<AnyScope>>,
         pub(crate) state: Rc<dyn Any>,
+        pub(crate) scheduler: Rc<Scheduler>,
     }
 
     impl<COMP: Component> From<Scope<COMP>> for AnyScope {
         fn from(scope: Scope<COMP>) -> Self {
             AnyScope {
                 type_id: TypeId::of::<COMP>(),
+                type_name: any::type_name::<COMP>(),
                 parent: scope.parent,
                 state: Rc::new(scope.state),
+                scheduler: scope.scheduler,
             }
         }
     }
@@ -59,6 +65,16 @@ mod anyscope {
             &self.type_id
         }
 
+        /// Returns the type name of the linked component
+        pub fn get_type_name(&self) -> &'static str {
+            self.type_name
+        }
+
+        /// Returns the request the component is rendered for on the server
+        pub fn request_context(&self) -> Option<Rc<RequestContext>> {
+            self.scheduler.request()
+        }
+
         /// Attempts to downcast into a typed scope
         pub fn downcast<COMP: Component>(self) -> Scope<COMP> {
             Scope {
@@ -68,6 +84,7 @@ mod anyscope {
                     .downcast_ref::<Shared<Option<ComponentState<COMP>>>>()
                     .expect("unexpected component type")
                     .clone(),
+                scheduler: self.scheduler,
             }
         }
     }
@@ -75,6 +92,18 @@ mod anyscope {
 
 mod scoped {
     use super::*;
+    use cfg_if::cfg_if;
+    #[cfg(any(feature = "web_sys", feature = "static_render"))]
+    use std::future::Future;
+
+    cfg_if! {
+        if #[cfg(yew_backend = "web_sys")] {
+            use wasm_bindgen_futures::spawn_local;
+        } else if #[cfg(yew_backend = "static_render")] {
+            use crate::smr::spawn_local;
+        }
+    }
+
     pub(crate) trait Scoped {
         fn to_any(&self) -> AnyScope;
         fn root_vnode(&self) -> Option<Ref<'_, VNode>>;
@@ -92,7 +121,7 @@ mod scoped {
                 state
                     .last_root
                     .as_ref()
-                    .or_else(|| state.placeholder.as_ref())
+                    .or(state.placeholder.as_ref())
             })?;
 
             Some(Ref::map(state_ref, |state_ref| {
@@ -100,7 +129,7 @@ mod scoped {
                 state
                     .last_root
                     .as_ref()
-                    .or_else(|| state.placeholder.as_ref())
+                    .or(state.placeholder.as_ref())
                     .unwrap()
             }))
         }
@@ -109,7 +138,8 @@ mod scoped {
         fn destroy(&mut self) {
             let state = self.state.clone();
             let destroy = super::messaging::DestroyComponent { state };
-            scheduler().push_comp(ComponentRunnableType::Destroy, Box::new(destroy));
+            self.scheduler
+                .push_comp(ComponentRunnableType::Destroy, Box::new(destroy));
         }
     }
 
@@ -117,6 +147,8 @@ mod scoped {
     pub struct Scope<COMP: Component> {
         pub(crate) parent: Option<Rc<AnyScope>>,
         pub(crate) state: Shared<Option<ComponentState<COMP>>>,
+        /// The scheduler the component's lifecycle runs on, shared with its descendants
+        pub(crate) scheduler: Rc<Scheduler>,
     }
 
     impl<COMP: Component> fmt::Debug for Scope<COMP> {
@@ -130,6 +162,7 @@ mod scoped {
             Scope {
                 parent: self.parent.clone(),
                 state: self.state.clone(),
+                scheduler: self.scheduler.clone(),
             }
         }
     }
@@ -150,10 +183,24 @@ mod scoped {
             })
         }
 
+        /// Returns the request the component is rendered for on the server, `None` on the
+        /// client.
+        pub fn request_context(&self) -> Option<Rc<RequestContext>> {
+            self.scheduler.request()
+        }
+
         pub(crate) fn new(parent: Option<AnyScope>) -> Self {
+            let scheduler = match &parent {
+                Some(parent) => parent.scheduler.clone(),
+                None => scheduler(),
+            };
             let parent = parent.map(Rc::new);
             let state = Rc::new(RefCell::new(None));
-            Scope { parent, state }
+            Scope {
+                parent,
+                state,
+                scheduler,
+            }
         }
 
         /// Mounts a component with `props` to the specified `element` in the DOM.
@@ -165,7 +212,7 @@ mod scoped {
             node_ref: NodeRef,
             props: COMP::Properties,
         ) -> Scope<COMP> {
-            let scheduler = scheduler();
+            let scheduler = &self.scheduler;
             // Hold scheduler lock so that `create` doesn't run until `update` is scheduled
             let lock = scheduler.lock();
             scheduler.push_comp(
@@ -186,13 +233,37 @@ mod scoped {
             self
         }
 
+        /// Mounts a component with `props` over the existing DOM nodes of `parent`, starting at
+        /// `cursor`.
+        ///
+        /// Unlike `mount_in_place`, the component is created and rendered right away so that
+        /// `cursor` can be moved past the nodes it adopted before its siblings are hydrated.
+        pub(crate) fn hydrate_in_place(
+            self,
+            parent: Element,
+            next_sibling: NodeRef,
+            node_ref: NodeRef,
+            props: COMP::Properties,
+            cursor: &mut Option<Node>,
+        ) -> Scope<COMP> {
+            let scheduler = self.scheduler.clone();
+            // Hold scheduler lock so that messages sent during `create` are handled after the
+            // first render
+            let lock = scheduler.lock();
+            messaging::hydrate(&self, parent, next_sibling, node_ref, props, cursor);
+            drop(lock);
+            scheduler.start();
+            self
+        }
+
         /// Schedules a task to send an update to a component
         pub(crate) fn update(&self, update: ComponentUpdate<COMP>) {
             let update = messaging::UpdateComponent {
                 state: self.state.clone(),
                 update,
             };
-            scheduler().push_comp(ComponentRunnableType::Update, Box::new(update));
+            self.scheduler
+                .push_comp(ComponentRunnableType::Update, Box::new(update));
         }
 
         /// Send a message to the component.
@@ -317,6 +388,54 @@ mod scoped {
             };
             Callback::once(closure)
         }
+
+        /// Spawns `future` and sends its output as a message to the linked component once it
+        /// resolves.
+        ///
+        /// When rendering on the server, `render_to_string_async` waits for these futures
+        /// before the component tree is serialized.
+        #[cfg(any(feature = "web_sys", feature = "static_render"))]
+        pub fn send_future<F, M>(&self, future: F)
+        where
+            M: Into<COMP::Message>,
+            F: Future<Output = M> + 'static,
+        {
+            let scope = self.clone();
+            spawn_local(async move {
+                let message = future.await;
+                scope.send_message(message);
+            });
+        }
+
+        /// Spawns `future` and sends the batch of messages it resolves to to the linked
+        /// component.
+        #[cfg(any(feature = "web_sys", feature = "static_render"))]
+        pub fn send_future_batch<F>(&self, future: F)
+        where
+            F: Future<Output = Vec<COMP::Message>> + 'static,
+        {
+            let scope = self.clone();
+            spawn_local(async move {
+                let messages = future.await;
+                scope.send_message_batch(messages);
+            });
+        }
+
+        /// Creates a `Callback` which spawns the future returned by `function` and sends its
+        /// output as a message to the linked component once it resolves.
+        #[cfg(any(feature = "web_sys", feature = "static_render"))]
+        pub fn callback_future<FN, FU, IN, M>(&self, function: FN) -> Callback<IN>
+        where
+            M: Into<COMP::Message>,
+            FU: Future<Output = M> + 'static,
+            FN: Fn(IN) -> FU + 'static,
+        {
+            let scope = self.clone();
+            let closure = move |input| {
+                scope.send_future(function(input));
+            };
+            closure.into()
+        }
     }
 }
 
@@ -390,6 +509,78 @@ mod messaging {
         }
     }
 
+    /// Creates the `ComponentState` and hydrates the first render of the component against
+    /// the DOM at `cursor` without going through the scheduler. The `rendered()` method is
+    /// scheduled as usual.
+    pub(super) fn hydrate<COMP>(
+        scope: &Scope<COMP>,
+        parent: Element,
+        next_sibling: NodeRef,
+        node_ref: NodeRef,
+        props: COMP::Properties,
+        cursor: &mut Option<Node>,
+    ) where
+        COMP: Component,
+    {
+        let mut current_state = scope.state.borrow_mut();
+        if current_state.is_none() {
+            let state = hydration::create(cursor.as_ref(), || {
+                guard(scope, || {
+                    Ok(ComponentState::new(
+                        parent,
+                        next_sibling,
+                        None,
+                        node_ref,
+                        scope.clone(),
+                        props,
+                    ))
+                })
+            });
+            match state {
+                Some(state) => *current_state = Some(state),
+                None => return,
+            }
+        }
+        let state = current_state.as_mut().unwrap();
+
+        let mut root = match guard(scope, || state.component.try_view()) {
+            Some(root) => root,
+            None => return,
+        };
+        let parent_scope = state.scope.clone().into();
+        let (mut node, rerender) = hydration::component(|| {
+            root.hydrate(
+                &parent_scope,
+                &state.parent,
+                state.next_sibling.clone(),
+                cursor,
+            )
+        });
+        if rerender {
+            // The markup didn't match, so the nodes which were adopted are dropped and the
+            // component is rendered in their place from scratch
+            root.detach(&state.parent);
+            root = match guard(scope, || state.component.try_view()) {
+                Some(root) => root,
+                None => return,
+            };
+            let next_sibling = NodeRef::default();
+            next_sibling.set(cursor.clone());
+            node = root.apply(&parent_scope, &state.parent, next_sibling, None);
+        }
+        state.node_ref.link(node);
+        state.last_root = Some(root);
+        drop(current_state);
+
+        scope.scheduler.push_comp(
+            ComponentRunnableType::Rendered,
+            Box::new(RenderedComponent {
+                state: scope.state.clone(),
+                first_render: true,
+            }),
+        );
+    }
+
     /// A `Runnable` task which creates the `ComponentState` (if there is
     /// none) and invokes the `create()` method on a `Component` to create
     /// it.
@@ -397,13 +588,13 @@ mod messaging {
     where
         COMP: Component,
     {
-        state: Shared<Option<ComponentState<COMP>>>,
-        parent: Element,
-        next_sibling: NodeRef,
-        placeholder: Option<VNode>,
-        node_ref: NodeRef,
-        scope: Scope<COMP>,
-        props: COMP::Properties,
+        pub(super) state: Shared<Option<ComponentState<COMP>>>,
+        pub(super) parent: Element,
+        pub(super) next_sibling: NodeRef,
+        pub(super) placeholder: Option<VNode>,
+        pub(super) node_ref: NodeRef,
+        pub(super) scope: Scope<COMP>,
+        pub(super) props: COMP::Properties,
     }
 
     impl<COMP> Runnable for CreateComponent<COMP>
@@ -411,16 +602,27 @@ mod messaging {
         COMP: Component,
     {
         fn run(self: Box<Self>) {
-            let mut current_state = self.state.borrow_mut();
+            let CreateComponent {
+                state,
+                parent,
+                next_sibling,
+                placeholder,
+                node_ref,
+                scope,
+                props,
+            } = *self;
+            let mut current_state = state.borrow_mut();
             if current_state.is_none() {
-                *current_state = Some(ComponentState::new(
-                    self.parent,
-                    self.next_sibling,
-                    self.placeholder,
-                    self.node_ref,
-                    self.scope,
-                    self.props,
-                ));
+                *current_state = guard(&scope.clone(), || {
+                    Ok(ComponentState::new(
+                        parent,
+                        next_sibling,
+                        placeholder,
+                        node_ref,
+                        scope,
+                        props,
+                    ))
+                });
             }
         }
     }
@@ -430,8 +632,8 @@ mod messaging {
     where
         COMP: Component,
     {
-        state: Shared<Option<ComponentState<COMP>>>,
-        update: ComponentUpdate<COMP>,
+        pub(super) state: Shared<Option<ComponentState<COMP>>>,
+        pub(super) update: ComponentUpdate<COMP>,
     }
 
     impl<COMP> Runnable for UpdateComponent<COMP>
@@ -440,7 +642,7 @@ mod messaging {
     {
         fn run(self: Box<Self>) {
             let state_clone = self.state.clone();
-            if let Some(mut state) = state_clone.borrow_mut().as_mut() {
+            if let Some(state) = state_clone.borrow_mut().as_mut() {
                 if state.new_root.is_some() {
                     state.pending_updates.push(self);
                     return;
@@ -448,24 +650,33 @@ mod messaging {
 
                 let first_update = matches!(self.update, ComponentUpdate::First);
 
-                let should_update = match self.update {
-                    ComponentUpdate::First => true,
-                    ComponentUpdate::Message(message) => state.component.update(message),
-                    ComponentUpdate::MessageBatch(messages) => messages
-                        .into_iter()
-                        .fold(false, |acc, msg| state.component.update(msg) || acc),
-                    ComponentUpdate::Properties(props, node_ref, next_sibling) => {
-                        // When components are updated, a new node ref could have been passed in
-                        state.node_ref = node_ref;
-                        // When components are updated, their siblings were likely also updated
-                        state.next_sibling = next_sibling;
-                        state.component.change(props)
-                    }
-                };
-
-                if should_update {
-                    state.new_root = Some(state.component.view());
-                    scheduler().push_comp(
+                let scope = state.scope.clone();
+                let update = self.update;
+                let should_update = guard(&scope, || {
+                    Ok(match update {
+                        ComponentUpdate::First => true,
+                        ComponentUpdate::Message(message) => state.component.update(message),
+                        // Every message must be processed, so `any` can't be used here.
+                        #[allow(clippy::unnecessary_fold)]
+                        ComponentUpdate::MessageBatch(messages) => messages
+                            .into_iter()
+                            .fold(false, |acc, msg| state.component.update(msg) || acc),
+                        ComponentUpdate::Properties(props, node_ref, next_sibling) => {
+                            // When components are updated, a new node ref could have been passed in
+                            state.node_ref = node_ref;
+                            // When components are updated, their siblings were likely also updated
+                            state.next_sibling = next_sibling;
+                            state.component.change(props)
+                        }
+                    })
+                });
+
+                if should_update == Some(true) {
+                    state.new_root = match guard(&scope, || state.component.try_view()) {
+                        Some(root) => Some(root),
+                        None => return,
+                    };
+                    state.scope.scheduler.push_comp(
                         ComponentRunnableType::Render,
                         Box::new(RenderComponent {
                             state: self.state,
@@ -482,8 +693,8 @@ mod messaging {
     where
         COMP: Component,
     {
-        state: Shared<Option<ComponentState<COMP>>>,
-        first_render: bool,
+        pub(super) state: Shared<Option<ComponentState<COMP>>>,
+        pub(super) first_render: bool,
     }
 
     impl<COMP> Runnable for RenderComponent<COMP>
@@ -492,7 +703,7 @@ mod messaging {
     {
         fn run(self: Box<Self>) {
             let state_clone = self.state.clone();
-            if let Some(mut state) = self.state.borrow_mut().as_mut() {
+            if let Some(state) = self.state.borrow_mut().as_mut() {
                 // Skip render if we haven't seen the "first render" yet
                 if !self.first_render && state.last_root.is_none() {
                     return;
@@ -506,7 +717,7 @@ mod messaging {
                         new_root.apply(&parent_scope, &state.parent, next_sibling, last_root);
                     state.node_ref.link(node);
                     state.last_root = Some(new_root);
-                    scheduler().push_comp(
+                    state.scope.scheduler.push_comp(
                         ComponentRunnableType::Rendered,
                         Box::new(RenderedComponent {
                             state: state_clone,
@@ -523,8 +734,8 @@ mod messaging {
     where
         COMP: Component,
     {
-        state: Shared<Option<ComponentState<COMP>>>,
-        first_render: bool,
+        pub(super) state: Shared<Option<ComponentState<COMP>>>,
+        pub(super) first_render: bool,
     }
 
     impl<COMP> Runnable for RenderedComponent<COMP>
@@ -532,16 +743,20 @@ mod messaging {
         COMP: Component,
     {
         fn run(self: Box<Self>) {
-            if let Some(mut state) = self.state.borrow_mut().as_mut() {
+            if let Some(state) = self.state.borrow_mut().as_mut() {
                 // Don't call rendered if we haven't seen the "first render" yet
                 if !self.first_render && !state.has_rendered {
                     return;
                 }
 
                 state.has_rendered = true;
-                state.component.rendered(self.first_render);
+                let scope = state.scope.clone();
+                guard(&scope, || {
+                    state.component.rendered(self.first_render);
+                    Ok(())
+                });
                 if !state.pending_updates.is_empty() {
-                    scheduler().push_comp_update_batch(
+                    state.scope.scheduler.push_comp_update_batch(
                         state
                             .pending_updates
                             .drain(..)
@@ -552,12 +767,37 @@ mod messaging {
         }
     }
 
+    /// Runs a lifecycle method of the component of `scope`.
+    ///
+    /// If the method fails, the error is passed to the closest `ErrorBoundary` and `None` is
+    /// returned. Without a boundary, errors are logged and panics are resumed.
+    fn guard<COMP, R>(
+        scope: &Scope<COMP>,
+        f: impl FnOnce() -> Result<R, ComponentError>,
+    ) -> Option<R>
+    where
+        COMP: Component,
+    {
+        let (error, payload) = match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(Ok(value)) => return Some(value),
+            Ok(Err(error)) => (error, None),
+            Err(payload) => (ComponentError::from_panic(&*payload), Some(payload)),
+        };
+        if let Err(error) = report(scope.get_parent(), error.of::<COMP>()) {
+            match payload {
+                Some(payload) => panic::resume_unwind(payload),
+                None => log::error!("{}", error),
+            }
+        }
+        None
+    }
+
     /// A `Runnable` task which calls the `destroy()` method on a `Component`.
     pub(crate) struct DestroyComponent<COMP>
     where
         COMP: Component,
     {
-        state: Shared<Option<ComponentState<COMP>>>,
+        pub(super) state: Shared<Option<ComponentState<COMP>>>,
     }
 
     impl<COMP> Runnable for DestroyComponent<COMP>
@@ -0,0 +1,767 @@
+//! This module contains the detection of hydration mismatches, the places where the markup
+//! rendered on the server differs from the first render on the client, e.g. because a component
+//! renders the current time, a random id or something that depends on a `cfg`.
+//!
+//! Every mismatch is described by a `HydrationMismatch`, which names the components and the
+//! elements leading to the node. In debug builds mismatches are logged as warnings. What happens
+//! to the markup is decided by the `MismatchPolicy` of the app, see `App::with_mismatch_policy`.
+//!
+//! The HTML parser of the browser merges adjacent text nodes and drops empty ones, which would
+//! leave hydration unable to line the markup up with the virtual DOM. Therefore the static
+//! renderer separates adjacent text nodes with a `<!--,-->` comment and writes a `<!---->`
+//! comment in place of empty text and empty lists. These markers are removed when hydrating.
+
+use crate::backend::{DomBackend, Element, Node, Renderer, TextNode};
+use crate::html::AnyScope;
+use crate::utils;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+/// The data of the comment which separates adjacent text nodes in rendered markup.
+pub(crate) const TEXT_SEPARATOR: &str = ",";
+
+/// The data of the comment which stands in for empty text and empty lists in rendered markup.
+pub(crate) const PLACEHOLDER: &str = "";
+
+/// The number of characters of a text node which are shown in a `HydrationMismatch`.
+const TEXT_PREVIEW: usize = 40;
+
+thread_local! {
+    /// The hydration which is currently in progress.
+    static CURRENT: RefCell<Option<Rc<Hydration>>> = const { RefCell::new(None) };
+}
+
+/// What happens to markup which doesn't match the first render on the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MismatchPolicy {
+    /// Mismatched nodes are patched or replaced one by one and hydration carries on. This is
+    /// the default.
+    #[default]
+    Patch,
+    /// Components whose markup doesn't match are rendered from scratch, replacing all nodes
+    /// they adopted. Their child components are created anew as well.
+    Rerender,
+    /// Hydration panics at the first mismatch.
+    Panic,
+}
+
+/// A place where the markup differs from the first render on the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydrationMismatch {
+    components: Vec<&'static str>,
+    dom_path: String,
+    expected: String,
+    found: String,
+}
+
+impl HydrationMismatch {
+    /// Returns the type names of the components from the root to the one which rendered the
+    /// node, without their module paths.
+    pub fn components(&self) -> &[&'static str] {
+        &self.components
+    }
+
+    /// Returns the path to the node from the element the app was hydrated in, e.g.
+    /// `div#app > main[1] > p[0]`. Each element is followed by its position among the nodes of
+    /// its parent. If no node was found, the path leads to the parent.
+    pub fn dom_path(&self) -> &str {
+        &self.dom_path
+    }
+
+    /// Returns what the client rendered, e.g. `<p>` or `text "Hello"`.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Returns what the markup contained instead.
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+}
+
+impl fmt::Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hydration mismatch in `{}` at `{}`: expected {}, found {}",
+            self.components.join(" > "),
+            self.dom_path,
+            self.expected,
+            self.found
+        )
+    }
+}
+
+/// The state of the hydration which is in progress.
+struct Hydration {
+    policy: MismatchPolicy,
+    /// The elements from the root to the one whose children are being hydrated
+    elements: RefCell<Vec<Element>>,
+    /// Whether the markup of the component which is being hydrated didn't match
+    mismatched: Cell<bool>,
+    /// The first node of the markup of the component which is being created
+    adopting: RefCell<Option<Node>>,
+}
+
+fn current() -> Option<Rc<Hydration>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Hydrates the children of `root` in `f`, handling mismatches according to `policy`.
+pub(crate) fn run<R>(policy: MismatchPolicy, root: &Element, f: impl FnOnce() -> R) -> R {
+    let hydration = Hydration {
+        policy,
+        elements: RefCell::new(vec![root.clone()]),
+        mismatched: Cell::new(false),
+        adopting: RefCell::new(None),
+    };
+    utils::scoped(&CURRENT, Rc::new(hydration), f)
+}
+
+/// Hydrates the children of `element` in `f`.
+pub(crate) fn within<R>(element: &Element, f: impl FnOnce() -> R) -> R {
+    struct Leave(Option<Rc<Hydration>>);
+
+    impl Drop for Leave {
+        fn drop(&mut self) {
+            if let Some(hydration) = &self.0 {
+                hydration.elements.borrow_mut().pop();
+            }
+        }
+    }
+
+    let hydration = current();
+    if let Some(hydration) = &hydration {
+        hydration.elements.borrow_mut().push(element.clone());
+    }
+    let _leave = Leave(hydration);
+    f()
+}
+
+/// Hydrates the root of a component in `f`. Also returns whether the component has to be
+/// rendered from scratch because its markup didn't match.
+pub(crate) fn component<R>(f: impl FnOnce() -> R) -> (R, bool) {
+    let hydration = match current() {
+        Some(hydration) => hydration,
+        None => return (f(), false),
+    };
+    let outer = hydration.mismatched.replace(false);
+    let result = f();
+    let mismatched = hydration.mismatched.replace(outer);
+    (
+        result,
+        mismatched && hydration.policy == MismatchPolicy::Rerender,
+    )
+}
+
+/// Creates a component in `f` which is going to adopt the markup at `cursor`.
+pub(crate) fn create<R>(cursor: Option<&Node>, f: impl FnOnce() -> R) -> R {
+    let hydration = match current() {
+        Some(hydration) => hydration,
+        None => return f(),
+    };
+    let outer = hydration.adopting.replace(cursor.cloned());
+    let result = f();
+    hydration.adopting.replace(outer);
+    result
+}
+
+/// Returns the node at which the markup of the component which is being created starts, if it
+/// is being hydrated.
+pub(crate) fn adopting() -> Option<Node> {
+    current().and_then(|hydration| hydration.adopting.borrow().clone())
+}
+
+/// Removes the comment at `cursor` which separates the following text from the text before it.
+pub(crate) fn skip_separator(parent: &Element, cursor: &mut Option<Node>) {
+    if let Some(separator) = cursor
+        .clone()
+        .filter(|node| is_marker(node, TEXT_SEPARATOR))
+    {
+        *cursor = Renderer::node_next_sibling(&separator);
+        Renderer::element_remove_child(parent, &separator).expect("can't remove a child");
+    }
+}
+
+/// Replaces the placeholder comment at `cursor` with an empty text node, which is returned,
+/// and moves the cursor past it.
+pub(crate) fn claim_placeholder(parent: &Element, cursor: &mut Option<Node>) -> Option<TextNode> {
+    let placeholder = cursor.clone().filter(|node| is_marker(node, PLACEHOLDER))?;
+    *cursor = Renderer::node_next_sibling(&placeholder);
+    let text = Renderer::create_text_node("");
+    Renderer::element_insert_before(parent, &Renderer::text_as_node(&text), Some(&placeholder))
+        .expect("can't insert a placeholder");
+    Renderer::element_remove_child(parent, &placeholder).expect("can't remove a child");
+    Some(text)
+}
+
+/// Replaces the node at `cursor`, which the server rendered from `node`, with `node` itself and
+/// moves the cursor past it. The nodes of a `VRef` are created on the client, so the server's
+/// copy can't be adopted.
+pub(crate) fn replace(
+    scope: Option<&AnyScope>,
+    parent: &Element,
+    cursor: &mut Option<Node>,
+    node: &Node,
+) {
+    let existing = cursor.take();
+    *cursor = existing.as_ref().and_then(Renderer::node_next_sibling);
+    let empty_text =
+        Renderer::node_as_text(node).is_some_and(|text| Renderer::text_data(&text).is_empty());
+    let matches = existing.as_ref().is_some_and(|existing| {
+        same_kind(existing, node) || (empty_text && is_marker(existing, PLACEHOLDER))
+    });
+    if !matches {
+        report(scope, parent, existing.as_ref(), describe_node(Some(node)));
+    }
+    Renderer::element_insert_before(parent, node, existing.as_ref()).expect("can't insert a node");
+    if let Some(existing) = existing {
+        Renderer::element_remove_child(parent, &existing).expect("can't remove a child");
+    }
+}
+
+/// Returns `true` if both nodes are text, comments with the same data or elements with the
+/// same name.
+pub(crate) fn same_kind(a: &Node, b: &Node) -> bool {
+    match (Renderer::node_as_element(a), Renderer::node_as_element(b)) {
+        (Some(a), Some(b)) => {
+            Renderer::element_local_name(&a) == Renderer::element_local_name(&b)
+                && Renderer::element_namespace_uri(&a) == Renderer::element_namespace_uri(&b)
+        }
+        (None, None) => match (
+            Renderer::node_comment_data(a),
+            Renderer::node_comment_data(b),
+        ) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                Renderer::node_as_text(a).is_some() && Renderer::node_as_text(b).is_some()
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_marker(node: &Node, data: &str) -> bool {
+    Renderer::node_comment_data(node).as_deref() == Some(data)
+}
+
+/// Reports that the node at `found` in `parent`, rendered by the component of `scope`, doesn't
+/// match what the client `expected`. `None` stands for the end of the children of `parent`.
+pub(crate) fn report(
+    scope: Option<&AnyScope>,
+    parent: &Element,
+    found: Option<&Node>,
+    expected: String,
+) {
+    mismatch(scope, parent, found, expected, || describe_node(found));
+}
+
+/// Reports that the attribute `name` of `element` isn't set to the `expected` value, see
+/// `report`.
+pub(crate) fn report_attribute(
+    scope: Option<&AnyScope>,
+    parent: &Element,
+    element: &Element,
+    name: &str,
+    expected: Option<&str>,
+) {
+    let node = Renderer::element_as_node(element);
+    mismatch(
+        scope,
+        parent,
+        Some(&node),
+        describe_attribute(name, expected),
+        || {
+            describe_attribute(
+                name,
+                Renderer::element_get_attribute(element, name).as_deref(),
+            )
+        },
+    );
+}
+
+/// Marks the component which is being hydrated as mismatched and, in debug builds or if the
+/// policy is to panic, describes the mismatch.
+fn mismatch(
+    scope: Option<&AnyScope>,
+    parent: &Element,
+    node: Option<&Node>,
+    expected: String,
+    found: impl FnOnce() -> String,
+) {
+    let hydration = current();
+    let policy = hydration
+        .as_ref()
+        .map_or_else(MismatchPolicy::default, |hydration| hydration.policy);
+    if let Some(hydration) = &hydration {
+        hydration.mismatched.set(true);
+    }
+    if !cfg!(debug_assertions) && policy != MismatchPolicy::Panic {
+        return;
+    }
+
+    let mut elements = hydration
+        .map(|hydration| hydration.elements.borrow().clone())
+        .unwrap_or_default();
+    if elements.last() != Some(parent) {
+        elements = vec![parent.clone()];
+    }
+    let mismatch = HydrationMismatch {
+        components: component_path(scope),
+        dom_path: dom_path(&elements, node),
+        expected,
+        found: found(),
+    };
+    if policy == MismatchPolicy::Panic {
+        panic!("{}", mismatch);
+    }
+    log::warn!("{}", mismatch);
+}
+
+/// Describes a text node with `text`.
+pub(crate) fn describe_text(text: &str) -> String {
+    let preview: String = text.chars().take(TEXT_PREVIEW).collect();
+    if preview.len() < text.len() {
+        format!("text {:?}…", preview)
+    } else {
+        format!("text {:?}", preview)
+    }
+}
+
+/// Describes an element named `name`.
+pub(crate) fn describe_element(name: &str) -> String {
+    format!("<{}>", name)
+}
+
+fn describe_attribute(name: &str, value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("attribute {}={:?}", name, value),
+        None => format!("no attribute {}", name),
+    }
+}
+
+/// Describes `node`, `None` being no node at all.
+pub(crate) fn describe_node(node: Option<&Node>) -> String {
+    let node = match node {
+        Some(node) => node,
+        None => return "nothing".to_owned(),
+    };
+    if let Some(element) = Renderer::node_as_element(node) {
+        describe_element(&Renderer::element_local_name(&element))
+    } else if let Some(text) = Renderer::node_as_text(node) {
+        describe_text(&Renderer::text_data(&text))
+    } else if let Some(data) = Renderer::node_comment_data(node) {
+        format!("comment {:?}", data)
+    } else {
+        "another node".to_owned()
+    }
+}
+
+/// Returns the type names of the components from the root to `scope`.
+fn component_path(scope: Option<&AnyScope>) -> Vec<&'static str> {
+    let mut components = Vec::new();
+    let mut scope = scope;
+    while let Some(current) = scope {
+        components.push(short_type_name(current.get_type_name()));
+        scope = current.get_parent();
+    }
+    components.reverse();
+    components
+}
+
+/// Strips the module path from a type name, e.g. `app::Page` becomes `Page`. The module paths
+/// of generic arguments are kept.
+fn short_type_name(name: &'static str) -> &'static str {
+    let end = name.find('<').unwrap_or(name.len());
+    let start = name[..end].rfind("::").map_or(0, |index| index + 2);
+    &name[start..]
+}
+
+/// Describes the path from the first of `elements` to `node`, which is a child of the last one.
+fn dom_path(elements: &[Element], node: Option<&Node>) -> String {
+    let mut segments = Vec::new();
+    let mut parent: Option<&Element> = None;
+    for element in elements {
+        let name = Renderer::element_local_name(element);
+        segments.push(match parent {
+            Some(parent) => {
+                let index = index_of(parent, &Renderer::element_as_node(element));
+                format!("{}[{}]", name, index)
+            }
+            None => match Renderer::element_get_attribute(element, "id") {
+                Some(id) => format!("{}#{}", name, id),
+                None => name,
+            },
+        });
+        parent = Some(element);
+    }
+    if let (Some(parent), Some(node)) = (parent, node) {
+        let name = match Renderer::node_as_element(node) {
+            Some(element) => Renderer::element_local_name(&element),
+            None if Renderer::node_as_text(node).is_some() => "#text".to_owned(),
+            None if Renderer::node_comment_data(node).is_some() => "#comment".to_owned(),
+            None => "#node".to_owned(),
+        };
+        segments.push(format!("{}[{}]", name, index_of(parent, node)));
+    }
+    segments.join(" > ")
+}
+
+/// Returns the position of `node` among the child nodes of `parent`.
+fn index_of(parent: &Element, node: &Node) -> usize {
+    let mut index = 0;
+    let mut child = Renderer::element_first_child(parent);
+    while let Some(current) = child {
+        if current == *node {
+            break;
+        }
+        index += 1;
+        child = Renderer::node_next_sibling(&current);
+    }
+    index
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::{Component, ComponentLink, Html, Properties, ShouldRender};
+    use crate::{html, utils::document, App};
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Debug, Default, PartialEq, Properties)]
+    struct LabelProps {
+        #[prop_or_default]
+        label: String,
+    }
+
+    struct Widget {
+        props: LabelProps,
+    }
+
+    impl Component for Widget {
+        type Message = ();
+        type Properties = LabelProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Widget { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p data-label=self.props.label.clone()>{ &self.props.label }</p> }
+        }
+    }
+
+    struct Page {
+        props: LabelProps,
+    }
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = LabelProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <main>
+                    <h1>{ "Title" }</h1>
+                    <Widget label=self.props.label.clone() />
+                </main>
+            }
+        }
+    }
+
+    fn label(label: &str) -> LabelProps {
+        LabelProps {
+            label: label.to_owned(),
+        }
+    }
+
+    /// Returns an element holding the markup of `Page` rendered with `server`.
+    fn server_markup(server: &str) -> Element {
+        let markup = crate::render_to_string::<Page>(label(server)).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_attribute("id", "app").unwrap();
+        root.set_inner_html(markup.as_str());
+        root
+    }
+
+    fn query(root: &Element, selector: &str) -> Element {
+        root.query_selector(selector).unwrap().unwrap()
+    }
+
+    fn panic_message(root: Element, props: LabelProps) -> String {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            App::<Page>::new()
+                .with_mismatch_policy(MismatchPolicy::Panic)
+                .hydrate_with_props(root, props)
+        }));
+        let payload = result.expect_err("hydration didn't panic");
+        payload.downcast_ref::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn mismatches_name_the_components_and_the_path() {
+        assert_eq!(
+            panic_message(server_markup("server"), label("client")),
+            "hydration mismatch in `Page > Widget` at `div#app > main[0] > p[1]`: \
+             expected attribute data-label=\"client\", found attribute data-label=\"server\""
+        );
+
+        let root = server_markup("server");
+        query(&root, "main")
+            .remove_child(&query(&root, "p"))
+            .unwrap();
+        assert_eq!(
+            panic_message(root, label("server")),
+            "hydration mismatch in `Page > Widget` at `div#app > main[0]`: \
+             expected <p>, found nothing"
+        );
+    }
+
+    #[test]
+    fn matching_markup_is_no_mismatch() {
+        let root = server_markup("same");
+        App::<Page>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate_with_props(root.clone(), label("same"));
+        assert_eq!(root.text_content().unwrap(), "Titlesame");
+    }
+
+    #[test]
+    fn mismatches_are_patched_by_default() {
+        let root = server_markup("server");
+        let (h1, p) = (query(&root, "h1"), query(&root, "p"));
+        App::<Page>::new().hydrate_with_props(root.clone(), label("client"));
+        assert_eq!(query(&root, "h1"), h1);
+        assert_eq!(query(&root, "p"), p);
+        assert_eq!(p.get_attribute("data-label").unwrap(), "client");
+        assert_eq!(root.text_content().unwrap(), "Titleclient");
+    }
+
+    #[test]
+    fn mismatched_components_can_be_rendered_from_scratch() {
+        let root = server_markup("server");
+        let (h1, p) = (query(&root, "h1"), query(&root, "p"));
+        App::<Page>::new()
+            .with_mismatch_policy(MismatchPolicy::Rerender)
+            .hydrate_with_props(root.clone(), label("client"));
+        assert_eq!(query(&root, "h1"), h1);
+        assert_ne!(query(&root, "p"), p);
+        assert_eq!(query(&root, "main").child_nodes().len(), 2);
+        assert_eq!(root.text_content().unwrap(), "Titleclient");
+    }
+
+    struct Words {
+        props: LabelProps,
+    }
+
+    impl Component for Words {
+        type Message = ();
+        type Properties = LabelProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Words { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p>{ "Hello" }{ &self.props.label }{ "" }<></></p> }
+        }
+    }
+
+    #[test]
+    fn markers_keep_text_nodes_apart() {
+        let markup = crate::render_to_string::<Words>(label("world")).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+        let hello = query(&root, "p").first_child().unwrap();
+
+        App::<Words>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate_with_props(root.clone(), label("world"));
+        let nodes = query(&root, "p").child_nodes();
+        let texts: Vec<_> = nodes.iter().filter_map(|node| node.node_value()).collect();
+        assert_eq!(texts, ["Hello", "world", "", ""]);
+        assert!(nodes.iter().all(|node| node.node_name() == "#text"));
+        assert_eq!(nodes[0], hello);
+    }
+
+    struct Embed {
+        span: Element,
+    }
+
+    impl Component for Embed {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            let span = document().create_element("span").unwrap();
+            let text = document().create_text_node("embedded");
+            span.append_child(&Renderer::text_as_node(&text)).unwrap();
+            Embed { span }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let span = Renderer::element_as_node(&self.span);
+            html! { <div>{ "before" }{ Html::VRef(span) }<p>{ "after" }</p></div> }
+        }
+    }
+
+    #[test]
+    fn refs_replace_their_server_copy() {
+        let markup = crate::render_to_string::<Embed>(()).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+        let (span, p) = (query(&root, "span"), query(&root, "p"));
+
+        App::<Embed>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate(root.clone());
+        let nodes = query(&root, "div").child_nodes();
+        assert_eq!(nodes.len(), 3);
+        assert_ne!(nodes[1], Renderer::element_as_node(&span));
+        assert_eq!(nodes[2], Renderer::element_as_node(&p));
+        assert_eq!(root.query_selector_all("span").unwrap().len(), 1);
+        assert_eq!(root.text_content().unwrap(), "beforeembeddedafter");
+    }
+
+    struct Editor;
+
+    impl Component for Editor {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Editor
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <form><textarea value="hello" /><p>{ "after" }</p></form> }
+        }
+    }
+
+    #[test]
+    fn textarea_values_are_no_mismatch() {
+        let markup = crate::render_to_string::<Editor>(()).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+        let textarea = query(&root, "textarea");
+
+        App::<Editor>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate(root.clone());
+        assert_eq!(query(&root, "textarea"), textarea);
+        let textarea = Renderer::element_as_textarea(&textarea).unwrap();
+        assert_eq!(Renderer::textarea_value(&textarea), "hello");
+        assert_eq!(root.text_content().unwrap(), "helloafter");
+    }
+
+    struct Heading {
+        props: LabelProps,
+    }
+
+    impl Component for Heading {
+        type Message = ();
+        type Properties = LabelProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Heading { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <div><title>{ "Hello, " }{ &self.props.label }</title><p /></div> }
+        }
+    }
+
+    #[test]
+    fn title_text_is_merged() {
+        let markup = crate::render_to_string::<Heading>(label("world")).unwrap();
+        assert_eq!(
+            markup.as_str(),
+            "<div><title>Hello, world</title><p></p></div>"
+        );
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+
+        App::<Heading>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate_with_props(root.clone(), label("world"));
+        let title = query(&root, "title");
+        assert_eq!(title.child_nodes().len(), 2);
+        assert_eq!(title.text_content().unwrap(), "Hello, world");
+
+        let markup = crate::render_to_string::<Heading>(label("server")).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_attribute("id", "app").unwrap();
+        root.set_inner_html(markup.as_str());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            App::<Heading>::new()
+                .with_mismatch_policy(MismatchPolicy::Panic)
+                .hydrate_with_props(root, label("client"))
+        }));
+        let payload = result.expect_err("hydration didn't panic");
+        assert_eq!(
+            payload.downcast_ref::<String>().unwrap(),
+            "hydration mismatch in `Heading` at `div#app > div[0] > title[0] > #text[0]`: \
+             expected text \"Hello, client\", found text \"Hello, server\""
+        );
+    }
+}
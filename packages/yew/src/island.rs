@@ -0,0 +1,457 @@
+//! This module contains `Island`, which marks the interactive parts of a mostly static page so
+//! that the client only has to hydrate those parts.
+//!
+//! On the server an island is rendered inside of a `<yew-island>` boundary element, which names
+//! the component and carries a stable id. The props of the component are embedded into a state
+//! block at the end of the boundary. On the client every island is hydrated on its own with
+//! `App::hydrate_island` or `hydrate_islands`, while the markup around the islands stays inert.
+//!
+//! Ids are given out on the server in the order in which the islands are created, starting at
+//! `0`, and kept when the islands are hydrated. Islands nested inside of an island are hydrated
+//! along with the enclosing one.
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use serde::{Deserialize, Serialize};
+//! use yew::island::Island;
+//!
+//! # #[derive(Clone, Properties, Serialize, Deserialize)]
+//! # struct CartProps { items: usize }
+//! # struct Cart;
+//! # impl Component for Cart {
+//! #     type Message = ();
+//! #     type Properties = CartProps;
+//! #     fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self { Cart }
+//! #     fn update(&mut self, _: Self::Message) -> ShouldRender { false }
+//! #     fn change(&mut self, _: Self::Properties) -> ShouldRender { false }
+//! #     fn view(&self) -> Html { html! {} }
+//! # }
+//! # fn view() -> Html {
+//! html! {
+//!     <article>
+//!         <h1>{ "Lots of static content" }</h1>
+//!         <Island<Cart> items=3 />
+//!     </article>
+//! }
+//! # }
+//! # fn main() {
+//! // On the client
+//! yew::island::hydrate_islands::<Cart>().unwrap();
+//! # }
+//! ```
+
+use crate::backend::{DomBackend, Element, Renderer};
+use crate::html::{Component, ComponentError, ComponentLink, Html, NodeRef, ShouldRender};
+use crate::hydration;
+use crate::state::{self, StateError, StateFormat};
+use crate::utils::document;
+use crate::virtual_dom::{VComp, VTag};
+use crate::App;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any;
+#[cfg(feature = "static_render")]
+use std::cell::{Cell, RefCell};
+use std::fmt;
+#[cfg(feature = "static_render")]
+use std::rc::Rc;
+
+/// The tag name of the boundary element of an island.
+pub const ISLAND_TAG: &str = "yew-island";
+
+/// The attribute which holds the id of an island.
+pub const ISLAND_ID_ATTRIBUTE: &str = "data-island";
+
+/// The attribute which holds the type name of the component of an island.
+pub const ISLAND_COMPONENT_ATTRIBUTE: &str = "data-component";
+
+#[cfg(feature = "static_render")]
+thread_local! {
+    /// The ids of the islands in the component tree which is being rendered.
+    static CURRENT: RefCell<Option<IslandIds>> = const { RefCell::new(None) };
+}
+
+/// Gives out the ids of the islands created while rendering a component tree on the server.
+#[cfg(feature = "static_render")]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct IslandIds {
+    next: Rc<Cell<usize>>,
+}
+
+#[cfg(feature = "static_render")]
+impl IslandIds {
+    /// Runs `f` while giving out ids to the islands it creates.
+    pub(crate) fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        crate::utils::scoped(&CURRENT, self.clone(), f)
+    }
+
+    fn next_id() -> Option<usize> {
+        CURRENT.with(|current| {
+            current.borrow().as_ref().map(|ids| {
+                let id = ids.next.get();
+                ids.next.set(id + 1);
+                id
+            })
+        })
+    }
+}
+
+/// Renders `COMP` as an island which can be hydrated on its own. `Island<COMP>` takes the same
+/// props as `COMP`.
+///
+/// Islands are only given a boundary while a page is rendered on the server or hydrated.
+/// Otherwise, e.g. when the app is mounted on the client, `Island<COMP>` just renders `COMP`.
+pub struct Island<COMP: Component> {
+    props: COMP::Properties,
+    id: Option<usize>,
+}
+
+impl<COMP: Component> fmt::Debug for Island<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Island")
+            .field("component", &any::type_name::<COMP>())
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<COMP> Component for Island<COMP>
+where
+    COMP: Component,
+    COMP::Properties: Serialize,
+{
+    type Message = ();
+    type Properties = COMP::Properties;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        // When hydrating, the island keeps the id it was given on the server
+        let id = match hydration::adopting().and_then(|node| Renderer::node_as_element(&node)) {
+            Some(boundary) => boundary_id::<COMP>(&boundary),
+            #[cfg(feature = "static_render")]
+            None => IslandIds::next_id(),
+            #[cfg(not(feature = "static_render"))]
+            None => None,
+        };
+        Island { props, id }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        Html::default()
+    }
+
+    fn try_view(&self) -> Result<Html, ComponentError> {
+        let component = VComp::new::<COMP>(self.props.clone(), NodeRef::default(), None).into();
+        let id = match self.id {
+            Some(id) => id,
+            None => return Ok(component),
+        };
+        let format = StateFormat::default();
+        let data = format
+            .encode(&self.props)
+            .map_err(|error| ComponentError::new(error.to_string()))?;
+        let mut boundary = VTag::new(ISLAND_TAG);
+        boundary.add_attribute(ISLAND_ID_ATTRIBUTE, id.to_string());
+        boundary.add_attribute(ISLAND_COMPONENT_ATTRIBUTE, any::type_name::<COMP>());
+        boundary.add_child(component);
+        boundary.add_child(state::state_block(format, data).into());
+        Ok(boundary.into())
+    }
+}
+
+/// Returns the boundary element of the island with the given id.
+pub fn find_island(id: usize) -> Option<Element> {
+    let selector = format!(r#"{}[{}="{}"]"#, ISLAND_TAG, ISLAND_ID_ATTRIBUTE, id);
+    document().query_selector(&selector).ok().flatten()
+}
+
+/// Returns the boundary elements of the islands of `COMP` in the document, leaving out islands
+/// nested inside of another island.
+pub fn find_islands<COMP: Component>() -> Vec<Element> {
+    let mut boundaries = Vec::new();
+    if let Some(body) = document().body() {
+        collect_boundaries(&body, any::type_name::<COMP>(), &mut boundaries);
+    }
+    boundaries
+}
+
+fn collect_boundaries(parent: &Element, component: &str, boundaries: &mut Vec<Element>) {
+    let mut cursor = Renderer::element_first_child(parent);
+    while let Some(node) = cursor {
+        cursor = Renderer::node_next_sibling(&node);
+        let element = match Renderer::node_as_element(&node) {
+            Some(element) => element,
+            None => continue,
+        };
+        if !is_boundary(&element) {
+            collect_boundaries(&element, component, boundaries);
+        } else if component_name(&element).as_deref() == Some(component) {
+            boundaries.push(element);
+        }
+    }
+}
+
+/// Hydrates every island of `COMP` in the document as an independent app, see
+/// `App::hydrate_island`.
+pub fn hydrate_islands<COMP>() -> Result<Vec<ComponentLink<COMP>>, StateError>
+where
+    COMP: Component,
+    COMP::Properties: DeserializeOwned,
+{
+    find_islands::<COMP>()
+        .into_iter()
+        .map(|boundary| App::<COMP>::new().hydrate_island(boundary))
+        .collect()
+}
+
+/// Decodes the props which were embedded into the island `boundary` of `COMP`.
+pub(crate) fn decode_props<COMP>(boundary: &Element) -> Result<COMP::Properties, StateError>
+where
+    COMP: Component,
+    COMP::Properties: DeserializeOwned,
+{
+    let name = any::type_name::<COMP>();
+    if !is_boundary_of::<COMP>(boundary) {
+        return Err(StateError::NotFound(format!("an island of `{}`", name)));
+    }
+    let script = Renderer::element_last_child(boundary)
+        .and_then(|node| Renderer::node_as_element(&node))
+        .filter(state::is_state_block)
+        .ok_or_else(|| StateError::NotFound(format!("the props of the island of `{}`", name)))?;
+    state::decode_block(&script)
+}
+
+fn is_boundary(element: &Element) -> bool {
+    Renderer::element_local_name(element).eq_ignore_ascii_case(ISLAND_TAG)
+}
+
+fn is_boundary_of<COMP: Component>(element: &Element) -> bool {
+    is_boundary(element) && component_name(element).as_deref() == Some(any::type_name::<COMP>())
+}
+
+/// Returns the id of `boundary` if it is the boundary of an island of `COMP`.
+fn boundary_id<COMP: Component>(boundary: &Element) -> Option<usize> {
+    if !is_boundary_of::<COMP>(boundary) {
+        return None;
+    }
+    Renderer::element_get_attribute(boundary, ISLAND_ID_ATTRIBUTE)?
+        .parse()
+        .ok()
+}
+
+fn component_name(boundary: &Element) -> Option<String> {
+    Renderer::element_get_attribute(boundary, ISLAND_COMPONENT_ATTRIBUTE)
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::Properties;
+    use crate::hydration::MismatchPolicy;
+    use crate::{html, utils::document};
+    use serde::Deserialize;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Properties, Serialize, Deserialize)]
+    struct CounterProps {
+        start: usize,
+    }
+
+    struct Counter {
+        props: CounterProps,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+        type Properties = CounterProps;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Counter { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <button>{ self.props.start }</button> }
+        }
+    }
+
+    struct Panel;
+
+    impl Component for Panel {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Panel
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <section>
+                    <Island<Counter> start=1 />
+                    <Island<Counter> start=2 />
+                </section>
+            }
+        }
+    }
+
+    struct Page;
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <main>
+                    <Island<Panel> />
+                    <p>{ "Static" }</p>
+                    <Island<Counter> start=3 />
+                </main>
+            }
+        }
+    }
+
+    fn boundary(id: usize, component: &str, content: &str, props: &str) -> String {
+        format!(
+            "<yew-island data-island=\"{}\" data-component=\"{}\">{}\
+             <script type=\"application&#x2F;yew&#x2D;state\" data-format=\"json\">{}</script>\
+             </yew-island>",
+            id,
+            htmlescape::encode_attribute(component),
+            content,
+            props
+        )
+    }
+
+    fn server_markup() -> String {
+        let counter = any::type_name::<Counter>();
+        let panel = boundary(
+            0,
+            any::type_name::<Panel>(),
+            &format!(
+                "<section>{}{}</section>",
+                boundary(2, counter, "<button>1</button>", r#"{"start":1}"#),
+                boundary(3, counter, "<button>2</button>", r#"{"start":2}"#)
+            ),
+            "null",
+        );
+        format!(
+            "<main>{}<p>Static</p>{}</main>",
+            panel,
+            boundary(1, counter, "<button>3</button>", r#"{"start":3}"#)
+        )
+    }
+
+    #[test]
+    fn islands_are_rendered_with_boundaries() {
+        let html = App::<Page>::new().render_to_string().unwrap();
+        assert_eq!(html.as_str(), server_markup());
+    }
+
+    #[test]
+    fn islands_are_hydrated_on_their_own() {
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(&server_markup());
+        document().body().unwrap().append_child(&root).unwrap();
+        let text = root.query_selector("p").unwrap().unwrap();
+        let buttons = root.query_selector_all("button").unwrap();
+
+        let counter = App::<Counter>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate_island(find_island(1).unwrap())
+            .unwrap();
+        assert_eq!(counter.get_component().unwrap().props.start, 3);
+        let panel = find_islands::<Panel>().pop().unwrap();
+        App::<Panel>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate_island(panel)
+            .unwrap();
+
+        let hydrated = root.query_selector_all("button").unwrap();
+        assert_eq!(hydrated, buttons);
+        assert_eq!(root.query_selector_all("script").unwrap().len(), 4);
+        assert!(root
+            .query_selector("p")
+            .unwrap()
+            .unwrap()
+            .is_same_node(Some(&text)));
+        assert!(matches!(
+            App::<Panel>::new().hydrate_island(find_island(2).unwrap()),
+            Err(StateError::NotFound(_))
+        ));
+        document().body().unwrap().remove_child(&root).unwrap();
+    }
+
+    #[test]
+    fn islands_keep_their_ids_when_the_page_is_hydrated() {
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(&server_markup());
+        let buttons = root.query_selector_all("button").unwrap();
+
+        App::<Page>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate(root.clone());
+        assert_eq!(root.query_selector_all("button").unwrap(), buttons);
+    }
+
+    #[test]
+    fn nested_islands_are_left_to_the_enclosing_island() {
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(&server_markup());
+        document().body().unwrap().append_child(&root).unwrap();
+
+        assert_eq!(find_islands::<Counter>(), vec![find_island(1).unwrap()]);
+        let counters = hydrate_islands::<Counter>().unwrap();
+        assert_eq!(counters.len(), 1);
+        document().body().unwrap().remove_child(&root).unwrap();
+    }
+
+    #[test]
+    fn islands_are_plain_components_when_mounted() {
+        let root = document().create_element("div").unwrap();
+        App::<Panel>::new().mount(root.clone());
+        assert!(root.query_selector(ISLAND_TAG).unwrap().is_none());
+        assert_eq!(root.query_selector_all("button").unwrap().len(), 2);
+    }
+}
@@ -257,17 +257,29 @@ pub mod macros {
     pub use crate::props;
 }
 
-// pub mod app;
+pub mod app;
 pub mod backend;
 pub mod callback;
-// pub mod format;
+pub mod head;
+pub use http;
+pub use yew_format as format;
 pub mod html;
+pub mod hydration;
+pub mod island;
+pub mod request;
+pub mod response;
+pub mod runtime;
 mod scheduler;
+#[cfg(feature = "static_render")]
+pub mod smr;
+pub mod state;
+#[cfg(yew_backend = "static_render")]
+pub mod testing;
 pub mod utils;
 pub mod virtual_dom;
 
-// #[cfg(feature = "agent")]
-// pub mod agent;
+#[cfg(feature = "agent")]
+pub mod agent;
 // #[cfg(feature = "services")]
 // pub mod services;
 
@@ -293,12 +305,18 @@ pub mod events {
                 PointerLeaveEvent, PointerMoveEvent, PointerOutEvent, PointerOverEvent, PointerUpEvent,
                 ScrollEvent, SubmitEvent, TouchCancel, TouchEnd, TouchEnter, TouchMove, TouchStart,
             };
-        } else if #[cfg(feature = "web_sys")] {
+        } else if #[cfg(yew_backend = "web_sys")] {
             #[doc(no_inline)]
             pub use web_sys::{
                 AnimationEvent, DragEvent, ErrorEvent, Event, FocusEvent, InputEvent, KeyboardEvent,
                 MouseEvent, PointerEvent, ProgressEvent, TouchEvent, TransitionEvent, UiEvent, WheelEvent,
             };
+        } else if #[cfg(yew_backend = "static_render")] {
+            #[doc(no_inline)]
+            pub use crate::backend::{
+                AnimationEvent, DragEvent, ErrorEvent, Event, FocusEvent, InputEvent, KeyboardEvent,
+                MouseEvent, PointerEvent, ProgressEvent, TouchEvent, TransitionEvent, UiEvent, WheelEvent,
+            };
         }
     }
 }
@@ -309,11 +327,17 @@ use cfg_match::cfg_match;
 pub fn initialize() {
     cfg_match! {
         feature = "std_web" => stdweb::initialize(),
-        feature = "web_sys" => std::panic::set_hook(Box::new(console_error_panic_hook::hook)),
-        feature = "static_render" => || {},
+        yew_backend = "web_sys" => std::panic::set_hook(Box::new(console_error_panic_hook::hook)),
+        yew_backend = "static_render" => (),
     };
 }
 
+#[cfg(feature = "static_render")]
+pub use smr::{
+    render_to_fmt_writer, render_to_response, render_to_string, render_to_string_async,
+    render_to_writer,
+};
+
 /// Starts event loop.
 pub fn run_loop() {
     #[cfg(feature = "std_web")]
@@ -321,25 +345,25 @@ pub fn run_loop() {
 }
 
 /// Starts an app mounted to a body of the document.
-// pub fn start_app<COMP>()
-// where
-//     COMP: Component,
-//     COMP::Properties: Default,
-// {
-//     initialize();
-//     App::<COMP>::new().mount_to_body();
-//     run_loop();
-// }
+pub fn start_app<COMP>()
+where
+    COMP: Component,
+    COMP::Properties: Default,
+{
+    initialize();
+    App::<COMP>::new().mount_to_body();
+    run_loop();
+}
 
 /// Starts an app mounted to a body of the document.
-// pub fn start_app_with_props<COMP>(props: COMP::Properties)
-// where
-//     COMP: Component,
-// {
-//     initialize();
-//     App::<COMP>::new().mount_to_body_with_props(props);
-//     run_loop();
-// }
+pub fn start_app_with_props<COMP>(props: COMP::Properties)
+where
+    COMP: Component,
+{
+    initialize();
+    App::<COMP>::new().mount_to_body_with_props(props);
+    run_loop();
+}
 
 /// The Yew Prelude
 ///
@@ -351,7 +375,7 @@ pub fn run_loop() {
 /// ```
 pub mod prelude {
 
-    // pub use crate::app::App;
+    pub use crate::app::App;
     pub use crate::callback::Callback;
     pub use crate::events::*;
     pub use crate::html::{
@@ -360,6 +384,19 @@ pub mod prelude {
     };
     pub use crate::macros::{html, html_nested};
     pub use crate::virtual_dom::Classes;
+
+    #[cfg(all(feature = "agent", any(feature = "std_web", yew_backend = "web_sys")))]
+    pub use crate::agent::Threaded;
+    #[cfg(feature = "agent")]
+    pub use crate::agent::{Bridge, Bridged, Dispatched};
+
+    /// Prelude module for creating worker.
+    #[cfg(feature = "agent")]
+    pub mod worker {
+        pub use crate::agent::{Agent, AgentLink, Bridge, Bridged, Context, HandlerId, Job};
+        #[cfg(any(feature = "std_web", yew_backend = "web_sys"))]
+        pub use crate::agent::{Private, Public};
+    }
 }
 
 pub use self::prelude::*;
@@ -0,0 +1,204 @@
+//! This module contains `RequestContext`, which gives components access to the HTTP request
+//! a page is rendered for on the server.
+
+use http::header::{self, HeaderMap};
+use http::{Method, Uri};
+
+/// The HTTP request a page is being rendered for.
+///
+/// The request belongs to the runtime the page is rendered in, see `Runtime::with_request` and
+/// `App::with_request`. Components read it with `ComponentLink::request_context` and function
+/// components with the `use_request_context` hook. It can't be changed while rendering and
+/// there is none on the client, so components need to render something sensible without it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestContext {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+}
+
+impl RequestContext {
+    /// Creates the context of a request.
+    pub fn new(method: Method, uri: Uri, headers: HeaderMap) -> Self {
+        RequestContext {
+            method,
+            uri,
+            headers,
+        }
+    }
+
+    /// Returns the method of the request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns the URI of the request.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Returns the headers of the request.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the first value of the header `name` if it is valid text.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Returns the host the request was sent to, taken from the URI or the `Host` header.
+    pub fn host(&self) -> Option<&str> {
+        self.uri.host().or_else(|| {
+            self.header(header::HOST.as_str())
+                .map(|host| host.split(':').next().unwrap_or(host))
+        })
+    }
+
+    /// Returns the `User-Agent` header.
+    pub fn user_agent(&self) -> Option<&str> {
+        self.header(header::USER_AGENT.as_str())
+    }
+
+    /// Returns the value of the cookie `name`.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the languages of the `Accept-Language` header, most preferred first.
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<(&str, f32)> = self
+            .headers
+            .get_all(header::ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|language| {
+                let mut parts = language.split(';').map(str::trim);
+                let tag = parts.next().filter(|tag| !tag.is_empty() && *tag != "*")?;
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.parse().ok())?;
+                Some((tag, quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+        // The sort is stable, so languages of the same quality keep their order
+        languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+        languages.into_iter().map(|(tag, _)| tag).collect()
+    }
+
+    /// Returns the language the client prefers, e.g. `de-CH`.
+    pub fn locale(&self) -> Option<&str> {
+        self.languages().into_iter().next()
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::{Component, ComponentLink, Html, ShouldRender};
+    use crate::{html, App};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn request() -> RequestContext {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "example.com:8080".parse().unwrap());
+        headers.insert(header::USER_AGENT, "test".parse().unwrap());
+        headers.insert(header::COOKIE, "theme=dark; session=abc".parse().unwrap());
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            "en;q=0.8, de-CH, fr;q=0.8, *;q=0.5, it;q=0"
+                .parse()
+                .unwrap(),
+        );
+        RequestContext::new(Method::GET, Uri::from_static("/docs"), headers)
+    }
+
+    #[test]
+    fn it_reads_the_headers() {
+        let request = request();
+        assert_eq!(request.host(), Some("example.com"));
+        assert_eq!(request.user_agent(), Some("test"));
+        assert_eq!(request.cookie("session"), Some("abc"));
+        assert_eq!(request.cookie("missing"), None);
+        assert_eq!(request.languages(), ["de-CH", "en", "fr"]);
+        assert_eq!(request.locale(), Some("de-CH"));
+    }
+
+    struct Greeting {
+        link: ComponentLink<Self>,
+    }
+
+    impl Component for Greeting {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+            Greeting { link }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let locale = self
+                .link
+                .request_context()
+                .and_then(|request| request.locale().map(ToOwned::to_owned));
+            html! { <p>{ locale.unwrap_or_else(|| "none".to_owned()) }</p> }
+        }
+    }
+
+    struct Page;
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <main><Greeting /></main> }
+        }
+    }
+
+    #[test]
+    fn components_read_the_request_of_their_runtime() {
+        let html = App::<Page>::new()
+            .with_request(request())
+            .render_to_string()
+            .unwrap();
+        assert_eq!(html.as_str(), "<main><p>de-CH</p></main>");
+
+        let html = App::<Page>::new().render_to_string().unwrap();
+        assert_eq!(html.as_str(), "<main><p>none</p></main>");
+    }
+}
@@ -0,0 +1,249 @@
+//! This module contains `ResponseContext`, which lets components set the status and headers
+//! of the HTTP response while they are rendered on the server.
+
+use http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+pub use http::StatusCode;
+
+thread_local! {
+    /// The response of the render which is currently in progress.
+    static CURRENT: RefCell<Option<ResponseContext>> = const { RefCell::new(None) };
+}
+
+/// The status and headers which components set for the page they are rendered into.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerResponse {
+    /// The status of the response, `200 OK` unless a component set another one
+    pub status: StatusCode,
+    /// The headers set by components
+    pub headers: HeaderMap,
+}
+
+impl Default for ServerResponse {
+    fn default() -> Self {
+        ServerResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+        }
+    }
+}
+
+impl ServerResponse {
+    /// Returns the target of the redirect if a component redirected the request.
+    pub fn location(&self) -> Option<&str> {
+        if !self.status.is_redirection() {
+            return None;
+        }
+        self.headers
+            .get(header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+    }
+}
+
+/// The response of the page which is being rendered on the server.
+///
+/// Components get the context of the render they are part of with `ResponseContext::current`
+/// and can set the status of the response, e.g. to `404 Not Found`, redirect it or add
+/// headers. The router does this on its own for routes which don't match and for redirects.
+/// On the client there is no context, so components can set the response unconditionally:
+///
+/// ```
+/// # use yew::response::{ResponseContext, StatusCode};
+/// if let Some(response) = ResponseContext::current() {
+///     response.set_status(StatusCode::NOT_FOUND);
+/// }
+/// ```
+///
+/// Pass a context to `App::with_response` to read the response after rendering, or use
+/// `App::render_to_response`.
+#[derive(Clone, Default)]
+pub struct ResponseContext {
+    response: Rc<RefCell<ServerResponse>>,
+}
+
+impl ResponseContext {
+    /// Creates the context of a `200 OK` response without headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the context of the render which is in progress, `None` on the client.
+    pub fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Sets the status of the response.
+    pub fn set_status(&self, status: StatusCode) {
+        self.response.borrow_mut().status = status;
+    }
+
+    /// Redirects the request to `location` with `302 Found`.
+    pub fn redirect(&self, location: &str) {
+        self.redirect_with_status(StatusCode::FOUND, location);
+    }
+
+    /// Redirects the request to `location` with `status`, e.g. `301 Moved Permanently`.
+    pub fn redirect_with_status(&self, status: StatusCode, location: &str) {
+        match HeaderValue::from_str(location) {
+            Ok(location) => {
+                let mut response = self.response.borrow_mut();
+                response.status = status;
+                response.headers.insert(header::LOCATION, location);
+            }
+            Err(_) => log::warn!("ignoring redirect to invalid location `{}`", location),
+        }
+    }
+
+    /// Sets the header `name` to `value`, replacing previous values.
+    pub fn set_header(&self, name: &str, value: &str) {
+        if let Some((name, value)) = header_pair(name, value) {
+            self.response.borrow_mut().headers.insert(name, value);
+        }
+    }
+
+    /// Adds `value` to the values of the header `name`.
+    pub fn append_header(&self, name: &str, value: &str) {
+        if let Some((name, value)) = header_pair(name, value) {
+            self.response.borrow_mut().headers.append(name, value);
+        }
+    }
+
+    /// Sets the `Cache-Control` header, e.g. to `public, max-age=3600`.
+    pub fn set_cache_control(&self, value: &str) {
+        self.set_header(header::CACHE_CONTROL.as_str(), value);
+    }
+
+    /// Adds a `Set-Cookie` header with `cookie`, e.g. `session=abc; HttpOnly`.
+    pub fn add_cookie(&self, cookie: &str) {
+        self.append_header(header::SET_COOKIE.as_str(), cookie);
+    }
+
+    /// Returns the response set so far.
+    pub fn response(&self) -> ServerResponse {
+        self.response.borrow().clone()
+    }
+
+    /// Runs `f` with components writing to this context.
+    #[cfg(feature = "static_render")]
+    pub(crate) fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        crate::utils::scoped(&CURRENT, self.clone(), f)
+    }
+}
+
+impl fmt::Debug for ResponseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseContext")
+            .field("response", &*self.response.borrow())
+            .finish()
+    }
+}
+
+fn header_pair(name: &str, value: &str) -> Option<(HeaderName, HeaderValue)> {
+    match (
+        HeaderName::from_bytes(name.as_bytes()),
+        HeaderValue::from_str(value),
+    ) {
+        (Ok(name), Ok(value)) => Some((name, value)),
+        _ => {
+            log::warn!("ignoring invalid header `{}: {}`", name, value);
+            None
+        }
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::{Component, ComponentLink, Html, Properties, ShouldRender};
+    use crate::{html, App};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Debug, Default, Properties)]
+    struct Props {
+        path: String,
+    }
+
+    struct Page {
+        props: Props,
+    }
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = Props;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            if let Some(response) = ResponseContext::current() {
+                response.set_cache_control("no-store");
+                response.add_cookie("a=1");
+                response.add_cookie("b=2");
+                match props.path.as_str() {
+                    "/" => {}
+                    "/old" => response.redirect("/new"),
+                    _ => response.set_status(StatusCode::NOT_FOUND),
+                }
+            }
+            Page { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p>{ &self.props.path }</p> }
+        }
+    }
+
+    fn render(path: &str) -> ServerResponse {
+        let props = Props {
+            path: path.to_owned(),
+        };
+        let (response, html) = crate::render_to_response::<Page>(props).unwrap();
+        assert_eq!(html.as_str(), format!("<p>{}</p>", path));
+        response
+    }
+
+    #[test]
+    fn components_set_the_response() {
+        let response = render("/");
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.location(), None);
+        assert_eq!(response.headers[header::CACHE_CONTROL], "no-store");
+        let cookies: Vec<_> = response
+            .headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .collect();
+        assert_eq!(cookies, ["a=1", "b=2"]);
+
+        assert_eq!(render("/missing").status, StatusCode::NOT_FOUND);
+
+        let response = render("/old");
+        assert_eq!(response.status, StatusCode::FOUND);
+        assert_eq!(response.location(), Some("/new"));
+    }
+
+    #[test]
+    fn there_is_no_response_outside_of_renders() {
+        let element = crate::utils::document().create_element("div").unwrap();
+        App::<Page>::new().mount_with_props(
+            element,
+            Props {
+                path: "/missing".to_owned(),
+            },
+        );
+        assert!(ResponseContext::current().is_none());
+    }
+}
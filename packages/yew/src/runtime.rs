@@ -0,0 +1,160 @@
+//! This module contains the `Runtime`, which keeps component trees from interfering with each
+//! other.
+
+use crate::request::RequestContext;
+use crate::scheduler::{scheduler, Scheduler};
+use std::rc::Rc;
+
+/// An isolated environment for component trees.
+///
+/// Every runtime owns a scheduler. A component is scheduled on the runtime its root component
+/// was created in, so its `create`, `update`, render and `destroy` tasks only ever run as part
+/// of that runtime, even if messages are sent to it while another runtime is running.
+///
+/// Apps which aren't created in a runtime share the runtime of the thread. Rendering to HTML
+/// uses a new runtime for every render, so renders which are interleaved on one thread, e.g. by
+/// an asynchronous server, can't run each other's tasks. Pass a runtime to `App::with_runtime`
+/// to pick the runtime of an app explicitly.
+///
+/// State which would otherwise be global to the thread, like the in-memory document and its
+/// location or the history of a router, belongs to the runtime as well. See `Runtime::local`.
+#[derive(Debug, Clone)]
+pub struct Runtime {
+    scheduler: Rc<Scheduler>,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::new()
+    }
+}
+
+impl Runtime {
+    /// Creates a runtime with an empty scheduler.
+    pub fn new() -> Self {
+        Runtime {
+            scheduler: Rc::new(Scheduler::new()),
+        }
+    }
+
+    /// Creates a runtime for rendering a page for `request`. Its components can read the
+    /// request with `ComponentLink::request_context`.
+    pub fn with_request(request: RequestContext) -> Self {
+        Runtime {
+            scheduler: Rc::new(Scheduler::with_request(request)),
+        }
+    }
+
+    /// Returns the runtime which is currently entered, i.e. the runtime of the component whose
+    /// task is running, or the runtime of the thread.
+    pub fn current() -> Self {
+        Runtime {
+            scheduler: scheduler(),
+        }
+    }
+
+    /// Returns the request this runtime renders a page for, if any.
+    pub fn request(&self) -> Option<Rc<RequestContext>> {
+        self.scheduler.request()
+    }
+
+    /// Returns the value of type `T` which belongs to this runtime. The first call for a type
+    /// creates the value with `init`, later calls return the same value, so state which has to
+    /// be shared by the components of a runtime, but not between runtimes, can be kept here
+    /// instead of in a thread local.
+    pub fn local<T: 'static>(&self, init: impl FnOnce() -> T) -> Rc<T> {
+        self.scheduler.local(init)
+    }
+
+    /// Runs `f` with apps created in it belonging to this runtime.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.scheduler.enter(f)
+    }
+
+    /// Runs the tasks which are scheduled on this runtime.
+    #[cfg(yew_backend = "static_render")]
+    pub(crate) fn run_scheduled(&self) {
+        self.scheduler.start();
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use crate::html::{Component, ComponentLink, Html, ShouldRender};
+    use crate::{html, App};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Inner;
+
+    impl Component for Inner {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Inner
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <b>{ "inner" }</b> }
+        }
+    }
+
+    /// Renders `Inner` to HTML while it is being rendered itself.
+    struct Outer;
+
+    impl Component for Outer {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Outer
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let inner = crate::render_to_string::<Inner>(()).unwrap();
+            html! { <pre>{ inner.as_str() }</pre> }
+        }
+    }
+
+    #[test]
+    fn renders_do_not_wait_for_each_other() {
+        let html = crate::render_to_string::<Outer>(()).unwrap();
+        assert_eq!(html.as_str(), "<pre>&lt;b&gt;inner&lt;/b&gt;</pre>");
+    }
+
+    #[test]
+    fn apps_run_in_their_runtime() {
+        let runtime = Runtime::new();
+        let html = App::<Outer>::new()
+            .with_runtime(runtime.clone())
+            .render_to_string()
+            .unwrap();
+        assert_eq!(html.as_str(), "<pre>&lt;b&gt;inner&lt;/b&gt;</pre>");
+
+        let element = crate::utils::document().create_element("div").unwrap();
+        runtime.enter(|| App::<Inner>::new().mount(element.clone()));
+        assert_eq!(element.text_content().unwrap(), "inner");
+    }
+}
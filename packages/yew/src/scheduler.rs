@@ -1,7 +1,11 @@
 //! This module contains a scheduler.
 
+use crate::request::RequestContext;
+use crate::utils;
+use anymap::AnyMap;
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::fmt;
 use std::rc::Rc;
 
 pub(crate) type Shared<T> = Rc<RefCell<T>>;
@@ -9,10 +13,28 @@ pub(crate) type Shared<T> = Rc<RefCell<T>>;
 thread_local! {
     static SCHEDULER: Rc<Scheduler> =
         Rc::new(Scheduler::new());
+    /// The scheduler which was entered last, if any
+    static CURRENT: RefCell<Option<Rc<Scheduler>>> = const { RefCell::new(None) };
 }
 
+/// Returns the scheduler new root components are scheduled on: the one which is currently
+/// entered or the one of the thread.
 pub(crate) fn scheduler() -> Rc<Scheduler> {
-    SCHEDULER.with(Rc::clone)
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| SCHEDULER.with(Rc::clone))
+}
+
+/// Drops the remaining tasks if a task panics, so that they don't run as part of whatever the
+/// scheduler runs next.
+struct ClearOnPanic<'a>(&'a Scheduler);
+
+impl Drop for ClearOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.clear();
+        }
+    }
 }
 
 /// A routine which could be run.
@@ -28,6 +50,16 @@ pub(crate) struct Scheduler {
     lock: Rc<RefCell<()>>,
     main: Shared<VecDeque<Box<dyn Runnable>>>,
     component: ComponentScheduler,
+    /// The request the components of this scheduler are rendered for
+    request: Option<Rc<RequestContext>>,
+    /// Values which belong to this scheduler, see `Runtime::local`
+    locals: Shared<AnyMap>,
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Scheduler")
+    }
 }
 
 pub(crate) enum ComponentRunnableType {
@@ -73,15 +105,42 @@ impl ComponentScheduler {
 }
 
 impl Scheduler {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Scheduler {
             lock: Rc::new(RefCell::new(())),
             main: Rc::new(RefCell::new(VecDeque::new())),
             component: ComponentScheduler::new(),
+            request: None,
+            locals: Rc::new(RefCell::new(AnyMap::new())),
         }
     }
 
-    pub(crate) fn push_comp(&self, run_type: ComponentRunnableType, runnable: Box<dyn Runnable>) {
+    pub(crate) fn with_request(request: RequestContext) -> Self {
+        Scheduler {
+            request: Some(Rc::new(request)),
+            ..Scheduler::new()
+        }
+    }
+
+    pub(crate) fn request(&self) -> Option<Rc<RequestContext>> {
+        self.request.clone()
+    }
+
+    pub(crate) fn local<T: 'static>(&self, init: impl FnOnce() -> T) -> Rc<T> {
+        if let Some(local) = self.locals.borrow().get::<Rc<T>>() {
+            return Rc::clone(local);
+        }
+        // `init` may use other locals, so the map must not be borrowed while it runs
+        let local = Rc::new(init());
+        self.locals.borrow_mut().insert(Rc::clone(&local));
+        local
+    }
+
+    pub(crate) fn push_comp(
+        self: &Rc<Self>,
+        run_type: ComponentRunnableType,
+        runnable: Box<dyn Runnable>,
+    ) {
         match run_type {
             ComponentRunnableType::Destroy => {
                 self.component.destroy.borrow_mut().push_back(runnable)
@@ -94,12 +153,16 @@ impl Scheduler {
         self.start();
     }
 
-    pub(crate) fn push_comp_update_batch(&self, it: impl IntoIterator<Item = Box<dyn Runnable>>) {
+    pub(crate) fn push_comp_update_batch(
+        self: &Rc<Self>,
+        it: impl IntoIterator<Item = Box<dyn Runnable>>,
+    ) {
         self.component.update.borrow_mut().extend(it);
         self.start();
     }
 
-    pub(crate) fn push(&self, runnable: Box<dyn Runnable>) {
+    #[allow(dead_code)]
+    pub(crate) fn push(self: &Rc<Self>, runnable: Box<dyn Runnable>) {
         self.main.borrow_mut().push_back(runnable);
         self.start();
     }
@@ -114,14 +177,32 @@ impl Scheduler {
             .or_else(|| self.main.borrow_mut().pop_front())
     }
 
-    pub(crate) fn start(&self) {
+    pub(crate) fn start(self: &Rc<Self>) {
         // The lock is used to prevent recursion. If the lock
         // cannot be acquired, it is because the `start()` method
         // is being called recursively as part of a `runnable.run()`.
         if let Ok(_lock) = self.lock.try_borrow_mut() {
-            while let Some(runnable) = self.next_runnable() {
-                runnable.run();
-            }
+            self.enter(|| {
+                let _clear = ClearOnPanic(self);
+                while let Some(runnable) = self.next_runnable() {
+                    runnable.run();
+                }
+            })
         }
     }
+
+    /// Drops all scheduled tasks.
+    fn clear(&self) {
+        self.main.borrow_mut().clear();
+        self.component.destroy.borrow_mut().clear();
+        self.component.create.borrow_mut().clear();
+        self.component.update.borrow_mut().clear();
+        self.component.render.borrow_mut().clear();
+        self.component.rendered.borrow_mut().clear();
+    }
+
+    /// Runs `f` with this scheduler returned by `scheduler()`.
+    pub(crate) fn enter<R>(self: &Rc<Self>, f: impl FnOnce() -> R) -> R {
+        utils::scoped(&CURRENT, Rc::clone(self), f)
+    }
 }
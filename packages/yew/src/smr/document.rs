@@ -0,0 +1,301 @@
+//! This module contains `HtmlDocument`, which wraps a rendered component tree in a complete
+//! HTML page.
+
+use super::writer::write_attribute;
+use super::{HtmlRenderError, HtmlWrite, RenderOptions, ToHtml};
+use crate::head::HeadCollector;
+use crate::virtual_dom::vtag::is_valid_name;
+use crate::virtual_dom::{VTag, VText};
+use indexmap::IndexMap;
+
+/// The shell of an HTML page which an app is rendered into.
+///
+/// Pass the document to `App::with_document` and the render methods of the app produce the
+/// whole page instead of a fragment:
+///
+/// ```html
+/// <!DOCTYPE html>
+/// <html {html attributes}>
+/// <head>{head tags}</head>
+/// <body {body attributes}>
+/// <div id="{mount id}">{rendered app}</div>
+/// {embedded state}
+/// {scripts}
+/// </body>
+/// </html>
+/// ```
+///
+/// The head holds the tags added with `head_tag` followed by the tags rendered by `Head`,
+/// `Title` and `Meta` components. A tag added here is left out if a component provides a tag
+/// with the same key, so it can serve as a default, e.g. for the title.
+///
+/// # Example
+/// ```
+/// use yew::smr::HtmlDocument;
+///
+/// let document = HtmlDocument::new()
+///     .html_attribute("lang", "en")
+///     .title("My app")
+///     .mount_id("app")
+///     .wasm_bindgen("/pkg/app.js", "/pkg/app_bg.wasm");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlDocument {
+    html_attributes: IndexMap<String, String>,
+    body_attributes: IndexMap<String, String>,
+    head: Vec<VTag>,
+    mount_id: String,
+    scripts: Vec<VTag>,
+}
+
+impl Default for HtmlDocument {
+    fn default() -> Self {
+        HtmlDocument {
+            html_attributes: IndexMap::new(),
+            body_attributes: IndexMap::new(),
+            head: Vec::new(),
+            mount_id: "app".to_owned(),
+            scripts: Vec::new(),
+        }
+    }
+}
+
+impl HtmlDocument {
+    /// Creates a document with an empty head which mounts the app at `<div id="app">`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an attribute of the `<html>` element, e.g. `lang`.
+    pub fn html_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.html_attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets an attribute of the `<body>` element.
+    pub fn body_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.body_attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// Adds a tag to the head, e.g. a stylesheet link.
+    pub fn head_tag(mut self, tag: VTag) -> Self {
+        self.head.push(tag);
+        self
+    }
+
+    /// Sets the title which is used unless a `Title` component is rendered.
+    pub fn title(self, title: impl Into<String>) -> Self {
+        let mut tag = VTag::new("title");
+        tag.add_child(VText::new(title.into()).into());
+        self.head_tag(tag)
+    }
+
+    /// Sets the id of the element the app is rendered into. This is the element to hydrate on
+    /// the client.
+    pub fn mount_id(mut self, id: impl Into<String>) -> Self {
+        self.mount_id = id.into();
+        self
+    }
+
+    /// Returns the id of the element the app is rendered into.
+    pub fn get_mount_id(&self) -> &str {
+        &self.mount_id
+    }
+
+    /// Adds a `<script type="module">` which loads `src`. Scripts follow the app and the
+    /// embedded state at the end of the body.
+    pub fn module_script(mut self, src: impl Into<String>) -> Self {
+        let mut script = VTag::new("script");
+        script.add_attribute("type", "module");
+        script.add_attribute("src", src.into());
+        self.scripts.push(script);
+        self
+    }
+
+    /// Adds the script which starts an app built by `wasm-bindgen --target web`: it imports
+    /// the generated JavaScript module from `js_src` and initializes it with the wasm binary at
+    /// `wasm_src`.
+    pub fn wasm_bindgen(mut self, js_src: &str, wasm_src: &str) -> Self {
+        let code = format!(
+            "import init from {};init({});",
+            js_string(js_src),
+            js_string(wasm_src)
+        );
+        let mut script = VTag::new("script");
+        script.add_attribute("type", "module");
+        script.add_child(VText::new(code).into());
+        self.scripts.push(script);
+        self
+    }
+
+    /// Writes everything up to the start of the mount element.
+    pub(crate) fn write_start<W: HtmlWrite + ?Sized>(
+        &self,
+        head: &HeadCollector,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        writer.write_html("<!DOCTYPE html><html")?;
+        write_attributes(writer, &self.html_attributes)?;
+        writer.write_html("><head>")?;
+        head.write_html_with_defaults(&self.head, writer, options)?;
+        writer.write_html("</head><body")?;
+        write_attributes(writer, &self.body_attributes)?;
+        writer.write_html(">")?;
+        writer.write_html("<div")?;
+        write_attribute(writer, "id", &self.mount_id)?;
+        writer.write_html(">")
+    }
+
+    /// Closes the mount element.
+    pub(crate) fn write_mount_end<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), HtmlRenderError> {
+        writer.write_html("</div>")
+    }
+
+    /// Writes the scripts and closes the document.
+    pub(crate) fn write_end<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        for script in self.scripts.iter() {
+            script.write_html_with_options(writer, options)?;
+        }
+        writer.write_html("</body></html>")
+    }
+}
+
+fn write_attributes<W: HtmlWrite + ?Sized>(
+    writer: &mut W,
+    attributes: &IndexMap<String, String>,
+) -> Result<(), HtmlRenderError> {
+    for (name, value) in attributes.iter() {
+        let name = name.to_lowercase();
+        if !is_valid_name(&name) {
+            return Err(HtmlRenderError::InvalidAttributeName(name));
+        }
+        write_attribute(writer, &name, value)?;
+    }
+    Ok(())
+}
+
+/// Quotes `value` as a JavaScript string literal.
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings can always be serialized")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::head::Title;
+    use crate::html::{Component, ComponentLink, Html, Properties, ShouldRender};
+    use crate::state::StateFormat;
+    use crate::{html, App};
+    use serde::Serialize;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Debug, Default, Properties, Serialize)]
+    struct Props {
+        title: Option<String>,
+    }
+
+    struct Page {
+        props: Props,
+    }
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = Props;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Page { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let title = match &self.props.title {
+                Some(title) => html! { <Title>{ title }</Title> },
+                None => html! {},
+            };
+            html! { <main>{ title }{ "Hello" }</main> }
+        }
+    }
+
+    fn document() -> HtmlDocument {
+        HtmlDocument::new()
+            .html_attribute("lang", "en")
+            .body_attribute("class", "dark")
+            .title("Site")
+            .mount_id("root")
+            .wasm_bindgen("/pkg/app.js", "/pkg/app_bg.wasm")
+    }
+
+    #[test]
+    fn it_wraps_the_app_in_a_page() {
+        let html = App::<Page>::new()
+            .with_document(document())
+            .render_to_string()
+            .unwrap();
+
+        assert_eq!(
+            html.as_str(),
+            "<!DOCTYPE html><html lang=\"en\"><head>\
+             <title data-yew-head=\"title\">Site</title></head>\
+             <body class=\"dark\"><div id=\"root\"><main><!---->Hello</main></div>\
+             <script type=\"module\">\
+             import init from \"/pkg/app.js\";init(\"/pkg/app_bg.wasm\");</script>\
+             </body></html>"
+        );
+    }
+
+    #[test]
+    fn components_override_head_tags_and_state_follows_the_mount_element() {
+        let html = App::<Page>::new()
+            .with_document(document().module_script("/extra.js"))
+            .with_embedded_props(StateFormat::Json)
+            .render_to_string_with_props(Props {
+                title: Some("Post".to_owned()),
+            })
+            .unwrap();
+
+        assert_eq!(
+            html.as_str(),
+            "<!DOCTYPE html><html lang=\"en\"><head>\
+             <title data-yew-head=\"title\">Post</title></head>\
+             <body class=\"dark\"><div id=\"root\"><main><!---->Hello</main></div>\
+             <script type=\"application&#x2F;yew&#x2D;state\" data-format=\"json\" data-root=\"\">\
+             {\"title\":\"Post\"}</script>\
+             <script type=\"module\">\
+             import init from \"/pkg/app.js\";init(\"/pkg/app_bg.wasm\");</script>\
+             <script type=\"module\" src=\"&#x2F;extra&#x2E;js\"></script>\
+             </body></html>"
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_attribute_names() {
+        let result = App::<Page>::new()
+            .with_document(HtmlDocument::new().html_attribute("a b", ""))
+            .render_to_string();
+        assert!(matches!(
+            result,
+            Err(HtmlRenderError::InvalidAttributeName(name)) if name == "a b"
+        ));
+    }
+}
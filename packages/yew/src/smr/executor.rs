@@ -0,0 +1,334 @@
+//! This module contains a minimal executor for futures which components spawn through their
+//! links while they are rendered on the server.
+
+use crate::utils;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use std::time::Instant;
+
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    /// The task set futures are spawned into.
+    static CURRENT: RefCell<Option<Rc<TaskSet>>> = const { RefCell::new(None) };
+}
+
+/// Spawns `future` into the task set of the render which is currently in progress.
+///
+/// Only asynchronous renders poll their task set. Futures which are spawned while no render is
+/// in progress are dropped right away. With the `web_sys` backend, futures are spawned onto the
+/// browser's event loop instead.
+#[cfg(yew_backend = "static_render")]
+pub(crate) fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    CURRENT.with(|current| {
+        if let Some(tasks) = current.borrow().as_ref() {
+            tasks.spawn(Box::pin(future));
+        }
+    })
+}
+
+/// Runs `future` to completion on the current thread, parking the thread while the future is
+/// pending.
+///
+/// This drives an asynchronous render, e.g. `App::render_to_string_with_props_async`, on a
+/// thread without an executor of its own, like a blocking thread of an asynchronous server.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// The futures spawned while rendering a single component tree.
+#[derive(Default)]
+pub(crate) struct TaskSet {
+    tasks: RefCell<Vec<Option<LocalFuture>>>,
+    shared: Arc<Shared>,
+}
+
+/// The part of a task set which is shared with its wakers.
+#[derive(Default)]
+struct Shared {
+    /// Tasks which have to be polled again
+    woken: Mutex<Vec<usize>>,
+    /// The waker of the future which polls the task set
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Shared {
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().unwrap().as_ref() {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+struct TaskWaker {
+    id: usize,
+    shared: Arc<Shared>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.shared.woken.lock().unwrap().push(self.id);
+        self.shared.wake();
+    }
+}
+
+impl TaskSet {
+    /// Runs `f` with this task set receiving all spawned futures.
+    pub(crate) fn enter<R>(self: &Rc<Self>, f: impl FnOnce() -> R) -> R {
+        utils::scoped(&CURRENT, Rc::clone(self), f)
+    }
+
+    /// Returns `true` if there are futures which haven't completed yet.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.tasks.borrow().iter().any(Option::is_some)
+    }
+
+    #[cfg(yew_backend = "static_render")]
+    fn spawn(&self, future: LocalFuture) {
+        let mut tasks = self.tasks.borrow_mut();
+        let id = tasks.len();
+        tasks.push(Some(future));
+        self.shared.woken.lock().unwrap().push(id);
+        self.shared.wake();
+    }
+
+    /// Polls every task which was woken since the last call. Resolves once all tasks have
+    /// completed.
+    pub(crate) fn poll(self: &Rc<Self>, cx: &mut Context<'_>) -> Poll<()> {
+        *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+        self.enter(|| loop {
+            let woken = mem::take(&mut *self.shared.woken.lock().unwrap());
+            if woken.is_empty() {
+                break;
+            }
+            for id in woken {
+                // The future is taken out while it is polled because it might spawn new tasks.
+                let future = self.tasks.borrow_mut()[id].take();
+                if let Some(mut future) = future {
+                    let waker = Waker::from(Arc::new(TaskWaker {
+                        id,
+                        shared: Arc::clone(&self.shared),
+                    }));
+                    if future
+                        .as_mut()
+                        .poll(&mut Context::from_waker(&waker))
+                        .is_pending()
+                    {
+                        self.tasks.borrow_mut()[id] = Some(future);
+                    }
+                }
+            }
+        });
+
+        if self.is_pending() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    /// Polls every task which was woken since the last call, without waiting for the tasks
+    /// which are still pending.
+    #[cfg(yew_backend = "static_render")]
+    pub(crate) fn run_until_stalled(self: &Rc<Self>) {
+        struct Noop;
+
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Waker::from(Arc::new(Noop));
+        let _ = self.poll(&mut Context::from_waker(&waker));
+    }
+
+    /// Wakes the future polling this task set at `deadline`, even if no task made progress.
+    /// Dropping the returned timer cancels the wake-up.
+    pub(crate) fn wake_at(&self, deadline: Instant) -> Timer {
+        Timers::shared().insert(deadline, Arc::clone(&self.shared))
+    }
+}
+
+/// The deadlines of all renders in the process, which are waited for by a single thread.
+struct Timers {
+    state: Mutex<TimerState>,
+    /// Notified when a deadline is added
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct TimerState {
+    next_id: u64,
+    /// The task sets to wake, ordered by their deadlines
+    deadlines: BTreeMap<(Instant, u64), Arc<Shared>>,
+}
+
+impl Timers {
+    /// Returns the timers of the process, starting their thread on first use.
+    fn shared() -> &'static Timers {
+        static TIMERS: OnceLock<Timers> = OnceLock::new();
+        TIMERS.get_or_init(|| {
+            thread::Builder::new()
+                .name("yew-render-timer".to_owned())
+                .spawn(|| Timers::shared().run())
+                .expect("can't spawn the timer thread");
+            Timers {
+                state: Mutex::default(),
+                changed: Condvar::new(),
+            }
+        })
+    }
+
+    fn insert(&'static self, deadline: Instant, shared: Arc<Shared>) -> Timer {
+        let mut state = self.state.lock().unwrap();
+        let key = (deadline, state.next_id);
+        state.next_id += 1;
+        state.deadlines.insert(key, shared);
+        self.changed.notify_one();
+        Timer { timers: self, key }
+    }
+
+    /// Wakes the task sets whose deadlines have passed, sleeping until the next deadline.
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let pending = state.deadlines.split_off(&(now, u64::MAX));
+            let due = mem::replace(&mut state.deadlines, pending);
+            if !due.is_empty() {
+                // Wakers might cancel timers, so they are called without holding the lock
+                drop(state);
+                due.values().for_each(|shared| shared.wake());
+                state = self.state.lock().unwrap();
+                continue;
+            }
+            state = match state.deadlines.keys().next() {
+                Some(&(deadline, _)) => {
+                    let timeout = deadline.saturating_duration_since(now);
+                    self.changed.wait_timeout(state, timeout).unwrap().0
+                }
+                None => self.changed.wait(state).unwrap(),
+            };
+        }
+    }
+}
+
+/// A wake-up of a task set, which is cancelled when it is dropped.
+#[must_use = "the wake-up is cancelled when the timer is dropped"]
+pub(crate) struct Timer {
+    timers: &'static Timers,
+    key: (Instant, u64),
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.timers
+            .state
+            .lock()
+            .unwrap()
+            .deadlines
+            .remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::callback::test_util::CallbackFuture;
+    use crate::callback::Callback;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    fn noop_context<R>(f: impl FnOnce(&mut Context<'_>) -> R) -> R {
+        struct Noop;
+
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Waker::from(Arc::new(Noop));
+        f(&mut Context::from_waker(&waker))
+    }
+
+    #[test]
+    fn it_polls_spawned_futures() {
+        let tasks = Rc::new(TaskSet::default());
+        let done = Rc::new(Cell::new(false));
+        let future = CallbackFuture::<()>::default();
+        let callback = Callback::from(future.clone());
+
+        let flag = Rc::clone(&done);
+        tasks.enter(|| {
+            spawn_local(async move {
+                future.await;
+                flag.set(true);
+            })
+        });
+        assert!(noop_context(|cx| tasks.poll(cx)).is_pending());
+        assert!(!done.get());
+
+        callback.emit(());
+        assert!(noop_context(|cx| tasks.poll(cx)).is_ready());
+        assert!(done.get());
+    }
+
+    #[test]
+    fn timers_wake_at_their_deadline_unless_cancelled() {
+        struct CountWakes(Arc<Mutex<usize>>);
+
+        impl Wake for CountWakes {
+            fn wake(self: Arc<Self>) {
+                *self.0.lock().unwrap() += 1;
+            }
+        }
+
+        let tasks = TaskSet::default();
+        let woken = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&woken);
+        let waker = Waker::from(Arc::new(CountWakes(count)));
+        *tasks.shared.waker.lock().unwrap() = Some(waker);
+
+        let cancelled = tasks.wake_at(Instant::now() + Duration::from_millis(10));
+        drop(cancelled);
+        let _timer = tasks.wake_at(Instant::now() + Duration::from_millis(20));
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(*woken.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn it_drops_futures_spawned_outside_of_a_render() {
+        let done = Rc::new(Cell::new(false));
+        let flag = Rc::clone(&done);
+        spawn_local(async move { flag.set(true) });
+        assert!(!done.get());
+    }
+}
@@ -0,0 +1,796 @@
+//! This module contains Yew's implementation of Sans-Mount Rendering (SMR), to support
+//! Static Site Generation and Server-Side Rendering (SSR).
+//! This functionality allows Yew Components to be rendered to a string without needing
+//! to be mounted onto a browser DOM first.
+//!
+//! *This module is only available if the `static_render` feature is enabled.*
+
+mod document;
+mod executor;
+mod writer;
+
+pub use self::document::HtmlDocument;
+pub use self::executor::block_on;
+#[cfg(yew_backend = "static_render")]
+pub(crate) use self::executor::spawn_local;
+pub(crate) use self::executor::TaskSet;
+#[cfg(yew_backend = "static_render")]
+pub(crate) use self::writer::write_dom_node;
+pub use self::writer::{FmtWriter, HtmlWrite, IoWriter, RenderOptions, ToHtml};
+
+use crate::app::App;
+use crate::html::Component;
+use crate::response::ServerResponse;
+use crate::virtual_dom::{VComp, VList, VNode, VTag, VText};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::time::Duration;
+use thiserror::Error as ThisError;
+
+/// Represents a block of HTML string content generated via Sans-Mount Rendering
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct HtmlString(String);
+
+impl HtmlString {
+    pub(crate) fn new(html: String) -> Self {
+        Self(html)
+    }
+
+    /// Returns the HTML as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for HtmlString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<HtmlString> for String {
+    fn from(html: HtmlString) -> Self {
+        html.0
+    }
+}
+
+/// Represents errors associated with conversion of Yew structures to HTML.
+#[derive(Debug, ThisError)]
+pub enum HtmlRenderError {
+    /// Malformed/unserializable attribute name
+    #[error("cannot serialize invalid attribute name `{0}`")]
+    InvalidAttributeName(String),
+
+    /// Malformed/unserializable tag name
+    #[error("cannot serialize invalid tag name `{0}`")]
+    InvalidTagName(String),
+
+    /// A `VRef` holds a node which is neither an element, text nor a comment
+    #[error("cannot serialize VRef which is neither an element, text nor a comment")]
+    UnserializableVRef,
+
+    /// Writing to an `io::Write` sink failed
+    #[error("cannot write HTML: {0}")]
+    Io(#[from] io::Error),
+
+    /// Writing to a `fmt::Write` sink failed
+    #[error("cannot format HTML")]
+    Fmt(#[from] fmt::Error),
+
+    /// Props which should be embedded into the page couldn't be encoded
+    #[error("cannot encode embedded state: {0}")]
+    State(anyhow::Error),
+}
+
+/// Renders the component `COMP` with `props` to HTML.
+///
+/// This is a shorthand for `App::<COMP>::new().render_to_string_with_props(props)`.
+///
+/// # Example
+/// ```
+///# use yew::prelude::*;
+/// struct Greeting;
+///
+/// impl Component for Greeting {
+///     type Message = ();
+///     type Properties = ();
+///
+///     fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+///         Greeting
+///     }
+///
+///     fn update(&mut self, _: Self::Message) -> ShouldRender {
+///         false
+///     }
+///
+///     fn change(&mut self, _: Self::Properties) -> ShouldRender {
+///         false
+///     }
+///
+///     fn view(&self) -> Html {
+///         html! { <p>{ "Hello" }</p> }
+///     }
+/// }
+///
+/// let html = yew::render_to_string::<Greeting>(()).unwrap();
+/// assert_eq!(html.to_string(), "<p>Hello</p>");
+/// ```
+pub fn render_to_string<COMP>(props: COMP::Properties) -> Result<HtmlString, HtmlRenderError>
+where
+    COMP: Component,
+{
+    App::<COMP>::new().render_to_string_with_props(props)
+}
+
+/// Renders the component `COMP` with `props` to HTML and returns it with the response its
+/// components set, e.g. a `404 Not Found` status or the location of a redirect.
+///
+/// This is a shorthand for `App::<COMP>::new().render_to_response_with_props(props)`.
+pub fn render_to_response<COMP>(
+    props: COMP::Properties,
+) -> Result<(ServerResponse, HtmlString), HtmlRenderError>
+where
+    COMP: Component,
+{
+    App::<COMP>::new().render_to_response_with_props(props)
+}
+
+/// Renders the component `COMP` with `props` and streams the HTML into `writer`.
+///
+/// The writer is flushed every time a component has been written, so wrapping e.g. a socket in
+/// a `BufWriter` still lets the first bytes of a page go out before the rest is serialized.
+/// Use [`App::render_to_writer_with_props`] to stream into a custom [`HtmlWrite`] sink.
+pub fn render_to_writer<COMP, W>(props: COMP::Properties, writer: W) -> Result<(), HtmlRenderError>
+where
+    COMP: Component,
+    W: io::Write,
+{
+    App::<COMP>::new().render_to_writer_with_props(props, IoWriter::new(writer))
+}
+
+/// Renders the component `COMP` with `props` and streams the HTML into the
+/// [`fmt::Write`] `writer`.
+pub fn render_to_fmt_writer<COMP, W>(
+    props: COMP::Properties,
+    writer: W,
+) -> Result<(), HtmlRenderError>
+where
+    COMP: Component,
+    W: fmt::Write,
+{
+    App::<COMP>::new().render_to_writer_with_props(props, FmtWriter::new(writer))
+}
+
+/// Renders the component `COMP` with `props` to HTML once the futures spawned through the
+/// links of its components have completed, waiting at most `timeout`.
+///
+/// This is a shorthand for
+/// `App::<COMP>::new().render_to_string_with_props_async(props, timeout)`.
+pub async fn render_to_string_async<COMP>(
+    props: COMP::Properties,
+    timeout: Duration,
+) -> Result<HtmlString, HtmlRenderError>
+where
+    COMP: Component,
+{
+    App::<COMP>::new()
+        .render_to_string_with_props_async(props, timeout)
+        .await
+}
+
+// HTML output for a VTag is not necessarily deterministic due to the
+// serialization of props which do not have a particular ordering.
+macro_rules! impl_try_from {
+    ($($type:ty),*) => {$(
+        impl TryFrom<&$type> for HtmlString {
+            type Error = HtmlRenderError;
+
+            fn try_from(value: &$type) -> Result<HtmlString, HtmlRenderError> {
+                let mut writer = FmtWriter::new(String::new());
+                value.write_html(&mut writer)?;
+                Ok(HtmlString::new(writer.into_inner()))
+            }
+        }
+
+        impl TryFrom<$type> for HtmlString {
+            type Error = HtmlRenderError;
+
+            fn try_from(value: $type) -> Result<HtmlString, HtmlRenderError> {
+                HtmlString::try_from(&value)
+            }
+        }
+    )*};
+}
+
+impl_try_from!(VComp, VTag, VText, VList, VNode);
+
+#[cfg(test)]
+mod test_vtext {
+    use super::HtmlString;
+    use crate::html;
+    use std::convert::TryFrom;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn text_as_root_smr() {
+        let a = html! {
+            "Text Node As Root"
+        };
+
+        let b = html! {
+            { "Text Node As Root" }
+        };
+
+        assert_eq!(
+            HtmlString::try_from(a).expect("HTML stringify error"),
+            HtmlString::try_from(b.clone()).expect("HTML stringify error")
+        );
+        assert_eq!(
+            HtmlString::try_from(b)
+                .expect("HTML stringify error")
+                .to_string(),
+            "Text Node As Root"
+        );
+    }
+
+    #[test]
+    fn special_chars_smr() {
+        let a = html! {
+            "some special-chars\"> here!"
+        };
+
+        let b = html! {
+            { "some special-chars\"> here!" }
+        };
+
+        assert_eq!(
+            HtmlString::try_from(a).expect("HTML stringify error"),
+            HtmlString::try_from(b.clone()).expect("HTML stringify error")
+        );
+        assert_eq!(
+            HtmlString::try_from(b)
+                .expect("HTML stringify error")
+                .to_string(),
+            "some special-chars&quot;&gt; here!"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_vtag {
+    use super::*;
+    use crate::html;
+    use crate::html::NodeRef;
+    use std::convert::TryFrom;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn it_stringifies_simple() {
+        let p = html! {
+            <p></p>
+        };
+
+        let p_html = HtmlString::try_from(p)
+            .expect("HTML stringify error")
+            .to_string();
+
+        assert_eq!(p_html, "<p></p>");
+    }
+
+    #[test]
+    fn it_stringifies_complex() {
+        let other_sym = "bar";
+        let div = html! {
+            <div class=("foo", other_sym)>
+                { "quux" }
+            </div>
+        };
+        let p = html! {
+            <p aria-controls="it-works">
+                { "test" }
+                {div}
+            </p>
+        };
+
+        let p_html = HtmlString::try_from(p)
+            .expect("HTML stringify error")
+            .to_string();
+
+        assert_eq!(
+            p_html,
+            "<p aria-controls=\"it&#x2D;works\">test<div class=\"foo&#x20;bar\">quux</div></p>"
+        );
+    }
+
+    #[test]
+    fn it_stringifies_attrs() {
+        let div = html! {
+            <div a="b" b="a" />
+        };
+
+        let div_html = HtmlString::try_from(div)
+            .expect("HTML stringify error")
+            .to_string();
+        let order_1 = "<div a=\"b\" b=\"a\"></div>";
+        let order_2 = "<div b=\"a\" a=\"b\"></div>";
+        assert!(div_html == order_1 || div_html == order_2);
+    }
+
+    #[test]
+    fn it_orders_attributes_deterministically() {
+        let options = RenderOptions {
+            deterministic_attributes: true,
+        };
+        let render = |node: &VNode| {
+            let mut html = String::new();
+            node.write_html_with_options(&mut FmtWriter::new(&mut html), &options)
+                .expect("HTML write error");
+            html
+        };
+
+        let positional = html! { <input b="a" a="b" type="text" value="v" /> };
+        assert_eq!(
+            render(&positional),
+            "<input a=\"b\" b=\"a\" type=\"text\" value=\"v\">"
+        );
+
+        let mut tag = VTag::new("div");
+        tag.add_attribute("data-z", "");
+        tag.add_attribute("ID", "x");
+        tag.add_attribute("class", "c");
+        assert_eq!(
+            render(&tag.into()),
+            "<div class=\"c\" data-z=\"\" id=\"x\"></div>"
+        );
+    }
+
+    #[test]
+    fn it_does_not_stringify_special_attrs() {
+        let node_ref = NodeRef::default();
+
+        let div = html! {
+            <div ref=node_ref />
+        };
+
+        let div_html = HtmlString::try_from(div)
+            .expect("HTML stringify error")
+            .to_string();
+        assert_eq!(div_html, "<div></div>");
+    }
+
+    #[test]
+    fn it_stringifies_form_controls() {
+        let form = html! {
+            <form>
+                <input type="checkbox" value="yes" checked=true />
+                <textarea value="<text>" />
+            </form>
+        };
+
+        let form_html = HtmlString::try_from(form)
+            .expect("HTML stringify error")
+            .to_string();
+        assert_eq!(
+            form_html,
+            "<form><input type=\"checkbox\" value=\"yes\" checked><textarea>&lt;text&gt;</textarea></form>"
+        );
+    }
+
+    #[test]
+    fn it_stringifies_void_elements() {
+        let html = html! {
+            <div>
+                <img alt="logo" />
+                <br />
+                <p />
+            </div>
+        };
+
+        let html = HtmlString::try_from(html)
+            .expect("HTML stringify error")
+            .to_string();
+        assert_eq!(html, "<div><img alt=\"logo\"><br><p></p></div>");
+    }
+
+    #[test]
+    fn it_does_not_escape_raw_text() {
+        let html = html! {
+            <>
+                <script>{ "if (a < b && c) { s = '</SCRIPT><b>'; }" }</script>
+                <style>{ "a > b { content: \"</style>\" }" }</style>
+                <title>{ "a < b" }</title>
+                <noscript>{ "<p>Enable JavaScript & reload</p>" }</noscript>
+                <xmp>{ "<b>" }</xmp>
+            </>
+        };
+
+        let html = HtmlString::try_from(html)
+            .expect("HTML stringify error")
+            .to_string();
+        assert_eq!(
+            html,
+            "<script>if (a < b && c) { s = '<\\/SCRIPT><b>'; }</script>\
+             <style>a > b { content: \"<\\/style>\" }</style>\
+             <title>a &lt; b</title>\
+             <noscript><p>Enable JavaScript & reload</p></noscript>\
+             <xmp><b></xmp>"
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_names() {
+        let mut tag = VTag::new("div");
+        tag.add_attribute("on\"click", "");
+        assert!(matches!(
+            HtmlString::try_from(tag),
+            Err(HtmlRenderError::InvalidAttributeName(_))
+        ));
+
+        let tag = VTag::new("my tag");
+        assert!(matches!(
+            HtmlString::try_from(tag),
+            Err(HtmlRenderError::InvalidTagName(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_rendering {
+    use super::*;
+    use crate::html::{ComponentLink, Properties, ShouldRender};
+    use crate::{html, Html};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Properties, Default)]
+    struct Props {
+        #[prop_or_default]
+        title: String,
+        #[prop_or_default]
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    struct TestApplication {
+        props: Props,
+    }
+
+    impl Component for TestApplication {
+        type Message = ();
+        type Properties = Props;
+
+        fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+            props.log.borrow_mut().push("create");
+            Self { props }
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            true
+        }
+
+        fn rendered(&mut self, _first_render: bool) {
+            self.props.log.borrow_mut().push("rendered");
+        }
+
+        fn destroy(&mut self) {
+            self.props.log.borrow_mut().push("destroy");
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <div>
+                    <h1>{ &self.props.title }</h1>
+                    <Child />
+                </div>
+            }
+        }
+    }
+
+    struct Child;
+
+    impl Component for Child {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Child
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <p>{ "This is rendered without a dom!" }</p> }
+        }
+    }
+
+    #[test]
+    fn it_renders_components() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let props = Props::builder()
+            .title("Hello".to_owned())
+            .log(log.clone())
+            .build();
+
+        let html = render_to_string::<TestApplication>(props).expect("HTML render error");
+        assert_eq!(
+            html.as_str(),
+            "<div><h1>Hello</h1><p>This is rendered without a dom!</p></div>"
+        );
+        assert_eq!(&*log.borrow(), &["create", "rendered", "destroy"]);
+    }
+
+    #[test]
+    fn it_renders_default_props() {
+        let html = App::<TestApplication>::new()
+            .render_to_string()
+            .expect("HTML render error");
+        assert_eq!(
+            html.to_string(),
+            "<div><h1><!----></h1><p>This is rendered without a dom!</p></div>"
+        );
+    }
+
+    #[test]
+    fn it_streams_to_writers() {
+        let props = Props::builder().title("Hello".to_owned()).build();
+        let mut bytes = Vec::new();
+        render_to_writer::<TestApplication, _>(props, &mut bytes).expect("HTML render error");
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "<div><h1>Hello</h1><p>This is rendered without a dom!</p></div>"
+        );
+
+        let mut html = String::new();
+        render_to_fmt_writer::<Child, _>((), &mut html).expect("HTML render error");
+        assert_eq!(html, "<p>This is rendered without a dom!</p>");
+    }
+
+    #[test]
+    fn it_flushes_at_component_boundaries() {
+        struct Chunks(Vec<String>);
+
+        impl HtmlWrite for Chunks {
+            fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+                self.0.last_mut().unwrap().push_str(html);
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), HtmlRenderError> {
+                self.0.push(String::new());
+                Ok(())
+            }
+        }
+
+        let mut chunks = Chunks(vec![String::new()]);
+        App::<TestApplication>::new()
+            .render_to_writer(&mut chunks)
+            .expect("HTML render error");
+        assert_eq!(
+            chunks.0,
+            vec![
+                "<div><h1><!----></h1><p>This is rendered without a dom!</p>",
+                "</div>",
+                ""
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_async_rendering {
+    use super::*;
+    use crate::html::{ComponentLink, Properties, ShouldRender};
+    use crate::{html, Html};
+    use std::future::{self, Future};
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::thread;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Polls `a` and `b` alternately until both have completed.
+    async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+        let (mut a_output, mut b_output) = (None, None);
+        future::poll_fn(|cx| {
+            if a_output.is_none() {
+                if let Poll::Ready(output) = a.as_mut().poll(cx) {
+                    a_output = Some(output);
+                }
+            }
+            if b_output.is_none() {
+                if let Poll::Ready(output) = b.as_mut().poll(cx) {
+                    b_output = Some(output);
+                }
+            }
+            match (a_output.take(), b_output.take()) {
+                (Some(a), Some(b)) => Poll::Ready((a, b)),
+                (a, b) => {
+                    a_output = a;
+                    b_output = b;
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// A future which resolves after `duration`, woken from another thread.
+    struct Delay {
+        duration: Duration,
+        done: Arc<AtomicBool>,
+        started: bool,
+    }
+
+    fn delay(millis: u64) -> Delay {
+        Delay {
+            duration: Duration::from_millis(millis),
+            done: Arc::default(),
+            started: false,
+        }
+    }
+
+    impl Future for Delay {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.done.load(Ordering::SeqCst) {
+                return Poll::Ready(());
+            }
+            if !self.started {
+                self.started = true;
+                let (duration, done, waker) =
+                    (self.duration, self.done.clone(), cx.waker().clone());
+                thread::spawn(move || {
+                    thread::sleep(duration);
+                    done.store(true, Ordering::SeqCst);
+                    waker.wake();
+                });
+            }
+            Poll::Pending
+        }
+    }
+
+    #[derive(Clone, Properties)]
+    struct Props {
+        #[prop_or_default]
+        hang: bool,
+    }
+
+    enum Msg {
+        Loaded(&'static str),
+        DetailsLoaded(&'static str),
+    }
+
+    struct Loader {
+        link: ComponentLink<Self>,
+        title: Option<&'static str>,
+        details: Option<&'static str>,
+    }
+
+    impl Component for Loader {
+        type Message = Msg;
+        type Properties = Props;
+
+        fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+            if props.hang {
+                link.send_future(async {
+                    future::pending::<()>().await;
+                    Msg::Loaded("never")
+                });
+            } else {
+                link.send_future(async {
+                    delay(10).await;
+                    Msg::Loaded("Title")
+                });
+            }
+            Loader {
+                link,
+                title: None,
+                details: None,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Message) -> ShouldRender {
+            match msg {
+                Msg::Loaded(title) => {
+                    self.title = Some(title);
+                    self.link.send_future(async {
+                        delay(5).await;
+                        Msg::DetailsLoaded("Details")
+                    });
+                }
+                Msg::DetailsLoaded(details) => self.details = Some(details),
+            }
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            match (self.title, self.details) {
+                (Some(title), Some(details)) => html! { <p>{ title }{ ": " }{ details }</p> },
+                _ => html! { <p>{ "Loading..." }</p> },
+            }
+        }
+    }
+
+    #[test]
+    fn it_waits_for_futures() {
+        let props = Props::builder().build();
+        let html = block_on(render_to_string_async::<Loader>(
+            props,
+            Duration::from_secs(5),
+        ))
+        .unwrap();
+        assert_eq!(html.as_str(), "<p>Title<!--,-->: <!--,-->Details</p>");
+    }
+
+    #[test]
+    fn it_renders_after_the_deadline() {
+        let props = Props::builder().hang(true).build();
+        let html = block_on(render_to_string_async::<Loader>(
+            props,
+            Duration::from_millis(20),
+        ))
+        .unwrap();
+        assert_eq!(html.as_str(), "<p>Loading...</p>");
+    }
+
+    #[test]
+    fn interleaved_renders_are_isolated() {
+        let (loaded, hanging) = block_on(join(
+            render_to_string_async::<Loader>(Props::builder().build(), Duration::from_secs(5)),
+            render_to_string_async::<Loader>(
+                Props::builder().hang(true).build(),
+                Duration::from_millis(50),
+            ),
+        ));
+        assert_eq!(
+            loaded.unwrap().as_str(),
+            "<p>Title<!--,-->: <!--,-->Details</p>"
+        );
+        assert_eq!(hanging.unwrap().as_str(), "<p>Loading...</p>");
+    }
+
+    #[test]
+    fn it_does_not_wait_when_rendering_synchronously() {
+        let props = Props::builder().build();
+        let html = render_to_string::<Loader>(props).unwrap();
+        assert_eq!(html.as_str(), "<p>Loading...</p>");
+    }
+}
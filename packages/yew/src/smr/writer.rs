@@ -0,0 +1,713 @@
+//! This module contains the streaming HTML serializer, which writes the HTML of a virtual DOM
+//! tree into a sink piece by piece instead of building it up in memory.
+
+use super::HtmlRenderError;
+use crate::backend::{DomBackend, Node, Renderer};
+use crate::hydration;
+use crate::virtual_dom::vtag::{
+    children_namespace, element_namespace, is_raw_text_element, is_valid_name, is_void_element,
+    HTML_NAMESPACE,
+};
+use crate::virtual_dom::{Attributes, VComp, VList, VNode, VTag, VText};
+use std::borrow::Cow;
+use std::{fmt, io};
+
+/// A destination for HTML produced by the streaming serializer.
+///
+/// Use [`IoWriter`] to stream into an [`io::Write`] such as a socket or an HTTP response body,
+/// and [`FmtWriter`] to stream into a [`fmt::Write`] such as a `String`.
+pub trait HtmlWrite {
+    /// Writes a chunk of HTML.
+    fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError>;
+
+    /// Called whenever a component has been written completely. Sinks which buffer their
+    /// output should hand it over to the underlying destination here.
+    fn flush(&mut self) -> Result<(), HtmlRenderError> {
+        Ok(())
+    }
+}
+
+impl<W: HtmlWrite + ?Sized> HtmlWrite for &mut W {
+    fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+        (**self).write_html(html)
+    }
+
+    fn flush(&mut self) -> Result<(), HtmlRenderError> {
+        (**self).flush()
+    }
+}
+
+/// Adapts an [`io::Write`] to [`HtmlWrite`]. The inner writer is flushed at every component
+/// boundary, so a response can start going out before the whole tree has been written.
+#[derive(Debug)]
+pub struct IoWriter<W: io::Write> {
+    inner: W,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    /// Wraps `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this `IoWriter`, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> HtmlWrite for IoWriter<W> {
+    fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+        self.inner.write_all(html.as_bytes())?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), HtmlRenderError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+/// Adapts a [`fmt::Write`] to [`HtmlWrite`].
+#[derive(Debug)]
+pub struct FmtWriter<W: fmt::Write> {
+    inner: W,
+}
+
+impl<W: fmt::Write> FmtWriter<W> {
+    /// Wraps `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this `FmtWriter`, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> HtmlWrite for FmtWriter<W> {
+    fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+        self.inner.write_str(html)?;
+        Ok(())
+    }
+}
+
+/// Options which control how HTML is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Writes attributes in an order which only depends on the `html!` source, so that the
+    /// same tree always produces byte-for-byte identical output. Attributes from `html!` keep
+    /// the order the macro emitted them in, while attributes which are held in a map, e.g.
+    /// because they were added at runtime, are sorted by name.
+    ///
+    /// The `type`, `value` and `checked` properties are always written after all other
+    /// attributes, in this order.
+    pub deterministic_attributes: bool,
+}
+
+/// Virtual DOM structures which can be written as HTML.
+pub trait ToHtml {
+    /// Writes the HTML of `self` into `writer` using the default [`RenderOptions`].
+    fn write_html<W: HtmlWrite + ?Sized>(&self, writer: &mut W) -> Result<(), HtmlRenderError> {
+        self.write_html_with_options(writer, &RenderOptions::default())
+    }
+
+    /// Writes the HTML of `self` into `writer` using `options`.
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError>;
+}
+
+impl ToHtml for VNode {
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        write_vnode(self, writer, options, &mut Siblings::new(HTML_NAMESPACE))
+    }
+}
+
+impl ToHtml for VComp {
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        write_vcomp(self, writer, options, &mut Siblings::new(HTML_NAMESPACE))
+    }
+}
+
+impl ToHtml for VList {
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        write_vlist(self, writer, options, &mut Siblings::new(HTML_NAMESPACE))
+    }
+}
+
+impl ToHtml for VText {
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        _options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        Siblings::new(HTML_NAMESPACE).write_text(writer, &self.text)
+    }
+}
+
+impl ToHtml for VTag {
+    fn write_html_with_options<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        options: &RenderOptions,
+    ) -> Result<(), HtmlRenderError> {
+        write_vtag(self, writer, options, &mut Siblings::new(HTML_NAMESPACE))
+    }
+}
+
+/// The state of the element whose children are being written.
+///
+/// The HTML parser of the browser merges adjacent text nodes and drops empty ones, so text is
+/// separated from preceding text by a `<!--,-->` comment and a `<!---->` comment is written in
+/// place of empty text and empty lists. The markers are removed again when hydrating.
+struct Siblings {
+    /// The namespace of the elements among the children
+    namespace: &'static str,
+    /// Whether the node which was written last ends with text
+    after_text: bool,
+    /// Whether markers can be written, which isn't the case for elements that only contain
+    /// text, like `<title>`. Hydration compares their text as a whole.
+    markers: bool,
+}
+
+impl Siblings {
+    fn new(namespace: &'static str) -> Self {
+        Siblings {
+            namespace,
+            after_text: false,
+            markers: true,
+        }
+    }
+
+    /// Escapes and writes `text`.
+    fn write_text<W: HtmlWrite + ?Sized>(
+        &mut self,
+        writer: &mut W,
+        text: &str,
+    ) -> Result<(), HtmlRenderError> {
+        if text.is_empty() {
+            return self.write_placeholder(writer);
+        }
+        self.write_separator(writer)?;
+        self.after_text = true;
+        writer.write_html(&htmlescape::encode_minimal(text))
+    }
+
+    /// Writes a marker in place of a node which wouldn't leave any trace in the HTML.
+    fn write_placeholder<W: HtmlWrite + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<(), HtmlRenderError> {
+        self.after_text = false;
+        self.write_comment(writer, hydration::PLACEHOLDER)
+    }
+
+    /// Separates text which is about to be written from the text before it.
+    fn write_separator<W: HtmlWrite + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<(), HtmlRenderError> {
+        if self.after_text {
+            self.write_comment(writer, hydration::TEXT_SEPARATOR)?;
+        }
+        Ok(())
+    }
+
+    fn write_comment<W: HtmlWrite + ?Sized>(
+        &self,
+        writer: &mut W,
+        data: &str,
+    ) -> Result<(), HtmlRenderError> {
+        if !self.markers {
+            return Ok(());
+        }
+        writer.write_html("<!--")?;
+        writer.write_html(data)?;
+        writer.write_html("-->")
+    }
+}
+
+/// Writes `node` among `siblings`.
+fn write_vnode<W: HtmlWrite + ?Sized>(
+    node: &VNode,
+    writer: &mut W,
+    options: &RenderOptions,
+    siblings: &mut Siblings,
+) -> Result<(), HtmlRenderError> {
+    match node {
+        VNode::VTag(vtag) => write_vtag(vtag, writer, options, siblings),
+        VNode::VText(vtext) => siblings.write_text(writer, &vtext.text),
+        VNode::VComp(vcomp) => write_vcomp(vcomp, writer, options, siblings),
+        VNode::VList(vlist) => write_vlist(vlist, writer, options, siblings),
+        VNode::VRef(node) => match Renderer::node_as_text(node) {
+            Some(text) => siblings.write_text(writer, &Renderer::text_data(&text)),
+            None => {
+                siblings.after_text = false;
+                write_dom_node(node, writer)
+            }
+        },
+        VNode::VRaw(vraw) if vraw.html.is_empty() => siblings.write_placeholder(writer),
+        VNode::VRaw(vraw) => {
+            if !vraw.html.starts_with('<') {
+                siblings.write_separator(writer)?;
+            }
+            siblings.after_text = !vraw.html.ends_with('>');
+            writer.write_html(&vraw.html)
+        }
+    }
+}
+
+fn write_vcomp<W: HtmlWrite + ?Sized>(
+    vcomp: &VComp,
+    writer: &mut W,
+    options: &RenderOptions,
+    siblings: &mut Siblings,
+) -> Result<(), HtmlRenderError> {
+    if let Some(root_vnode) = vcomp.root_vnode() {
+        write_vnode(&root_vnode, writer, options, siblings)?;
+    }
+    writer.flush()
+}
+
+fn write_vlist<W: HtmlWrite + ?Sized>(
+    vlist: &VList,
+    writer: &mut W,
+    options: &RenderOptions,
+    siblings: &mut Siblings,
+) -> Result<(), HtmlRenderError> {
+    if vlist.children.is_empty() {
+        return siblings.write_placeholder(writer);
+    }
+    for child in vlist.children.iter() {
+        write_vnode(child, writer, options, siblings)?;
+    }
+    Ok(())
+}
+
+/// Writes an element. The names of HTML elements and their attributes are lower-cased, while
+/// those of SVG and MathML elements are case-sensitive and written as they are, e.g.
+/// `foreignObject`, `viewBox` or `xlink:href`.
+fn write_vtag<W: HtmlWrite + ?Sized>(
+    vtag: &VTag,
+    writer: &mut W,
+    options: &RenderOptions,
+    siblings: &mut Siblings,
+) -> Result<(), HtmlRenderError> {
+    siblings.after_text = false;
+    let namespace = element_namespace(vtag.tag(), siblings.namespace);
+    let is_html = namespace == HTML_NAMESPACE;
+    let tag_name = if is_html {
+        vtag.tag().to_lowercase()
+    } else {
+        vtag.tag().to_owned()
+    };
+    if !is_valid_name(&tag_name) {
+        return Err(HtmlRenderError::InvalidTagName(tag_name));
+    }
+
+    writer.write_html("<")?;
+    writer.write_html(&tag_name)?;
+
+    let attributes: Box<dyn Iterator<Item = (&str, &str)>> = match &vtag.attributes {
+        Attributes::IndexMap(map) if options.deterministic_attributes => {
+            let mut sorted: Vec<_> = map.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+            sorted.sort_by_cached_key(|(key, _)| key.to_lowercase());
+            Box::new(sorted.into_iter())
+        }
+        attributes => attributes.iter(),
+    };
+    for (key_unclean, value) in attributes {
+        let key = if is_html {
+            Cow::Owned(key_unclean.to_lowercase())
+        } else {
+            Cow::Borrowed(key_unclean)
+        };
+        if !is_valid_name(&key) {
+            return Err(HtmlRenderError::InvalidAttributeName(key.into_owned()));
+        }
+        write_attribute(writer, &key, value)?;
+    }
+
+    if let Some(kind) = &vtag.kind {
+        write_attribute(writer, "type", kind)?;
+    }
+
+    // textareas' innerHTML properties are specified via the `value` prop which doesn't
+    // exist in HTML, so we defer this prop's serialization until later in the process.
+    let is_textarea = is_html && tag_name == "textarea";
+    if !is_textarea {
+        if let Some(value) = &vtag.value {
+            write_attribute(writer, "value", value)?;
+        }
+    }
+
+    if vtag.checked {
+        writer.write_html(" checked")?;
+    }
+
+    writer.write_html(">")?;
+
+    // Void elements have no end tag and can't hold any content
+    if is_html && is_void_element(&tag_name) {
+        return Ok(());
+    }
+
+    if is_textarea {
+        if let Some(value) = &vtag.value {
+            writer.write_html(&htmlescape::encode_minimal(value))?;
+        }
+    } else if is_html && is_raw_text_element(&tag_name) {
+        for child in vtag.children.children.iter() {
+            write_raw_text(child, &tag_name, writer, options)?;
+        }
+    } else if !vtag.children.is_empty() {
+        let mut children = Siblings::new(children_namespace(namespace, &tag_name));
+        children.markers = !(is_html && tag_name == "title");
+        write_vlist(&vtag.children, writer, options, &mut children)?;
+    }
+
+    writer.write_html("</")?;
+    writer.write_html(&tag_name)?;
+    writer.write_html(">")
+}
+
+pub(super) fn write_attribute<W: HtmlWrite + ?Sized>(
+    writer: &mut W,
+    key: &str,
+    value: &str,
+) -> Result<(), HtmlRenderError> {
+    writer.write_html(" ")?;
+    writer.write_html(key)?;
+    writer.write_html("=\"")?;
+    writer.write_html(&htmlescape::encode_attribute(value))?;
+    writer.write_html("\"")
+}
+
+/// Writes the text inside a raw text element without escaping it. Only an end tag for the
+/// element itself would end the text early, so the `/` of any such sequence is escaped with a
+/// backslash, which both JavaScript and CSS ignore.
+fn write_raw_text<W: HtmlWrite + ?Sized>(
+    node: &VNode,
+    tag_name: &str,
+    writer: &mut W,
+    options: &RenderOptions,
+) -> Result<(), HtmlRenderError> {
+    match node {
+        VNode::VText(vtext) => writer.write_html(&neutralize_end_tag(&vtext.text, tag_name)),
+        VNode::VList(vlist) => {
+            for child in vlist.children.iter() {
+                write_raw_text(child, tag_name, writer, options)?;
+            }
+            Ok(())
+        }
+        VNode::VComp(vcomp) => {
+            if let Some(root_vnode) = vcomp.root_vnode() {
+                write_raw_text(&root_vnode, tag_name, writer, options)?;
+            }
+            writer.flush()
+        }
+        VNode::VTag(_) | VNode::VRef(_) | VNode::VRaw(_) => {
+            node.write_html_with_options(writer, options)
+        }
+    }
+}
+
+/// Writes a node of the DOM and its descendants. Attributes are written as they are set on the
+/// elements, the state of form controls which differs from their attributes is not.
+pub(crate) fn write_dom_node<W: HtmlWrite + ?Sized>(
+    node: &Node,
+    writer: &mut W,
+) -> Result<(), HtmlRenderError> {
+    if let Some(text) = Renderer::node_as_text(node) {
+        return writer.write_html(&htmlescape::encode_minimal(&Renderer::text_data(&text)));
+    }
+    if let Some(data) = Renderer::node_comment_data(node) {
+        writer.write_html("<!--")?;
+        writer.write_html(&neutralize_comment(&data))?;
+        return writer.write_html("-->");
+    }
+    let element = Renderer::node_as_element(node).ok_or(HtmlRenderError::UnserializableVRef)?;
+    let tag_name = Renderer::element_local_name(&element);
+    if !is_valid_name(&tag_name) {
+        return Err(HtmlRenderError::InvalidTagName(tag_name));
+    }
+
+    writer.write_html("<")?;
+    writer.write_html(&tag_name)?;
+    for key in Renderer::element_attribute_names(&element) {
+        if !is_valid_name(&key) {
+            return Err(HtmlRenderError::InvalidAttributeName(key));
+        }
+        let value = Renderer::element_get_attribute(&element, &key).unwrap_or_default();
+        write_attribute(writer, &key, &value)?;
+    }
+    writer.write_html(">")?;
+
+    let is_html = Renderer::element_namespace_uri(&element).as_deref() == Some(HTML_NAMESPACE);
+    if is_html && is_void_element(&tag_name) {
+        return Ok(());
+    }
+    let is_raw_text = is_html && is_raw_text_element(&tag_name);
+    let mut child = Renderer::element_first_child(&element);
+    while let Some(node) = child {
+        match Renderer::node_as_text(&node) {
+            Some(text) if is_raw_text => {
+                let text = Renderer::text_data(&text);
+                writer.write_html(&neutralize_end_tag(&text, &tag_name))?;
+            }
+            _ => write_dom_node(&node, writer)?,
+        }
+        child = Renderer::node_next_sibling(&node);
+    }
+
+    writer.write_html("</")?;
+    writer.write_html(&tag_name)?;
+    writer.write_html(">")
+}
+
+fn neutralize_end_tag<'a>(text: &'a str, tag_name: &str) -> Cow<'a, str> {
+    let needle = format!("</{}", tag_name);
+    let lowercase = text.to_ascii_lowercase();
+    if !lowercase.contains(&needle) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len() + 1);
+    let mut last = 0;
+    for (index, _) in lowercase.match_indices(&needle) {
+        result.push_str(&text[last..index]);
+        result.push_str("<\\/");
+        last = index + 2;
+    }
+    result.push_str(&text[last..]);
+    Cow::Owned(result)
+}
+
+/// Keeps the data of a comment from ending it early, which a `-->` or `--!>` in it would, or a
+/// `>` or `->` at its start. Their `>` is written as `&gt;`, which isn't decoded in comments.
+fn neutralize_comment(data: &str) -> Cow<'_, str> {
+    let ends_early = data.starts_with('>')
+        || data.starts_with("->")
+        || data.contains("-->")
+        || data.contains("--!>");
+    if !ends_early {
+        return Cow::Borrowed(data);
+    }
+
+    let mut result = data.replace("-->", "--&gt;").replace("--!>", "--!&gt;");
+    if result.starts_with('>') {
+        result.replace_range(..1, "&gt;");
+    } else if result.starts_with("->") {
+        result.replace_range(..2, "-&gt;");
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html;
+    use crate::html::Html;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Records every chunk and flush so tests can check how output is streamed.
+    #[derive(Default)]
+    struct Recorder {
+        chunks: Vec<String>,
+        flushes: Vec<usize>,
+    }
+
+    impl HtmlWrite for Recorder {
+        fn write_html(&mut self, html: &str) -> Result<(), HtmlRenderError> {
+            self.chunks.push(html.to_owned());
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), HtmlRenderError> {
+            self.flushes.push(self.chunks.len());
+            Ok(())
+        }
+    }
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_writes_into_io_and_fmt_writers() {
+        let node = html! {
+            <ul class="list">
+                <li>{ "a & b" }</li>
+                <li />
+            </ul>
+        };
+        let expected = "<ul class=\"list\"><li>a &amp; b</li><li></li></ul>";
+
+        let mut bytes = IoWriter::new(Vec::new());
+        node.write_html(&mut bytes).expect("HTML write error");
+        assert_eq!(String::from_utf8(bytes.into_inner()).unwrap(), expected);
+
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(string, expected);
+    }
+
+    #[test]
+    fn it_streams_in_chunks() {
+        let node = html! {
+            <div>
+                <p>{ "first" }</p>
+                <p>{ "second" }</p>
+            </div>
+        };
+
+        let mut recorder = Recorder::default();
+        node.write_html(&mut recorder).expect("HTML write error");
+        assert!(recorder.chunks.len() > 1);
+        assert_eq!(
+            recorder.chunks.concat(),
+            "<div><p>first</p><p>second</p></div>"
+        );
+        assert!(recorder.flushes.is_empty());
+    }
+
+    #[test]
+    fn it_surfaces_io_errors() {
+        let node = html! { <p>{ "lost" }</p> };
+        assert!(matches!(
+            node.write_html(&mut IoWriter::new(FailingWriter)),
+            Err(HtmlRenderError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn it_writes_raw_html_verbatim() {
+        let raw = Html::from_html_unchecked("<b>bold</b> & <i>unescaped</i>");
+        let node = html! { <p>{ raw }</p> };
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(string, "<p><b>bold</b> & <i>unescaped</i></p>");
+    }
+
+    #[test]
+    fn it_writes_referenced_nodes() {
+        let document = crate::utils::document();
+        let div = document.create_element("div").unwrap();
+        div.set_inner_html(
+            "<a href=\"/x\">a &amp; b</a><br><style>p > a {}</style><svg><circle/></svg>",
+        );
+
+        let node = html! { <main>{ VNode::VRef(div.into()) }</main> };
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(
+            string,
+            "<main><div><a href=\"&#x2F;x\">a &amp; b</a><br><style>p > a {}</style>\
+             <svg><circle></circle></svg></div></main>"
+        );
+    }
+
+    #[test]
+    fn comments_in_referenced_nodes_cannot_end_early() {
+        let document = crate::utils::document();
+        let div = document.create_element("div").unwrap();
+        for data in ["note", "--><script>alert(1)</script>", "> a --!> b", "->"] {
+            div.append_child(&document.create_comment(data)).unwrap();
+        }
+
+        let node = html! { <main>{ VNode::VRef(div.into()) }</main> };
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(
+            string,
+            "<main><div><!--note--><!----&gt;<script>alert(1)</script>-->\
+             <!--&gt; a --!&gt; b--><!---&gt;--></div></main>"
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_case_of_svg_and_mathml_names() {
+        let node = html! {
+            <div data-Kind="figure">
+                <svg viewBox="0 0 10 10">
+                    <defs><linearGradient gradientUnits="userSpaceOnUse" /></defs>
+                    <use xlink:href="#shape" />
+                    <foreignObject><p data-Kind="caption">{ "text" }</p></foreignObject>
+                </svg>
+                <math><mi mathVariant="normal"><b>{ "x" }</b></mi></math>
+            </div>
+        };
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(
+            string,
+            "<div data-kind=\"figure\"><svg viewBox=\"0&#x20;0&#x20;10&#x20;10\">\
+             <defs><linearGradient gradientUnits=\"userSpaceOnUse\"></linearGradient></defs>\
+             <use xlink:href=\"&#x23;shape\"></use>\
+             <foreignObject><p data-kind=\"caption\">text</p></foreignObject></svg>\
+             <math><mi mathVariant=\"normal\"><b>x</b></mi></math></div>"
+        );
+    }
+
+    #[test]
+    fn it_marks_text_boundaries() {
+        let node: Html = html! {
+            <div>
+                <p>{ "a" }{ "b" }{ "" }{ "c" }<></><b>{ "d" }</b>{ "e" }</p>
+                <title>{ "f" }{ "g" }</title>
+                <br />
+            </div>
+        };
+        let mut string = String::new();
+        node.write_html(&mut FmtWriter::new(&mut string))
+            .expect("HTML write error");
+        assert_eq!(
+            string,
+            "<div><p>a<!--,-->b<!---->c<!----><b>d</b>e</p><title>fg</title><br></div>"
+        );
+    }
+}
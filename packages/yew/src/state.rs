@@ -0,0 +1,486 @@
+//! This module contains the support for embedding component properties into rendered pages.
+//!
+//! When a page is rendered on the server, the props of the root component (and of selected
+//! child components) can be encoded into inert `<script type="application/yew-state">` blocks
+//! following the markup. On the client, `App::hydrate_from_document` decodes the root props
+//! again so that hydration starts from exactly the same state the server rendered with.
+//! Islands, see `yew::island`, embed the props of their component the same way.
+
+use crate::backend::{DomBackend, Element, Renderer};
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "msgpack"))]
+use crate::format::Binary;
+#[cfg(feature = "bincode")]
+use crate::format::Bincode;
+#[cfg(feature = "cbor")]
+use crate::format::Cbor;
+#[cfg(feature = "msgpack")]
+use crate::format::MsgPack;
+use crate::format::{Json, Text};
+use crate::utils::document;
+use crate::virtual_dom::{VTag, VText};
+use anyhow::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error as ThisError;
+
+#[cfg(feature = "static_render")]
+pub(crate) use self::collector::{capture_props, Capture, EmbeddedState};
+
+/// The `type` of the script blocks which contain embedded state.
+pub const STATE_SCRIPT_TYPE: &str = "application/yew-state";
+
+/// The codecs which can be used to embed state into a page.
+///
+/// Binary formats are stored base64 encoded and are only available if the corresponding
+/// `bincode`, `cbor` or `msgpack` feature is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StateFormat {
+    /// Encodes state with `serde_json`.
+    #[default]
+    Json,
+    /// Encodes state with `bincode`.
+    #[cfg(feature = "bincode")]
+    Bincode,
+    /// Encodes state with `serde_cbor`.
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// Encodes state with `rmp_serde`.
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+}
+
+impl StateFormat {
+    /// Returns the name which is stored in the `data-format` attribute of a state block.
+    pub fn name(self) -> &'static str {
+        match self {
+            StateFormat::Json => "json",
+            #[cfg(feature = "bincode")]
+            StateFormat::Bincode => "bincode",
+            #[cfg(feature = "cbor")]
+            StateFormat::Cbor => "cbor",
+            #[cfg(feature = "msgpack")]
+            StateFormat::MsgPack => "msgpack",
+        }
+    }
+
+    /// Returns the format with the given name if it is available.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(StateFormat::Json),
+            #[cfg(feature = "bincode")]
+            "bincode" => Some(StateFormat::Bincode),
+            #[cfg(feature = "cbor")]
+            "cbor" => Some(StateFormat::Cbor),
+            #[cfg(feature = "msgpack")]
+            "msgpack" => Some(StateFormat::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Encodes `value` to text which can be placed inside of a script block.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<String, Error> {
+        match self {
+            // `<` can only occur inside of JSON strings where it may as well be escaped, which
+            // keeps sequences like `</script>` or `<!--` out of the script block.
+            StateFormat::Json => Ok(Text::from(Json(value))?.replace('<', "\\u003c")),
+            #[cfg(feature = "bincode")]
+            StateFormat::Bincode => encode_binary(Bincode(value).into()),
+            #[cfg(feature = "cbor")]
+            StateFormat::Cbor => encode_binary(Cbor(value).into()),
+            #[cfg(feature = "msgpack")]
+            StateFormat::MsgPack => encode_binary(MsgPack(value).into()),
+        }
+    }
+
+    /// Decodes a value from the content of a script block.
+    pub fn decode<T: DeserializeOwned>(self, data: &str) -> Result<T, Error> {
+        match self {
+            StateFormat::Json => Json::from(Ok(data.to_owned())).0,
+            #[cfg(feature = "bincode")]
+            StateFormat::Bincode => Bincode::from(decode_binary(data)).0,
+            #[cfg(feature = "cbor")]
+            StateFormat::Cbor => Cbor::from(decode_binary(data)).0,
+            #[cfg(feature = "msgpack")]
+            StateFormat::MsgPack => MsgPack::from(decode_binary(data)).0,
+        }
+    }
+}
+
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "msgpack"))]
+fn encode_binary(data: Binary) -> Text {
+    data.map(base64::encode)
+}
+
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "msgpack"))]
+fn decode_binary(data: &str) -> Binary {
+    base64::decode(data.trim()).map_err(Error::from)
+}
+
+/// Represents errors which occur when reading embedded state.
+#[derive(Debug, ThisError)]
+pub enum StateError {
+    /// The page doesn't contain a matching state block
+    #[error("cannot find embedded state for {0}")]
+    NotFound(String),
+
+    /// The state block uses a format which isn't available
+    #[error("cannot decode embedded state in unknown format `{0}`")]
+    UnknownFormat(String),
+
+    /// The content of the state block couldn't be decoded
+    #[error("cannot decode embedded state: {0}")]
+    Decode(Error),
+}
+
+/// Decodes the props of all instances of a child component which were embedded under `key`
+/// while rendering on the server, in the order the components were created.
+///
+/// The state blocks are left in the document, so this can also be called from within the
+/// `create` method of a component which is being hydrated.
+pub fn embedded_props<P: DeserializeOwned>(key: &str) -> Result<Vec<P>, StateError> {
+    let script = find_block(|script| script.get_attribute("data-key").as_deref() == Some(key))
+        .ok_or_else(|| StateError::NotFound(format!("key `{}`", key)))?;
+    decode_block(&script)
+}
+
+/// Finds the first state block holding the props of a root component.
+pub(crate) fn root_block() -> Result<Element, StateError> {
+    find_block(|script| script.has_attribute("data-root"))
+        .ok_or_else(|| StateError::NotFound("the root component".into()))
+}
+
+/// Decodes the content of a state block according to its `data-format` attribute.
+pub(crate) fn decode_block<T: DeserializeOwned>(script: &Element) -> Result<T, StateError> {
+    let name = script
+        .get_attribute("data-format")
+        .unwrap_or_else(|| StateFormat::default().name().to_owned());
+    let format = StateFormat::from_name(&name).ok_or(StateError::UnknownFormat(name))?;
+    let data = script.text_content().unwrap_or_default();
+    format.decode(&data).map_err(StateError::Decode)
+}
+
+/// Creates a state block holding `data`, which was encoded with `format`.
+pub(crate) fn state_block(format: StateFormat, data: String) -> VTag {
+    let mut script = VTag::new("script");
+    script.add_attribute("type", STATE_SCRIPT_TYPE);
+    script.add_attribute("data-format", format.name());
+    script.add_child(VText::new(data).into());
+    script
+}
+
+/// Returns `true` if `element` is a state block.
+pub(crate) fn is_state_block(element: &Element) -> bool {
+    Renderer::element_local_name(element).eq_ignore_ascii_case("script")
+        && element.get_attribute("type").as_deref() == Some(STATE_SCRIPT_TYPE)
+}
+
+/// Finds the first state block in the document for which `f` returns `true`. The attributes
+/// are compared directly instead of with a selector, so keys don't have to be escaped.
+fn find_block(f: impl Fn(&Element) -> bool) -> Option<Element> {
+    fn find(parent: &Element, f: &dyn Fn(&Element) -> bool) -> Option<Element> {
+        let mut cursor = Renderer::element_first_child(parent);
+        while let Some(node) = cursor {
+            if let Some(element) = Renderer::node_as_element(&node) {
+                if is_state_block(&element) && f(&element) {
+                    return Some(element);
+                }
+                if let Some(found) = find(&element, f) {
+                    return Some(found);
+                }
+            }
+            cursor = Renderer::node_next_sibling(&node);
+        }
+        None
+    }
+
+    find(&document().document_element()?, &f)
+}
+
+#[cfg(feature = "static_render")]
+mod collector {
+    use super::{state_block, StateFormat};
+    use crate::html::Component;
+    use crate::smr::{HtmlRenderError, HtmlWrite, RenderOptions, ToHtml};
+    use crate::utils;
+    use anyhow::Error;
+    use serde::Serialize;
+    use std::any::{Any, TypeId};
+    use std::cell::RefCell;
+    use std::fmt;
+    use std::mem;
+
+    thread_local! {
+        /// Child props which are captured while a component tree is being rendered.
+        static CAPTURED: RefCell<Option<Vec<ChildState>>> = const { RefCell::new(None) };
+    }
+
+    type RootEncoder<COMP> =
+        fn(StateFormat, &<COMP as Component>::Properties) -> Result<String, Error>;
+    type ChildEncoder = fn(StateFormat, &[Box<dyn Any>]) -> Result<String, Error>;
+
+    /// The state which an `App` embeds into the HTML it renders.
+    pub(crate) struct EmbeddedState<COMP: Component> {
+        format: StateFormat,
+        root: Option<RootEncoder<COMP>>,
+        children: Vec<ChildState>,
+    }
+
+    impl<COMP: Component> Default for EmbeddedState<COMP> {
+        fn default() -> Self {
+            EmbeddedState {
+                format: StateFormat::default(),
+                root: None,
+                children: Vec::new(),
+            }
+        }
+    }
+
+    impl<COMP: Component> fmt::Debug for EmbeddedState<COMP> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("EmbeddedState")
+                .field("format", &self.format)
+                .field("root", &self.root.is_some())
+                .field("children", &self.children)
+                .finish()
+        }
+    }
+
+    impl<COMP: Component> EmbeddedState<COMP> {
+        pub(crate) fn embed_root(&mut self, format: StateFormat)
+        where
+            COMP::Properties: Serialize,
+        {
+            self.format = format;
+            self.root = Some(|format, props| format.encode(props));
+        }
+
+        pub(crate) fn embed_child<CHILD>(&mut self, key: &'static str)
+        where
+            CHILD: Component,
+            CHILD::Properties: Serialize,
+        {
+            self.children.retain(|child| child.key != key);
+            self.children.push(ChildState {
+                type_id: TypeId::of::<CHILD>(),
+                key,
+                encode: encode_children::<CHILD>,
+                props: Vec::new(),
+            });
+        }
+
+        /// Encodes the root props. This has to happen before they are moved into the component.
+        pub(crate) fn encode_root(
+            &self,
+            props: &COMP::Properties,
+        ) -> Result<Option<String>, HtmlRenderError> {
+            self.root
+                .map(|encode| encode(self.format, props).map_err(HtmlRenderError::State))
+                .transpose()
+        }
+
+        /// Prepares capturing the props of the selected child components.
+        pub(crate) fn start_capture(&self) -> Capture {
+            let children = self
+                .children
+                .iter()
+                .map(|child| ChildState {
+                    props: Vec::new(),
+                    ..*child
+                })
+                .collect();
+            Capture { children }
+        }
+
+        /// Writes the state blocks for the root props and the captured child props.
+        pub(crate) fn write_blocks<W: HtmlWrite>(
+            &self,
+            root: Option<String>,
+            capture: Capture,
+            writer: &mut W,
+            options: &RenderOptions,
+        ) -> Result<(), HtmlRenderError> {
+            if let Some(data) = root {
+                let mut script = state_block(self.format, data);
+                script.add_attribute("data-root", "");
+                script.write_html_with_options(writer, options)?;
+            }
+            for child in capture
+                .children
+                .iter()
+                .filter(|child| !child.props.is_empty())
+            {
+                let data =
+                    (child.encode)(self.format, &child.props).map_err(HtmlRenderError::State)?;
+                let mut script = state_block(self.format, data);
+                script.add_attribute("data-key", child.key);
+                script.write_html_with_options(writer, options)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// The child props captured while rendering a single component tree.
+    pub(crate) struct Capture {
+        children: Vec<ChildState>,
+    }
+
+    impl Capture {
+        /// Runs `f` while capturing the props of the components it creates. Captures can be
+        /// nested and interleaved, e.g. by asynchronous renders on the same thread.
+        pub(crate) fn run<R>(&mut self, f: impl FnOnce() -> R) -> R {
+            let children = mem::take(&mut self.children);
+            let (result, children) = utils::scoped_take(&CAPTURED, children, f);
+            self.children = children.unwrap_or_default();
+            result
+        }
+    }
+
+    struct ChildState {
+        type_id: TypeId,
+        key: &'static str,
+        encode: ChildEncoder,
+        props: Vec<Box<dyn Any>>,
+    }
+
+    impl fmt::Debug for ChildState {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ChildState")
+                .field("key", &self.key)
+                .field("props", &self.props.len())
+                .finish()
+        }
+    }
+
+    fn encode_children<CHILD>(format: StateFormat, props: &[Box<dyn Any>]) -> Result<String, Error>
+    where
+        CHILD: Component,
+        CHILD::Properties: Serialize,
+    {
+        let props: Vec<&CHILD::Properties> = props
+            .iter()
+            .filter_map(|props| props.downcast_ref())
+            .collect();
+        format.encode(&props)
+    }
+
+    /// Records the props of a component which is being mounted if they were selected to be
+    /// embedded into the page.
+    pub(crate) fn capture_props<COMP: Component>(props: &COMP::Properties) {
+        CAPTURED.with(|captured| {
+            if let Some(children) = captured.borrow_mut().as_mut() {
+                for child in children.iter_mut() {
+                    if child.type_id == TypeId::of::<COMP>() {
+                        child.props.push(Box::new(props.clone()));
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Props {
+        text: String,
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let props = Props {
+            text: "</script><!--".into(),
+        };
+        let data = StateFormat::Json.encode(&props).unwrap();
+        assert!(!data.contains('<'));
+        assert_eq!(StateFormat::Json.decode::<Props>(&data).unwrap(), props);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        let props = Props {
+            text: "</script>".into(),
+        };
+        let data = StateFormat::Bincode.encode(&props).unwrap();
+        assert!(!data.contains('<'));
+        assert_eq!(StateFormat::Bincode.decode::<Props>(&data).unwrap(), props);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_round_trip() {
+        let props = Props {
+            text: "</script>".into(),
+        };
+        let data = StateFormat::Cbor.encode(&props).unwrap();
+        assert!(!data.contains('<'));
+        assert_eq!(StateFormat::Cbor.decode::<Props>(&data).unwrap(), props);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_round_trip() {
+        let props = Props {
+            text: "</script>".into(),
+        };
+        let data = StateFormat::MsgPack.encode(&props).unwrap();
+        assert!(!data.contains('<'));
+        assert_eq!(StateFormat::MsgPack.decode::<Props>(&data).unwrap(), props);
+    }
+
+    #[test]
+    fn it_reads_embedded_child_props() {
+        let body = document().body().unwrap();
+        let script = document().create_element("script").unwrap();
+        script.set_attribute("type", STATE_SCRIPT_TYPE).unwrap();
+        script.set_attribute("data-key", "item").unwrap();
+        let data = document().create_text_node(r#"[{"text":"a"},{"text":"b"}]"#);
+        script.append_child(&data).unwrap();
+        body.append_child(&script).unwrap();
+
+        let props: Vec<Props> = embedded_props("item").unwrap();
+        assert_eq!(
+            props,
+            vec![Props { text: "a".into() }, Props { text: "b".into() }]
+        );
+        assert!(matches!(
+            embedded_props::<Props>("missing"),
+            Err(StateError::NotFound(_))
+        ));
+
+        script.set_attribute("data-format", "yaml").unwrap();
+        assert!(matches!(
+            embedded_props::<Props>("item"),
+            Err(StateError::UnknownFormat(_))
+        ));
+        body.remove_child(&script).unwrap();
+    }
+
+    #[test]
+    fn keys_are_not_interpreted_as_selectors() {
+        let body = document().body().unwrap();
+        let script = document().create_element("script").unwrap();
+        script.set_attribute("type", STATE_SCRIPT_TYPE).unwrap();
+        script.set_attribute("data-key", r#"a"] , *[b\"#).unwrap();
+        let data = document().create_text_node(r#"[{"text":"a"}]"#);
+        script.append_child(&data).unwrap();
+        body.append_child(&script).unwrap();
+
+        let props: Vec<Props> = embedded_props(r#"a"] , *[b\"#).unwrap();
+        assert_eq!(props, vec![Props { text: "a".into() }]);
+        assert!(matches!(
+            embedded_props::<Props>("a"),
+            Err(StateError::NotFound(_))
+        ));
+        body.remove_child(&script).unwrap();
+    }
+}
@@ -0,0 +1,505 @@
+//! This module contains `TestRenderer`, which mounts components to the in-memory DOM so that
+//! they can be tested with a plain `cargo test`, without a browser. Events are simulated by
+//! dispatching them to the in-memory elements, e.g. with `TestRenderer::click`, and reach the
+//! listeners of the `html!` macro just like in a browser.
+//!
+//! *This module is only available if the in-memory DOM is the selected backend, i.e. if the
+//! `static_render` feature is enabled and the crate isn't built for a browser with `web_sys`.*
+//!
+//! ```
+//! # use yew::prelude::*;
+//! use yew::testing::TestRenderer;
+//!
+//! struct Counter {
+//!     link: ComponentLink<Self>,
+//!     count: usize,
+//! }
+//!
+//! impl Component for Counter {
+//!     type Message = ();
+//!     type Properties = ();
+//!
+//!     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+//!         Counter { link, count: 0 }
+//!     }
+//!
+//!     fn update(&mut self, _: Self::Message) -> ShouldRender {
+//!         self.count += 1;
+//!         true
+//!     }
+//!
+//!     fn change(&mut self, _: Self::Properties) -> ShouldRender {
+//!         false
+//!     }
+//!
+//!     fn view(&self) -> Html {
+//!         html! { <p onclick=self.link.callback(|_| ())>{ self.count }</p> }
+//!     }
+//! }
+//!
+//! let counter = TestRenderer::<Counter>::mount(());
+//! assert_eq!(counter.html(), "<p>0</p>");
+//! counter.send_message(());
+//! assert_eq!(counter.html(), "<p>1</p>");
+//! counter.click("p");
+//! assert_eq!(counter.html(), "<p>2</p>");
+//! ```
+
+use crate::backend::{DomBackend, Element, Event, Renderer, SelectElement};
+use crate::html::{Component, ComponentLink, Scoped};
+use crate::runtime::Runtime;
+use crate::smr::{write_dom_node, FmtWriter, TaskSet};
+use crate::App;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A component which is mounted to a detached element of the in-memory DOM for testing.
+///
+/// Every renderer has its own runtime, so the components of different renderers never run each
+/// other's tasks. Futures which the components spawn, e.g. with `ComponentLink::send_future`,
+/// are driven by `flush`. The component is destroyed when the renderer is dropped.
+pub struct TestRenderer<COMP: Component> {
+    runtime: Runtime,
+    tasks: Rc<TaskSet>,
+    root: Element,
+    link: ComponentLink<COMP>,
+}
+
+impl<COMP: Component> fmt::Debug for TestRenderer<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestRenderer")
+            .field("root", &self.root)
+            .field("link", &self.link)
+            .finish()
+    }
+}
+
+impl<COMP: Component> TestRenderer<COMP> {
+    /// Mounts the component with `props` and flushes its first render.
+    pub fn mount(props: COMP::Properties) -> Self {
+        let runtime = Runtime::new();
+        let tasks = Rc::new(TaskSet::default());
+        let root = Renderer::create_element("div");
+        let link = tasks.enter(|| {
+            App::<COMP>::new()
+                .with_runtime(runtime.clone())
+                .mount_with_props(root.clone(), props)
+        });
+        let renderer = TestRenderer {
+            runtime,
+            tasks,
+            root,
+            link,
+        };
+        renderer.flush();
+        renderer
+    }
+
+    /// Returns the link of the mounted component.
+    pub fn link(&self) -> &ComponentLink<COMP> {
+        &self.link
+    }
+
+    /// Returns the mounted component.
+    ///
+    /// # Panics
+    /// If the component failed to mount.
+    pub fn component(&self) -> impl Deref<Target = COMP> + '_ {
+        self.link
+            .get_component()
+            .expect("the component isn't mounted")
+    }
+
+    /// Returns the element the component is mounted to. This is the live in-memory tree, which
+    /// can be inspected and changed like the DOM of a browser.
+    pub fn root(&self) -> &Element {
+        &self.root
+    }
+
+    /// Returns the first element inside of the root which matches `selectors`.
+    pub fn query_selector(&self, selectors: &str) -> Option<Element> {
+        self.root.query_selector(selectors).ok().flatten()
+    }
+
+    /// Returns all elements inside of the root which match `selectors`.
+    pub fn query_selector_all(&self, selectors: &str) -> Vec<Element> {
+        self.root.query_selector_all(selectors).unwrap_or_default()
+    }
+
+    /// Dispatches `event` to the first element which matches `selectors` and flushes the
+    /// resulting updates. Returns `false` if a listener called `prevent_default`.
+    ///
+    /// # Panics
+    /// If no element matches `selectors`.
+    pub fn dispatch(&self, selectors: &str, event: Event) -> bool {
+        let element = self.expect_element(selectors);
+        self.act(|| element.dispatch_event(&event))
+    }
+
+    /// Clicks the first element which matches `selectors`, which triggers its `onclick`
+    /// listeners and those of its ancestors.
+    ///
+    /// # Panics
+    /// If no element matches `selectors`.
+    pub fn click(&self, selectors: &str) -> bool {
+        self.dispatch(selectors, Event::new("click"))
+    }
+
+    /// Sets the value of the first input or textarea which matches `selectors` to `value`, as
+    /// if it was typed in, and triggers its `oninput` listeners.
+    ///
+    /// # Panics
+    /// If no input or textarea matches `selectors`.
+    pub fn input(&self, selectors: &str, value: &str) {
+        self.set_value(selectors, value);
+        self.dispatch(selectors, Event::new("input"));
+    }
+
+    /// Sets the value of the first input, textarea or select which matches `selectors` to
+    /// `value` and triggers its `onchange` listeners. A select picks its first option with the
+    /// value `value`.
+    ///
+    /// # Panics
+    /// If no input, textarea or select matches `selectors`.
+    pub fn change(&self, selectors: &str, value: &str) {
+        self.set_value(selectors, value);
+        self.dispatch(selectors, Event::new("change"));
+    }
+
+    /// Submits the first form which matches `selectors`, which triggers its `onsubmit`
+    /// listeners. Returns `false` if a listener called `prevent_default`.
+    ///
+    /// # Panics
+    /// If no element matches `selectors`.
+    pub fn submit(&self, selectors: &str) -> bool {
+        self.dispatch(selectors, Event::new("submit"))
+    }
+
+    /// Presses `key`, e.g. `a` or `Enter`, on the first element which matches `selectors`,
+    /// which triggers its `onkeydown` listeners.
+    ///
+    /// # Panics
+    /// If no element matches `selectors`.
+    pub fn key_down(&self, selectors: &str, key: &str) -> bool {
+        self.dispatch(selectors, Event::keyboard("keydown", key))
+    }
+
+    /// Releases `key` on the first element which matches `selectors`, which triggers its
+    /// `onkeyup` listeners.
+    ///
+    /// # Panics
+    /// If no element matches `selectors`.
+    pub fn key_up(&self, selectors: &str, key: &str) -> bool {
+        self.dispatch(selectors, Event::keyboard("keyup", key))
+    }
+
+    fn expect_element(&self, selectors: &str) -> Element {
+        self.query_selector(selectors)
+            .unwrap_or_else(|| panic!("no element matches `{}`", selectors))
+    }
+
+    fn set_value(&self, selectors: &str, value: &str) {
+        let element = self.expect_element(selectors);
+        if let Some(input) = Renderer::element_as_input(&element) {
+            Renderer::input_set_value(&input, value);
+        } else if let Some(textarea) = Renderer::element_as_textarea(&element) {
+            Renderer::textarea_set_value(&textarea, value);
+        } else if let Ok(select) = SelectElement::try_from(element) {
+            select.set_value(value);
+        } else {
+            panic!("`{}` isn't an input, textarea or select", selectors);
+        }
+    }
+
+    /// Sends `msg` to the component and flushes the resulting updates.
+    pub fn send_message(&self, msg: COMP::Message) {
+        self.act(|| self.link.send_message(msg))
+    }
+
+    /// Runs `f`, e.g. to emit a callback, and flushes the resulting updates. Futures which are
+    /// spawned in `f` are driven by this renderer.
+    pub fn act<R>(&self, f: impl FnOnce() -> R) -> R {
+        let result = self.runtime.enter(|| self.tasks.enter(f));
+        self.flush();
+        result
+    }
+
+    /// Runs the tasks which are scheduled for the components and polls the futures they spawned
+    /// until none of them can make progress. Futures which are still waiting, e.g. for a timer,
+    /// are polled again by the next flush.
+    pub fn flush(&self) {
+        self.runtime.enter(|| {
+            self.tasks.enter(|| {
+                self.runtime.run_scheduled();
+                self.tasks.run_until_stalled();
+            })
+        })
+    }
+
+    /// Returns the current markup of the component.
+    pub fn html(&self) -> String {
+        let mut html = String::new();
+        let mut writer = FmtWriter::new(&mut html);
+        let mut child = Renderer::element_first_child(&self.root);
+        while let Some(node) = child {
+            write_dom_node(&node, &mut writer).expect("can't serialize the in-memory DOM");
+            child = Renderer::node_next_sibling(&node);
+        }
+        html
+    }
+}
+
+impl<COMP: Component> Drop for TestRenderer<COMP> {
+    fn drop(&mut self) {
+        let mut link = self.link.clone();
+        self.runtime.enter(|| link.destroy());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ChangeData, FocusEvent, InputData, KeyboardEvent};
+    use crate::html;
+    use crate::html::{Html, Properties, ShouldRender};
+    use std::cell::Cell;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    thread_local! {
+        static DESTROYED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Clone, Properties)]
+    struct Props {
+        label: &'static str,
+    }
+
+    enum Msg {
+        Add(usize),
+        Load,
+    }
+
+    struct Counter {
+        props: Props,
+        link: ComponentLink<Self>,
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Message = Msg;
+        type Properties = Props;
+
+        fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+            Counter {
+                props,
+                link,
+                count: 0,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Message) -> ShouldRender {
+            match msg {
+                Msg::Add(count) => self.count += count,
+                Msg::Load => self.link.send_future(async { Msg::Add(10) }),
+            }
+            true
+        }
+
+        fn change(&mut self, props: Self::Properties) -> ShouldRender {
+            self.props = props;
+            true
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <div class="counter">
+                    <span>{ self.props.label }{ ": " }{ self.count }</span>
+                    <Marker />
+                </div>
+            }
+        }
+    }
+
+    struct Marker;
+
+    impl Component for Marker {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Marker
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! { <br /> }
+        }
+    }
+
+    impl Drop for Marker {
+        fn drop(&mut self) {
+            DESTROYED.with(|destroyed| destroyed.set(destroyed.get() + 1));
+        }
+    }
+
+    #[test]
+    fn it_renders_updates() {
+        let counter = TestRenderer::<Counter>::mount(Props { label: "Clicks" });
+        assert_eq!(
+            counter.html(),
+            "<div class=\"counter\"><span>Clicks: 0</span><br></div>"
+        );
+
+        counter.send_message(Msg::Add(2));
+        assert_eq!(counter.component().count, 2);
+        assert_eq!(
+            counter
+                .query_selector("span")
+                .unwrap()
+                .text_content()
+                .unwrap(),
+            "Clicks: 2"
+        );
+        assert_eq!(counter.query_selector_all(".counter > *").len(), 2);
+    }
+
+    #[test]
+    fn flush_drives_spawned_futures() {
+        let counter = TestRenderer::<Counter>::mount(Props { label: "Items" });
+        counter.act(|| counter.link().send_message(Msg::Load));
+        assert_eq!(counter.component().count, 10);
+
+        counter.act(|| counter.link().callback(|_| Msg::Load).emit(()));
+        assert!(counter.html().contains("Items: 20"));
+    }
+
+    enum FormMsg {
+        Input(InputData),
+        Change(ChangeData),
+        Key(KeyboardEvent),
+        Submit(FocusEvent),
+        Click,
+    }
+
+    #[derive(Default)]
+    struct Form {
+        name: String,
+        color: String,
+        notes: String,
+        keys: Vec<String>,
+        submitted: usize,
+        clicks: usize,
+    }
+
+    struct FormComponent {
+        link: ComponentLink<Self>,
+        form: Form,
+    }
+
+    impl Component for FormComponent {
+        type Message = FormMsg;
+        type Properties = ();
+
+        fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+            FormComponent {
+                link,
+                form: Form::default(),
+            }
+        }
+
+        fn update(&mut self, msg: Self::Message) -> ShouldRender {
+            let form = &mut self.form;
+            match msg {
+                FormMsg::Input(data) => form.name = data.value,
+                FormMsg::Change(ChangeData::Value(value)) => form.notes = value,
+                FormMsg::Change(ChangeData::Select(select)) => form.color = select.value(),
+                FormMsg::Change(ChangeData::Files(_)) => {}
+                FormMsg::Key(event) => form.keys.push(event.key()),
+                FormMsg::Submit(event) => {
+                    event.prevent_default();
+                    form.submitted += 1;
+                }
+                FormMsg::Click => form.clicks += 1,
+            }
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            let link = &self.link;
+            html! {
+                <form onsubmit=link.callback(FormMsg::Submit)>
+                    <input oninput=link.callback(FormMsg::Input)
+                        onkeydown=link.callback(FormMsg::Key) />
+                    <select onchange=link.callback(FormMsg::Change)>
+                        <option value="red">{ "Red" }</option>
+                        <option value="blue">{ "Blue" }</option>
+                    </select>
+                    <textarea onchange=link.callback(FormMsg::Change) />
+                    <button type="button" onclick=link.callback(|_| FormMsg::Click)>
+                        <span>{ "Hello, " }{ &self.form.name }</span>
+                    </button>
+                </form>
+            }
+        }
+    }
+
+    #[test]
+    fn it_simulates_events() {
+        let renderer = TestRenderer::<FormComponent>::mount(());
+
+        renderer.input("input", "Ferris");
+        renderer.key_down("input", "Enter");
+        renderer.change("select", "blue");
+        renderer.change("textarea", "Notes");
+        assert!(!renderer.submit("form"));
+        assert!(renderer.click("button span"));
+
+        let component = renderer.component();
+        assert_eq!(component.form.name, "Ferris");
+        assert_eq!(component.form.keys, ["Enter"]);
+        assert_eq!(component.form.color, "blue");
+        assert_eq!(component.form.notes, "Notes");
+        assert_eq!(component.form.submitted, 1);
+        assert_eq!(component.form.clicks, 1);
+        assert_eq!(
+            renderer
+                .query_selector("span")
+                .unwrap()
+                .text_content()
+                .unwrap(),
+            "Hello, Ferris"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no element matches `a`")]
+    fn it_panics_for_missing_elements() {
+        TestRenderer::<FormComponent>::mount(()).click("a");
+    }
+
+    #[test]
+    fn dropping_the_renderer_destroys_the_component() {
+        let counter = TestRenderer::<Counter>::mount(Props { label: "" });
+        let destroyed = DESTROYED.with(Cell::get);
+        drop(counter);
+        assert_eq!(DESTROYED.with(Cell::get), destroyed + 1);
+    }
+}
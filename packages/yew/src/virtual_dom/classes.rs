@@ -68,13 +68,10 @@ impl IntoIterator for Classes {
     }
 }
 
-impl ToString for Classes {
-    fn to_string(&self) -> String {
-        self.set
-            .iter()
-            .map(Borrow::borrow)
-            .collect::<Vec<_>>()
-            .join(" ")
+impl fmt::Display for Classes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let classes: Vec<&str> = self.set.iter().map(Borrow::borrow).collect();
+        f.write_str(&classes.join(" "))
     }
 }
 
@@ -10,6 +10,8 @@ pub mod vlist;
 #[doc(hidden)]
 pub mod vnode;
 #[doc(hidden)]
+pub mod vraw;
+#[doc(hidden)]
 pub mod vtag;
 #[doc(hidden)]
 pub mod vtext;
@@ -22,7 +24,8 @@ mod classes;
 pub use classes::Classes;
 
 use crate::html::{AnyScope, NodeRef};
-use crate::backend::{Element, EventListener};
+use crate::backend::{DomBackend, Element, EventListener, Node, Renderer};
+use crate::hydration;
 use indexmap::{IndexMap, IndexSet};
 use std::{
     borrow::{Borrow, Cow},
@@ -40,12 +43,16 @@ pub use self::{
     vcomp::{VChild, VComp},
     vlist::VList,
     vnode::VNode,
+    vraw::VRaw,
     vtag::VTag,
     vtext::VText,
 };
 
-use attributes::Attributes;
-use positional_attributes::PositionalAttr;
+pub use self::{
+    attributes::Attributes,
+    listener::{Listener, Listeners},
+    positional_attributes::PositionalAttr,
+};
 use vdiff::{Patch, VDiff};
 
 mod listener {
@@ -56,8 +63,7 @@ mod listener {
         /// Returns the name of the event
         fn kind(&self) -> &'static str;
         /// Attaches a listener to the element.
-        fn attach(&self) -> EventListener;
-        // fn attach(&self, element: &Element) -> EventListener;
+        fn attach(&self, element: &Element) -> EventListener;
     }
 
     impl fmt::Debug for dyn Listener {
@@ -91,7 +97,7 @@ mod positional_attributes {
             value.map(|v| (key, v))
         }
 
-        pub(crate) fn transposed<'a>(&'a self) -> Option<(&'static str, &'a Cow<'static, str>)> {
+        pub(crate) fn transposed(&self) -> Option<(&'static str, &Cow<'static, str>)> {
             let Self(key, value) = self;
             value.as_ref().map(|v| (*key, v))
         }
@@ -243,14 +249,32 @@ mod attributes {
             let mut old_iter = old.iter();
             loop {
                 match (new_iter.next(), old_iter.next()) {
-                    (Some((new_key, new_value)), Some((old_key, old_value))) => {
-                        if new_key != *old_key {
-                            break;
-                        }
+                    (Some((new_key, new_value)), Some((old_key, old_value)))
+                        if new_key == *old_key =>
+                    {
                         if new_value != old_value.as_ref() {
                             out.push(Patch::Replace(new_key, new_value));
                         }
                     }
+                    // keys don't match, we can no longer compare linearly from here on out
+                    (Some(new_attr), Some(old_attr)) => {
+                        for (key, value) in iter::once(new_attr).chain(new_iter) {
+                            match old.get(key) {
+                                Some(old_value) => {
+                                    if value != old_value.as_ref() {
+                                        out.push(Patch::Replace(key, value));
+                                    }
+                                }
+                                None => out.push(Patch::Add(key, value)),
+                            }
+                        }
+                        for (key, _) in iter::once(old_attr).chain(old_iter) {
+                            if !new.contains_key(key) {
+                                out.push(Patch::Remove(*key));
+                            }
+                        }
+                        break;
+                    }
                     // new attributes
                     (Some(attr), None) => {
                         for (key, value) in iter::once(attr).chain(new_iter) {
@@ -281,7 +305,7 @@ mod attributes {
             out
         }
 
-        fn diff<'a>(new: &'a Self, old: &'a Self) -> Vec<Patch<&'static str, &'a str>> {
+        pub(crate) fn diff<'a>(new: &'a Self, old: &'a Self) -> Vec<Patch<&'static str, &'a str>> {
             match (new, old) {
                 (Self::Vec(new), Self::Vec(old)) => Self::diff_vec(new, old),
                 (Self::Vec(new), Self::IndexMap(old)) => {
@@ -381,29 +405,68 @@ pub mod vdiff {
             next_sibling: NodeRef,
             ancestor: Option<VNode>,
         ) -> NodeRef;
+
+        /// Hydrates existing DOM nodes instead of creating new ones.
+        ///
+        /// Walks the nodes of `parent` starting at `cursor` alongside the virtual node. Matching
+        /// nodes are adopted as the node's reference and only patched where they differ, and
+        /// listeners are attached to them. A DOM node that doesn't match is replaced by a
+        /// freshly rendered subtree. Afterwards `cursor` points to the first DOM node that was
+        /// not claimed by this virtual node.
+        ///
+        /// Parameters are the same as in `apply`, except that `next_sibling` is only recorded
+        /// for later renders: during hydration new nodes are always inserted before `cursor`.
+        ///
+        /// Returns a reference to the first node, like `apply`.
+        fn hydrate(
+            &mut self,
+            parent_scope: &AnyScope,
+            parent: &Element,
+            next_sibling: NodeRef,
+            cursor: &mut Option<Node>,
+        ) -> NodeRef;
     }
 }
 
-fn insert_node() {
-    // fn insert_node(node: &Node, parent: &Element, next_sibling: Option<Node>) {
-    todo!("@jon, insert node needs to work");
-    // match next_sibling {
-    //     Some(next_sibling) => parent
-    //         .insert_before(&node, Some(&next_sibling))
-    //         .expect("failed to insert tag before next sibling"),
-    //     None => parent.append_child(node).expect("failed to append child"),
-    // };
-    // match next_sibling {
-    //     Some(next_sibling) => parent
-    //         .insert_before(&node, Some(&next_sibling))
-    //         .expect("failed to insert tag before next sibling"),
-    //     None => parent.append_child(node).expect("failed to append child"),
-    // };
-    // if let Some(next_sibling) = next_sibling {
-    //     parent
-    //         .insert_before(node, &next_sibling)
-    //         .expect("failed to insert tag before next sibling");
-    // } else {
-    //     parent.append_child(node);
-    // }
+/// Renders `vnode` from scratch in place of the DOM node at `cursor`, which could not be
+/// hydrated, and moves the cursor past the replaced node.
+fn hydrate_mismatch(
+    vnode: &mut impl VDiff,
+    parent_scope: &AnyScope,
+    parent: &Element,
+    cursor: &mut Option<Node>,
+) -> NodeRef {
+    let stale = cursor.take();
+    *cursor = stale.as_ref().and_then(Renderer::node_next_sibling);
+    vnode.apply(
+        parent_scope,
+        parent,
+        NodeRef::default(),
+        stale.map(VNode::VRef),
+    )
+}
+
+/// Removes `cursor` and all of its following siblings from `parent`. Used to drop markup which
+/// was left over after hydrating the children of `parent`, which were rendered by the
+/// component of `parent_scope`.
+pub(crate) fn remove_unclaimed(
+    parent_scope: Option<&AnyScope>,
+    parent: &Element,
+    mut cursor: Option<Node>,
+) {
+    while let Some(node) = cursor {
+        hydration::report(parent_scope, parent, Some(&node), "nothing".to_owned());
+        cursor = Renderer::node_next_sibling(&node);
+        Renderer::element_remove_child(parent, &node).expect("can't remove a child");
+    }
+}
+
+fn insert_node(node: &Node, parent: &Element, next_sibling: Option<Node>) {
+    match next_sibling {
+        Some(next_sibling) => Renderer::element_insert_before(parent, node, Some(&next_sibling))
+            .expect("failed to insert tag before next sibling"),
+        None => {
+            Renderer::element_insert_before(parent, node, None).expect("failed to append child")
+        }
+    };
 }
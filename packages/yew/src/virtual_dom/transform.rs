@@ -39,7 +39,7 @@ mod vcomp {
     where
         T: Clone,
     {
-        fn transform(from: &T) -> Option<T> {
+        fn transform(from: &'a T) -> Option<T> {
             Some(from.clone())
         }
     }
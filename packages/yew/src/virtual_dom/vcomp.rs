@@ -8,11 +8,12 @@ use std::fmt;
 use std::ops::Deref;
 
 use super::Element;
-use crate::backend::{DomBackend, Renderer, Node};
+use crate::backend::{DomBackend, Node, Renderer};
 use mountable::{Mountable, PropsWrapper};
 pub use vchild::VChild;
 pub use vcomp::VComp;
 
+#[allow(clippy::module_inception)]
 mod vcomp {
     use super::*;
     /// A virtual component.
@@ -99,7 +100,7 @@ mod vcomp {
                 ancestor.detach(parent);
             }
 
-            let placeholder: Node = Renderer::get_document().create_text_node("").into();
+            let placeholder = Renderer::text_as_node(&Renderer::create_text_node(""));
             super::super::insert_node(&placeholder, parent, next_sibling.get());
             self.node_ref.set(Some(placeholder));
             let scope = mountable.mount(
@@ -111,6 +112,25 @@ mod vcomp {
             self.scope = Some(scope);
             self.node_ref.clone()
         }
+
+        fn hydrate(
+            &mut self,
+            parent_scope: &AnyScope,
+            parent: &Element,
+            next_sibling: NodeRef,
+            cursor: &mut Option<Node>,
+        ) -> NodeRef {
+            let mountable = self.props.take().expect("VComp has already been mounted");
+            let scope = mountable.hydrate(
+                self.node_ref.clone(),
+                parent_scope,
+                parent.to_owned(),
+                next_sibling,
+                cursor,
+            );
+            self.scope = Some(scope);
+            self.node_ref.clone()
+        }
     }
 
     impl PartialEq for VComp {
@@ -188,6 +208,14 @@ mod mountable {
             parent: Element,
             next_sibling: NodeRef,
         ) -> Box<dyn Scoped>;
+        fn hydrate(
+            self: Box<Self>,
+            node_ref: NodeRef,
+            parent_scope: &AnyScope,
+            parent: Element,
+            next_sibling: NodeRef,
+            cursor: &mut Option<Node>,
+        ) -> Box<dyn Scoped>;
         fn reuse(self: Box<Self>, node_ref: NodeRef, scope: &dyn Scoped, next_sibling: NodeRef);
     }
 
@@ -216,6 +244,9 @@ mod mountable {
             parent: Element,
             next_sibling: NodeRef,
         ) -> Box<dyn Scoped> {
+            #[cfg(feature = "static_render")]
+            crate::state::capture_props::<COMP>(&self.props);
+
             let scope: Scope<COMP> = Scope::new(Some(parent_scope.clone()));
             let scope = scope.mount_in_place(
                 parent,
@@ -228,6 +259,20 @@ mod mountable {
             Box::new(scope)
         }
 
+        fn hydrate(
+            self: Box<Self>,
+            node_ref: NodeRef,
+            parent_scope: &AnyScope,
+            parent: Element,
+            next_sibling: NodeRef,
+            cursor: &mut Option<Node>,
+        ) -> Box<dyn Scoped> {
+            let scope: Scope<COMP> = Scope::new(Some(parent_scope.clone()));
+            let scope = scope.hydrate_in_place(parent, next_sibling, node_ref, self.props, cursor);
+
+            Box::new(scope)
+        }
+
         fn reuse(self: Box<Self>, node_ref: NodeRef, scope: &dyn Scoped, next_sibling: NodeRef) {
             let scope: Scope<COMP> = scope.to_any().downcast();
             scope.update(ComponentUpdate::Properties(
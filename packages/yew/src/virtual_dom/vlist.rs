@@ -1,7 +1,7 @@
 //! This module contains fragments implementation.
 use super::{Key, VDiff, VNode, VText};
 use crate::html::{AnyScope, NodeRef};
-use crate::backend::{Element};
+use crate::backend::{Element, Node};
 use std::collections::{HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
 
@@ -226,4 +226,36 @@ impl VDiff for VList {
         assert!(!nodes.is_empty(), "VList should have at least one child");
         nodes.swap_remove(0)
     }
+
+    fn hydrate(
+        &mut self,
+        parent_scope: &AnyScope,
+        parent: &Element,
+        next_sibling: NodeRef,
+        cursor: &mut Option<Node>,
+    ) -> NodeRef {
+        if self.children.is_empty() {
+            // Stake out a place the same way `apply` does
+            let placeholder = VText::new("");
+            self.children.push(placeholder.into());
+        }
+
+        // Children are hydrated from left to right, so the `next_sibling` of each child is
+        // linked to the node of the following child once that one has been hydrated.
+        let mut last_next_sibling = NodeRef::default();
+        let mut nodes: Vec<NodeRef> = self
+            .children
+            .iter_mut()
+            .map(|child| {
+                let new_next_sibling = NodeRef::default();
+                let node = child.hydrate(parent_scope, parent, new_next_sibling.clone(), cursor);
+                last_next_sibling.link(node.clone());
+                last_next_sibling = new_next_sibling;
+                node
+            })
+            .collect();
+        last_next_sibling.link(next_sibling);
+
+        nodes.swap_remove(0)
+    }
 }
@@ -1,13 +1,13 @@
 //! This module contains the implementation of abstract virtual node.
 
-use super::{Key, VChild, VComp, VDiff, VList, VTag, VText};
+use super::{Key, VChild, VComp, VDiff, VList, VRaw, VTag, VText};
 use crate::{
-    backend::{DomBackend, Node, Element},
+    backend::{DomBackend, Element, Node, Renderer},
     html::{AnyScope, Component, NodeRef},
+    hydration,
 };
-use cfg_if::cfg_if;
-use cfg_match::cfg_match;
 use log::warn;
+use std::borrow::Cow;
 use std::cmp::PartialEq;
 use std::fmt;
 use std::iter::FromIterator;
@@ -25,14 +25,30 @@ pub enum VNode {
     VList(VList),
     /// A holder for any `Node` (necessary for replacing node).
     VRef(Node),
+    /// A holder for a string of HTML.
+    VRaw(VRaw),
 }
 
 impl VNode {
+    /// Creates a node which renders `html` as it is.
+    ///
+    /// The HTML isn't escaped or checked, so it must come from a trusted source. See `VRaw`.
+    ///
+    /// # Example
+    /// ```
+    /// # use yew::Html;
+    /// let html = Html::from_html_unchecked("<p>Rendered from <em>Markdown</em></p>");
+    /// ```
+    pub fn from_html_unchecked(html: impl Into<Cow<'static, str>>) -> Self {
+        VNode::VRaw(VRaw::new(html))
+    }
+
     pub fn key(&self) -> Option<Key> {
         match self {
             VNode::VComp(vcomp) => vcomp.key.clone(),
             VNode::VList(vlist) => vlist.key.clone(),
             VNode::VRef(_) => None,
+            VNode::VRaw(_) => None,
             VNode::VTag(vtag) => vtag.key.clone(),
             VNode::VText(_) => None,
         }
@@ -41,27 +57,20 @@ impl VNode {
     /// Returns the first DOM node that is used to designate the position of the virtual DOM node.
     pub(crate) fn first_node(&self) -> Node {
         match self {
-            VNode::VTag(vtag) => vtag
-                .reference
-                .as_ref()
-                .expect("VTag is not mounted")
-                .clone()
-                .into(),
+            VNode::VTag(vtag) => {
+                Renderer::element_as_node(vtag.reference.as_ref().expect("VTag is not mounted"))
+            }
             VNode::VText(vtext) => {
-                let text_node = vtext.reference.as_ref().expect("VText is not mounted");
-                cfg_match! {
-                    feature = "std_web" => text_node.as_node().clone(),
-                    feature = "web_sys" => text_node.clone().into(),
-                    feature = "static_render" => text_node.clone().into(),
-                }
+                Renderer::text_as_node(vtext.reference.as_ref().expect("VText is not mounted"))
             }
             VNode::VComp(vcomp) => vcomp.node_ref.get().expect("VComp is not mounted"),
             VNode::VList(vlist) => vlist
                 .children
-                .get(0)
+                .first()
                 .expect("VList is not mounted")
                 .first_node(),
             VNode::VRef(node) => node.clone(),
+            VNode::VRaw(vraw) => vraw.reference.first().expect("VRaw is not mounted").clone(),
         }
     }
 
@@ -78,6 +87,11 @@ impl VNode {
                     .expect("VComp has no root vnode")
                     .move_before(parent, next_sibling);
             }
+            VNode::VRaw(vraw) => {
+                for node in vraw.reference.iter() {
+                    super::insert_node(node, parent, next_sibling.clone());
+                }
+            }
             _ => super::insert_node(&self.first_node(), parent, next_sibling),
         };
     }
@@ -92,10 +106,11 @@ impl VDiff for VNode {
             VNode::VComp(ref mut vcomp) => vcomp.detach(parent),
             VNode::VList(ref mut vlist) => vlist.detach(parent),
             VNode::VRef(ref node) => {
-                if parent.remove_child(node).is_err() {
+                if Renderer::element_remove_child(parent, node).is_err() {
                     warn!("Node not found to remove VRef");
                 }
             }
+            VNode::VRaw(ref mut vraw) => vraw.detach(parent),
         }
     }
 
@@ -129,6 +144,36 @@ impl VDiff for VNode {
                 super::insert_node(node, parent, next_sibling.get());
                 NodeRef::new(node.clone())
             }
+            VNode::VRaw(ref mut vraw) => vraw.apply(parent_scope, parent, next_sibling, ancestor),
+        }
+    }
+
+    fn hydrate(
+        &mut self,
+        parent_scope: &AnyScope,
+        parent: &Element,
+        next_sibling: NodeRef,
+        cursor: &mut Option<Node>,
+    ) -> NodeRef {
+        match *self {
+            VNode::VTag(ref mut vtag) => vtag.hydrate(parent_scope, parent, next_sibling, cursor),
+            VNode::VText(ref mut vtext) => {
+                vtext.hydrate(parent_scope, parent, next_sibling, cursor)
+            }
+            VNode::VComp(ref mut vcomp) => {
+                vcomp.hydrate(parent_scope, parent, next_sibling, cursor)
+            }
+            VNode::VList(ref mut vlist) => {
+                vlist.hydrate(parent_scope, parent, next_sibling, cursor)
+            }
+            // The node of a `VRef` belongs to the client, so it takes the place of the copy
+            // the server wrote
+            VNode::VRef(ref node) => {
+                hydration::skip_separator(parent, cursor);
+                hydration::replace(Some(parent_scope), parent, cursor, node);
+                NodeRef::new(node.clone())
+            }
+            VNode::VRaw(ref mut vraw) => vraw.hydrate(parent_scope, parent, next_sibling, cursor),
         }
     }
 }
@@ -157,6 +202,12 @@ impl From<VTag> for VNode {
     }
 }
 
+impl From<VRaw> for VNode {
+    fn from(vraw: VRaw) -> Self {
+        VNode::VRaw(vraw)
+    }
+}
+
 impl From<VComp> for VNode {
     fn from(vcomp: VComp) -> Self {
         VNode::VComp(vcomp)
@@ -196,6 +247,7 @@ impl fmt::Debug for VNode {
             VNode::VComp(ref vcomp) => vcomp.fmt(f),
             VNode::VList(ref vlist) => vlist.fmt(f),
             VNode::VRef(ref vref) => vref.fmt(f),
+            VNode::VRaw(ref vraw) => vraw.fmt(f),
         }
     }
 }
@@ -207,6 +259,7 @@ impl PartialEq for VNode {
             (VNode::VText(a), VNode::VText(b)) => a == b,
             (VNode::VList(a), VNode::VList(b)) => a == b,
             (VNode::VRef(a), VNode::VRef(b)) => a == b,
+            (VNode::VRaw(a), VNode::VRaw(b)) => a == b,
             // TODO: Need to improve PartialEq for VComp before enabling.
             (VNode::VComp(_), VNode::VComp(_)) => false,
             _ => false,
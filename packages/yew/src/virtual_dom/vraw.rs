@@ -0,0 +1,267 @@
+//! This module contains the implementation of a raw HTML node `VRaw`.
+
+use super::{VDiff, VNode};
+use crate::backend::{DomBackend, Element, Node, Renderer};
+use crate::html::{AnyScope, NodeRef};
+use crate::hydration;
+use log::warn;
+use std::borrow::Cow;
+use std::cmp::PartialEq;
+use std::mem;
+
+/// A virtual node which holds a string of HTML.
+///
+/// The HTML is written verbatim when rendering on the server and parsed into DOM nodes when
+/// mounting on the client. It is neither escaped nor checked in any way, so it must never
+/// contain untrusted input.
+///
+/// A `VRaw` is only re-rendered when its HTML changes. When hydrating, the nodes which the
+/// server wrote for it are adopted as they are.
+#[derive(Clone, Debug)]
+pub struct VRaw {
+    /// The HTML of the node.
+    pub html: Cow<'static, str>,
+    /// The DOM nodes the HTML was parsed into.
+    pub reference: Vec<Node>,
+}
+
+impl VRaw {
+    /// Creates a virtual node which holds `html`.
+    pub fn new(html: impl Into<Cow<'static, str>>) -> Self {
+        VRaw {
+            html: html.into(),
+            reference: Vec::new(),
+        }
+    }
+
+    /// Parses the HTML. HTML without nodes is represented by an empty text node, so that there
+    /// always is a node which designates the position of the `VRaw`.
+    fn parse(&self) -> Vec<Node> {
+        let nodes = Renderer::parse_html(&self.html);
+        if nodes.is_empty() {
+            vec![Renderer::text_as_node(&Renderer::create_text_node(""))]
+        } else {
+            nodes
+        }
+    }
+}
+
+impl VDiff for VRaw {
+    /// Remove the nodes of the VRaw from parent.
+    fn detach(&mut self, parent: &Element) {
+        for node in self.reference.drain(..) {
+            if Renderer::element_remove_child(parent, &node).is_err() {
+                warn!("Node not found to remove VRaw");
+            }
+        }
+    }
+
+    /// Parses the HTML into new nodes unless the `ancestor` holds the same HTML.
+    fn apply(
+        &mut self,
+        _parent_scope: &AnyScope,
+        parent: &Element,
+        next_sibling: NodeRef,
+        ancestor: Option<VNode>,
+    ) -> NodeRef {
+        if let Some(mut ancestor) = ancestor {
+            if let VNode::VRaw(vraw) = &mut ancestor {
+                if vraw.html == self.html {
+                    self.reference = mem::take(&mut vraw.reference);
+                    return NodeRef::new(self.reference[0].clone());
+                }
+            }
+            ancestor.detach(parent);
+        }
+
+        self.reference = self.parse();
+        let next_sibling = next_sibling.get();
+        for node in self.reference.iter() {
+            super::insert_node(node, parent, next_sibling.clone());
+        }
+        NodeRef::new(self.reference[0].clone())
+    }
+
+    /// Adopts one node at `cursor` for every node the HTML consists of. Nodes which are of
+    /// another type or name than the parsed ones are replaced, the content of adopted nodes
+    /// isn't compared. Empty HTML takes the place of the placeholder comment rendered for it.
+    fn hydrate(
+        &mut self,
+        parent_scope: &AnyScope,
+        parent: &Element,
+        _next_sibling: NodeRef,
+        cursor: &mut Option<Node>,
+    ) -> NodeRef {
+        hydration::skip_separator(parent, cursor);
+        let parsed = Renderer::parse_html(&self.html);
+        if parsed.is_empty() {
+            if let Some(placeholder) = hydration::claim_placeholder(parent, cursor) {
+                self.reference = vec![Renderer::text_as_node(&placeholder)];
+                return NodeRef::new(self.reference[0].clone());
+            }
+            let expected = hydration::describe_text("");
+            hydration::report(Some(parent_scope), parent, cursor.as_ref(), expected);
+            let next_sibling = NodeRef::default();
+            next_sibling.set(cursor.clone());
+            return self.apply(parent_scope, parent, next_sibling, None);
+        }
+
+        self.reference = parsed
+            .into_iter()
+            .map(|node| {
+                let existing = cursor.take();
+                *cursor = existing.as_ref().and_then(Renderer::node_next_sibling);
+                match existing {
+                    Some(existing) if hydration::same_kind(&existing, &node) => existing,
+                    existing => {
+                        let expected = hydration::describe_node(Some(&node));
+                        hydration::report(Some(parent_scope), parent, existing.as_ref(), expected);
+                        super::insert_node(&node, parent, existing.clone());
+                        if let Some(existing) = existing {
+                            Renderer::element_remove_child(parent, &existing)
+                                .expect("can't remove a child");
+                        }
+                        node
+                    }
+                }
+            })
+            .collect();
+        NodeRef::new(self.reference[0].clone())
+    }
+}
+
+impl PartialEq for VRaw {
+    fn eq(&self, other: &VRaw) -> bool {
+        self.html == other.html
+    }
+}
+
+#[cfg(all(test, yew_backend = "static_render"))]
+mod tests {
+    use crate::backend::Node;
+    use crate::html::{Component, ComponentLink, Html, ShouldRender};
+    use crate::{html, hydration::MismatchPolicy, utils::document, App};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Article {
+        html: &'static str,
+    }
+
+    impl Component for Article {
+        type Message = &'static str;
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Article {
+                html: "<h1>Title</h1><p>A <em>short</em> text</p>",
+            }
+        }
+
+        fn update(&mut self, html: Self::Message) -> ShouldRender {
+            self.html = html;
+            true
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <article>
+                    { Html::from_html_unchecked(self.html) }
+                    <footer>{ "end" }</footer>
+                </article>
+            }
+        }
+    }
+
+    fn children(element: &crate::backend::Element) -> Vec<Node> {
+        element
+            .query_selector("article")
+            .unwrap()
+            .unwrap()
+            .child_nodes()
+    }
+
+    #[test]
+    fn raw_html_is_rendered_and_diffed_by_string() {
+        let root = document().create_element("div").unwrap();
+        let link = App::<Article>::new().mount(root.clone());
+        let article = root.query_selector("article").unwrap().unwrap();
+        assert_eq!(article.text_content().unwrap(), "TitleA short textend");
+        let nodes = children(&root);
+        assert_eq!(nodes.len(), 3);
+
+        link.send_message("<h1>Title</h1><p>A <em>short</em> text</p>");
+        assert_eq!(children(&root), nodes);
+
+        link.send_message("<h2>Other</h2>");
+        let changed = children(&root);
+        assert_eq!(changed.len(), 2);
+        assert_ne!(changed[0], nodes[0]);
+        assert_eq!(changed[1], nodes[2]);
+        assert_eq!(article.text_content().unwrap(), "Otherend");
+
+        link.send_message("");
+        assert_eq!(article.text_content().unwrap(), "end");
+    }
+
+    #[test]
+    fn hydration_adopts_the_server_markup() {
+        let markup = crate::render_to_string::<Article>(()).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+        let nodes = children(&root);
+
+        let link = App::<Article>::new().hydrate(root.clone());
+        assert_eq!(children(&root), nodes);
+
+        link.send_message("<h2>Other</h2>");
+        let article = root.query_selector("article").unwrap().unwrap();
+        assert_eq!(article.text_content().unwrap(), "Otherend");
+    }
+
+    struct Notes;
+
+    impl Component for Notes {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Notes
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn change(&mut self, _: Self::Properties) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {
+                <article>{ Html::from_html_unchecked("<!-- generated --><p>text</p>") }</article>
+            }
+        }
+    }
+
+    #[test]
+    fn hydration_adopts_comments() {
+        let markup = crate::render_to_string::<Notes>(()).unwrap();
+        let root = document().create_element("div").unwrap();
+        root.set_inner_html(markup.as_str());
+        let nodes = children(&root);
+
+        App::<Notes>::new()
+            .with_mismatch_policy(MismatchPolicy::Panic)
+            .hydrate(root.clone());
+        assert_eq!(children(&root), nodes);
+    }
+}
@@ -1,28 +1,102 @@
 //! This module contains the implementation of a virtual element node `VTag`.
 
 use super::{
-    Attributes, Key, Patch, PositionalAttr, Transformer, VDiff, VList, VNode,
+    Attributes, Key, Patch, PositionalAttr, VDiff, VList, VNode,
 };
 use super::listener::{Listener, Listeners};
 
 use crate::{
-    html::{AnyScope, NodeRef, EventListener},
-    backend::{Element, Renderer, InputElement, ButtonElement, TextAreaElement}
+    backend::{DomBackend, Element, InputElement, Node, Renderer},
+    html::{AnyScope, EventListener, NodeRef},
+    hydration,
 };
-use cfg_if::cfg_if;
 use log::warn;
 use std::borrow::Cow;
 use std::cmp::PartialEq;
 use std::rc::Rc;
-use std::ops::Deref;
-use wasm_bindgen::JsCast;
 
 /// SVG namespace string used for creating svg elements
 pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
 
+/// MathML namespace string used for creating MathML elements
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
 /// Default namespace for html elements
 pub const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
 
+/// XLink namespace string used for `xlink:` prefixed attributes
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// Returns the namespace of an element named `tag` whose parent's children are in `context`.
+/// `<svg>` and `<math>` start their namespaces, any other element stays in the context.
+pub(crate) fn element_namespace(tag: &str, context: &'static str) -> &'static str {
+    if tag.eq_ignore_ascii_case("svg") {
+        SVG_NAMESPACE
+    } else if tag.eq_ignore_ascii_case("math") {
+        MATHML_NAMESPACE
+    } else {
+        context
+    }
+}
+
+/// Returns the namespace of the children of an element named `tag` in `namespace`. Like in
+/// the HTML parser, `<foreignObject>` and the MathML text elements contain HTML again.
+pub(crate) fn children_namespace(namespace: &str, tag: &str) -> &'static str {
+    match namespace {
+        SVG_NAMESPACE if matches!(tag, "foreignObject" | "desc" | "title") => HTML_NAMESPACE,
+        SVG_NAMESPACE => SVG_NAMESPACE,
+        MATHML_NAMESPACE if matches!(tag, "mi" | "mo" | "mn" | "ms" | "mtext") => HTML_NAMESPACE,
+        MATHML_NAMESPACE => MATHML_NAMESPACE,
+        _ => HTML_NAMESPACE,
+    }
+}
+
+/// Tag and attribute names must be non-empty and must not contain whitespace, quotes or
+/// characters which have a meaning in markup.
+#[cfg(feature = "static_render")]
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '"' | '\'' | '<' | '>' | '/' | '=' | '&')
+        })
+}
+
+/// Returns `true` for the [void elements](https://html.spec.whatwg.org/#void-elements), which
+/// consist of a start tag only.
+#[cfg(feature = "static_render")]
+pub(crate) fn is_void_element(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Returns `true` for the [raw text elements](https://html.spec.whatwg.org/#raw-text-elements)
+/// and the elements which the HTML parser treats like them, whose text content is not parsed for
+/// markup or entities. `<noscript>` is one of them since scripting is enabled wherever Yew runs.
+#[cfg(feature = "static_render")]
+pub(crate) fn is_raw_text_element(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "script" | "style" | "xmp" | "iframe" | "noembed" | "noframes" | "noscript"
+    )
+}
+
 /// Used to improve performance of runtime element checks
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum ElementType {
@@ -229,6 +303,19 @@ impl VTag {
         }
     }
 
+    /// Returns `true` if this tag sets the attribute `name` when it is rendered.
+    fn renders_attribute(&self, name: &str) -> bool {
+        match name {
+            "type" if self.kind.is_some() => true,
+            "value" if self.value.is_some() => true,
+            "checked" if self.checked => true,
+            _ => self
+                .attributes
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case(name)),
+        }
+    }
+
     fn refresh_value(&mut self) {
         // Don't refresh value if the element is not controlled
         if self.value.is_none() {
@@ -236,29 +323,17 @@ impl VTag {
         }
 
         if let Some(element) = self.reference.as_ref() {
-            // if self.element_type == ElementType::Input {
-            //     let input_el = InputElement::try_from(element.clone()).ok();
-            //     // let input_el = cfg_match! {
-            //     //     feature = "std_web" => InputElement::try_from(element.clone()).ok(),
-            //     //     feature = "web_sys" => element.dyn_ref::<InputElement>(),
-            //     // };
-            //     if let Some(input) = input_el {
-            //         // let current_value = cfg_match! {
-            //         //     feature = "std_web" => input.raw_value(),
-            //         //     feature = "web_sys" => input.value(),
-            //         // };
-            //         self.set_value(&current_value)
-            //     }
-            // } else if self.element_type == ElementType::Textarea {
-            //     // let textarea_el = cfg_match! {
-            //     //     feature = "std_web" => TextAreaElement::try_from(element.clone()).ok(),
-            //     //     feature = "web_sys" => element.dyn_ref::<TextAreaElement>(),
-            //     // };
-            //     if let Some(tae) = textarea_el {
-            //         let current_value = &tae.value();
-            //         self.set_value(&current_value)
-            //     }
-            // }
+            if self.element_type == ElementType::Input {
+                if let Some(input) = Renderer::element_as_input(element) {
+                    let current_value = Renderer::input_value(&input);
+                    self.set_value(&current_value)
+                }
+            } else if self.element_type == ElementType::Textarea {
+                if let Some(tae) = Renderer::element_as_textarea(element) {
+                    let current_value = Renderer::textarea_value(&tae);
+                    self.set_value(&current_value)
+                }
+            }
         }
     }
 
@@ -268,14 +343,14 @@ impl VTag {
             self.kind.as_ref(),
             ancestor.as_ref().and_then(|anc| anc.kind.as_ref()),
         ) {
-            (Some(ref left), Some(ref right)) => {
+            (Some(left), Some(right)) => {
                 if left != right {
                     Some(Patch::Replace(&**left, ()))
                 } else {
                     None
                 }
             }
-            (Some(ref left), None) => Some(Patch::Add(&**left, ())),
+            (Some(left), None) => Some(Patch::Add(&**left, ())),
             (None, Some(right)) => Some(Patch::Remove(&**right)),
             (None, None) => None,
         }
@@ -287,14 +362,14 @@ impl VTag {
             self.value.as_ref(),
             ancestor.as_ref().and_then(|anc| anc.value.as_ref()),
         ) {
-            (Some(ref left), Some(ref right)) => {
+            (Some(left), Some(right)) => {
                 if left != right {
                     Some(Patch::Replace(&**left, ()))
                 } else {
                     None
                 }
             }
-            (Some(ref left), None) => Some(Patch::Add(&**left, ())),
+            (Some(left), None) => Some(Patch::Add(&**left, ())),
             (None, Some(right)) => Some(Patch::Remove(&**right)),
             (None, None) => None,
         }
@@ -315,29 +390,22 @@ impl VTag {
         for change in changes {
             match change {
                 Patch::Add(key, value) | Patch::Replace(key, value) => {
-                    element
-                        .set_attribute(&key, &value)
+                    Renderer::element_set_attribute(element, key, value)
                         .expect("invalid attribute key");
                 }
-                Patch::Remove(key) => element
-                    .remove_attribute(&key)
+                Patch::Remove(key) => Renderer::element_remove_attribute(element, key)
                     .expect("could not remove attribute"),
             }
         }
 
-        // TODO: add std_web after https://github.com/koute/stdweb/issues/395 will be approved
-        // Check this out: https://github.com/yewstack/yew/pull/1033/commits/4b4e958bb1ccac0524eb20f63f06ae394c20553d
-        #[cfg(feature = "web_sys")]
-        {
-            if self.element_type == ElementType::Button {
-                if let Some(button) = element.dyn_ref::<ButtonElement>() {
-                    if let Some(change) = self.diff_kind(ancestor) {
-                        let kind = match change {
-                            Patch::Add(kind, _) | Patch::Replace(kind, _) => kind,
-                            Patch::Remove(_) => "",
-                        };
-                        button.set_type(kind);
-                    }
+        if self.element_type == ElementType::Button {
+            if let Some(button) = Renderer::element_as_button(element) {
+                if let Some(change) = self.diff_kind(ancestor) {
+                    let kind = match change {
+                        Patch::Add(kind, _) | Patch::Replace(kind, _) => kind,
+                        Patch::Remove(_) => "",
+                    };
+                    Renderer::button_set_type(&button, kind);
                 }
             }
         }
@@ -347,25 +415,13 @@ impl VTag {
         // and useful in templates. For example I interpret `checked`
         // attribute as `checked` parameter, not `defaultChecked` as browsers do
         if self.element_type == ElementType::Input {
-            if let Some(input) = element.dyn_ref::<InputElement>() {
+            if let Some(input) = Renderer::element_as_input(element) {
                 if let Some(change) = self.diff_kind(ancestor) {
                     let kind = match change {
                         Patch::Add(kind, _) | Patch::Replace(kind, _) => kind,
                         Patch::Remove(_) => "",
                     };
-                    input.set_type(kind)
-                    // cfg_match! {
-                    //     feature = "std_web" => ({
-                    //         //https://github.com/koute/stdweb/commit/3b85c941db00b8e3c942624afd50c5929085fb08
-                    //         //input.set_kind(&kind);
-                    //         let input = &input;
-                    //         js! { @(no_return)
-                    //             @{input}.type = @{kind};
-                    //         }
-                    //     }),
-                    //     feature = "web_sys" => input.set_type(kind),
-                    //     feature = "static_render" => input.set_type(kind),
-                    // }
+                    Renderer::input_set_type(&input, kind)
                 }
 
                 if let Some(change) = self.diff_value(ancestor) {
@@ -373,12 +429,7 @@ impl VTag {
                         Patch::Add(kind, _) | Patch::Replace(kind, _) => kind,
                         Patch::Remove(_) => "",
                     };
-                    input.set_value(raw_value);
-                    // cfg_match! {
-                    //     feature = "std_web" => input.set_raw_value(raw_value),
-                    //     feature = "web_sys" => input.set_value(raw_value),
-                    //     feature = "static_render" => input.set_type(raw_value),
-                    // };
+                    Renderer::input_set_value(&input, raw_value);
                 }
 
                 // IMPORTANT! This parameter has to be set every time
@@ -386,20 +437,13 @@ impl VTag {
                 set_checked(&input, self.checked);
             }
         } else if self.element_type == ElementType::Textarea {
-            if let Some(tae) = {
-                element.dyn_ref::<TextAreaElement>()
-                // cfg_match! {
-                //     feature = "std_web" => TextAreaElement::try_from(element.clone()).ok(),
-                //     feature = "web_sys" => element.dyn_ref::<TextAreaElement>(),
-                //     feature = "static_render" => element.dyn_ref::<TextAreaElement>(),
-                // }
-            } {
+            if let Some(tae) = Renderer::element_as_textarea(element) {
                 if let Some(change) = self.diff_value(ancestor) {
                     let value = match change {
                         Patch::Add(kind, _) | Patch::Replace(kind, _) => kind,
                         Patch::Remove(_) => "",
                     };
-                    tae.set_value(value);
+                    Renderer::textarea_set_value(&tae, value);
                 }
             }
         }
@@ -407,20 +451,15 @@ impl VTag {
 
     fn create_element(&self, parent: &Element) -> Element {
         let tag = self.tag();
-        if tag == "svg"
-            || parent
-                .namespace_uri()
-                .map_or(false, |ns| ns == SVG_NAMESPACE)
-        {
-            let namespace = Some(SVG_NAMESPACE);
-
-            Renderer::get_document()
-                .create_element_ns(namespace, tag)
-                .expect("can't create namespaced element for vtag")
-        } else {
-            Renderer::get_document()
-                .create_element(tag)
-                .expect("can't create element for vtag")
+        let context = match Renderer::element_namespace_uri(parent) {
+            Some(namespace) => {
+                children_namespace(&namespace, &Renderer::element_local_name(parent))
+            }
+            None => HTML_NAMESPACE,
+        };
+        match element_namespace(tag, context) {
+            HTML_NAMESPACE => Renderer::create_element(tag),
+            namespace => Renderer::create_element_ns(Some(namespace), tag),
         }
     }
 }
@@ -438,7 +477,7 @@ mod diff {
 
             // recursively remove its children
             self.children.detach(&node);
-            if parent.remove_child(&node.into()).is_err() {
+            if Renderer::element_remove_child(parent, &Renderer::element_as_node(&node)).is_err() {
                 warn!("Node not found to remove VTag");
             }
             self.node_ref.set(None);
@@ -463,7 +502,7 @@ mod diff {
                     _ => {
                         let element = self.create_element(parent);
                         super::super::insert_node(
-                            (&element).into(),
+                            &Renderer::element_as_node(&element),
                             parent,
                             Some(ancestor.first_node()),
                         );
@@ -482,7 +521,11 @@ mod diff {
                 self.reference = ancestor_tag.reference.take();
             } else if self.reference.is_none() {
                 let element = self.create_element(parent);
-                super::super::insert_node((&element).into(), parent, next_sibling.get());
+                super::super::insert_node(
+                    &Renderer::element_as_node(&element),
+                    parent,
+                    next_sibling.get(),
+                );
                 self.reference = Some(element);
             }
 
@@ -502,22 +545,126 @@ mod diff {
                 ancestor_tag.children.detach(element);
             }
 
-            let node = element.as_node();
+            let node = Renderer::element_as_node(element);
+            self.node_ref.set(Some(node));
+            self.node_ref.clone()
+        }
+
+        /// Adopts the `Element` at `cursor` if it has the same tag, then hydrates its children.
+        /// Attributes which this tag doesn't render are removed from the element.
+        fn hydrate(
+            &mut self,
+            parent_scope: &AnyScope,
+            parent: &Element,
+            _next_sibling: NodeRef,
+            cursor: &mut Option<Node>,
+        ) -> NodeRef {
+            let element = cursor
+                .as_ref()
+                .and_then(Renderer::node_as_element)
+                .filter(|element| {
+                    Renderer::element_local_name(element).eq_ignore_ascii_case(self.tag())
+                });
+            let element = match element {
+                Some(element) => element,
+                None => {
+                    let expected = hydration::describe_element(self.tag());
+                    hydration::report(Some(parent_scope), parent, cursor.as_ref(), expected);
+                    return super::super::hydrate_mismatch(self, parent_scope, parent, cursor);
+                }
+            };
+            let node = Renderer::element_as_node(&element);
+            *cursor = Renderer::node_next_sibling(&node);
+
+            for name in Renderer::element_attribute_names(&element) {
+                if !self.renders_attribute(&name) {
+                    hydration::report_attribute(Some(parent_scope), parent, &element, &name, None);
+                    Renderer::element_remove_attribute(&element, &name)
+                        .expect("could not remove attribute");
+                }
+            }
+            for (key, value) in self.attributes.iter() {
+                if Renderer::element_get_attribute(&element, key).as_deref() != Some(value) {
+                    hydration::report_attribute(
+                        Some(parent_scope),
+                        parent,
+                        &element,
+                        key,
+                        Some(value),
+                    );
+                }
+            }
+
+            // The server writes the value of a `<textarea>` as its text, which is left to the
+            // element as its default value. The value itself is set with the other properties.
+            // The text of a `<title>` is written without markers, so the browser parses it into
+            // a single text node.
+            let is_textarea = self.element_type == ElementType::Textarea;
+            let is_title = self.tag() == "title"
+                && Renderer::element_namespace_uri(&element).as_deref() == Some(HTML_NAMESPACE);
+            let children = &mut self.children;
+            hydration::within(&element, || {
+                if is_textarea {
+                    return;
+                }
+                if is_title {
+                    hydrate_merged_text(parent_scope, children, &element);
+                    return;
+                }
+                let mut child_cursor = Renderer::element_first_child(&element);
+                if !children.is_empty() {
+                    children.hydrate(
+                        parent_scope,
+                        &element,
+                        NodeRef::default(),
+                        &mut child_cursor,
+                    );
+                }
+                super::super::remove_unclaimed(Some(parent_scope), &element, child_cursor);
+            });
 
-            // let node = cfg_match! {
-            //     feature = "std_web" => element.as_node(),
-            //     feature = "web_sys" => element.deref(),
-            //     feature = "static_render" => element.deref(),
-            // };
-            self.node_ref.set(Some(node.clone().into()));
+            self.reference = Some(element);
+            self.apply_diffs(&mut None);
+            self.recreate_listeners(&mut None);
+
+            self.node_ref.set(Some(node));
             self.node_ref.clone()
         }
     }
+
+    /// Renders `children` into `element` in place of the text the server merged them into.
+    /// Only a difference in the text as a whole is a mismatch.
+    fn hydrate_merged_text(parent_scope: &AnyScope, children: &mut VList, element: &Element) {
+        let child_nodes = || {
+            std::iter::successors(Renderer::element_first_child(element), |node| {
+                Renderer::node_next_sibling(node)
+            })
+        };
+        let text = |nodes: &[Node]| -> String {
+            nodes
+                .iter()
+                .filter_map(Renderer::node_as_text)
+                .map(|text| Renderer::text_data(&text))
+                .collect()
+        };
+
+        let server_nodes: Vec<_> = child_nodes().collect();
+        children.apply(parent_scope, element, NodeRef::default(), None);
+        let client_nodes: Vec<_> = child_nodes().skip(server_nodes.len()).collect();
+        let client_text = text(&client_nodes);
+        if text(&server_nodes) != client_text {
+            let expected = hydration::describe_text(&client_text);
+            hydration::report(Some(parent_scope), element, server_nodes.first(), expected);
+        }
+        for node in server_nodes {
+            Renderer::element_remove_child(element, &node).expect("can't remove a child");
+        }
+    }
 }
 
 /// Set `checked` value for the `InputElement`.
 fn set_checked(input: &InputElement, value: bool) {
-    input.set_checked(value)
+    Renderer::input_set_checked(input, value)
 }
 
 impl PartialEq for VTag {
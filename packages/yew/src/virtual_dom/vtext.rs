@@ -2,8 +2,8 @@
 
 use super::{VDiff, VNode};
 use crate::html::{AnyScope, NodeRef};
-use crate::backend::{Element, Renderer, TextNode};
-use cfg_if::cfg_if;
+use crate::backend::{DomBackend, Element, Node, Renderer, TextNode};
+use crate::hydration;
 use log::warn;
 use std::borrow::Cow;
 use std::cmp::PartialEq;
@@ -36,7 +36,7 @@ impl VDiff for VText {
             .reference
             .take()
             .expect("tried to remove not rendered VText from DOM");
-        if parent.remove_child(&node.into()).is_err() {
+        if Renderer::element_remove_child(parent, &Renderer::text_as_node(&node)).is_err() {
             warn!("Node not found to remove VText");
         }
     }
@@ -57,21 +57,58 @@ impl VDiff for VText {
                     .clone()
                     .expect("Rendered VText nodes should have a ref");
                 if self.text != vtext.text {
-                    text_node.set_node_value(Some(&self.text));
+                    Renderer::text_set_data(&text_node, &self.text);
                 }
 
-                return NodeRef::new(text_node.into());
+                return NodeRef::new(Renderer::text_as_node(&text_node));
             }
 
             ancestor.detach(parent);
         }
 
-        let text_node = Renderer::get_document().create_text_node(&self.text);
-        super::insert_node(&text_node, parent, next_sibling.get());
-        let text_node = Renderer::get_document().create_text_node(&self.text);
-        super::insert_node((&text_node).into(), parent, next_sibling.get());
-        self.reference = Some(text_node.clone());
-        NodeRef::new(text_node.into())
+        let text_node = Renderer::create_text_node(&self.text);
+        let node = Renderer::text_as_node(&text_node);
+        super::insert_node(&node, parent, next_sibling.get());
+        self.reference = Some(text_node);
+        NodeRef::new(node)
+    }
+
+    /// Adopts the `TextNode` at `cursor`, updating its data if it differs. Empty text takes the
+    /// place of the placeholder comment which was rendered for it.
+    fn hydrate(
+        &mut self,
+        parent_scope: &AnyScope,
+        parent: &Element,
+        _next_sibling: NodeRef,
+        cursor: &mut Option<Node>,
+    ) -> NodeRef {
+        hydration::skip_separator(parent, cursor);
+        let text_node = if self.text.is_empty() {
+            hydration::claim_placeholder(parent, cursor)
+        } else {
+            let text_node = cursor.as_ref().and_then(Renderer::node_as_text);
+            if let Some(text_node) = &text_node {
+                let node = Renderer::text_as_node(text_node);
+                *cursor = Renderer::node_next_sibling(&node);
+                if Renderer::text_data(text_node) != self.text {
+                    let expected = hydration::describe_text(&self.text);
+                    hydration::report(Some(parent_scope), parent, Some(&node), expected);
+                    Renderer::text_set_data(text_node, &self.text);
+                }
+            }
+            text_node
+        };
+        if let Some(text_node) = text_node {
+            let node = Renderer::text_as_node(&text_node);
+            self.reference = Some(text_node);
+            return NodeRef::new(node);
+        }
+
+        let expected = hydration::describe_text(&self.text);
+        hydration::report(Some(parent_scope), parent, cursor.as_ref(), expected);
+        let next_sibling = NodeRef::default();
+        next_sibling.set(cursor.clone());
+        self.apply(parent_scope, parent, next_sibling, None)
     }
 }
 